@@ -6,12 +6,16 @@ mod utils;
 
 use resp::{resp_desserializer, resp_serializer};
 use server::redis_app::RedisApp;
-use std::{env, sync::Arc};
+use std::{env, sync::Arc, time::Duration};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::TcpStream,
+    sync::watch,
 };
 use types::{connection_context::ConnectionContext, instance_type::InstanceType};
+
+const ACTIVE_EXPIRE_INTERVAL: Duration = Duration::from_millis(100);
+
 #[tokio::main]
 
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -27,6 +31,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         _ => {}
     }
 
+    tokio::spawn(active_expire_cycle(redis_app.clone()));
+
     loop {
         if let Ok((connection_stream, _)) = tcp_listener.accept().await {
             connection_counter += 1;
@@ -55,9 +61,21 @@ async fn handle_request(
 
         if let Some(token) = resp_desserializer::parse_resp_buffer(&stream_buffer[..read_result]) {
             let conn_addr = stream.peer_addr().unwrap().ip().to_string();
-            let context = ConnectionContext::new(connection_id, conn_addr);
-            let exec_response =
-                server::command_executor::execute_command(app.clone(), &token, context).await;
+            let (closed_sender, closed) = watch::channel(false);
+            let context = ConnectionContext::new(connection_id, conn_addr, closed);
+            let execution = server::command_executor::execute_command(app.clone(), &token, context);
+            tokio::pin!(execution);
+
+            // Commands always run to completion: a disconnect only ends the
+            // wait of a blocked command, which unregisters its waiter.
+            let exec_response = tokio::select! {
+                biased;
+                response = &mut execution => response,
+                _ = wait_for_disconnect(&stream) => {
+                    let _ = closed_sender.send(true);
+                    execution.await
+                }
+            };
 
             for response in exec_response.into_iter() {
                 println!("out> {:?}", response.clone());
@@ -70,6 +88,23 @@ async fn handle_request(
     }
 }
 
+async fn active_expire_cycle(app: Arc<RedisApp>) {
+    let mut interval = tokio::time::interval(ACTIVE_EXPIRE_INTERVAL);
+    loop {
+        interval.tick().await;
+        app.remove_expired().await;
+    }
+}
+
+async fn wait_for_disconnect(stream: &TcpStream) {
+    let mut buffer = [0; 1];
+    match stream.peek(&mut buffer).await {
+        Ok(0) | Err(_) => {}
+        // Pipelined data is left for the next read.
+        Ok(_) => std::future::pending().await,
+    }
+}
+
 async fn do_handshake(app: Arc<RedisApp>) -> Result<(), Box<dyn std::error::Error>> {
     let master_address = app.get_master_conn().unwrap();
     let mut stream = TcpStream::connect(master_address).await?;
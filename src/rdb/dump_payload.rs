@@ -0,0 +1,104 @@
+use std::io::Cursor;
+
+use crate::types::{redis_error::RedisError, value_container::ValueContainer};
+
+use super::{crc64, file_extensions::FileExt, rdb_loader, rdb_types::ValueType, rdb_writer};
+
+const RDB_VERSION: u16 = 12;
+const FOOTER_SIZE: usize = 10;
+
+pub fn serialize(value: &ValueContainer) -> Result<Vec<u8>, RedisError> {
+    let mut payload = vec![rdb_writer::value_type(value)? as u8];
+    rdb_writer::write_object(&mut payload, value)?;
+
+    payload.extend_from_slice(&RDB_VERSION.to_le_bytes());
+    let crc = crc64::crc64(0, &payload);
+    payload.extend_from_slice(&crc.to_le_bytes());
+
+    Ok(payload)
+}
+
+pub fn deserialize(payload: &[u8]) -> Result<ValueContainer, RedisError> {
+    if payload.len() < FOOTER_SIZE + 1 {
+        return Err(RedisError::InvalidDumpPayload);
+    }
+
+    let body_len = payload.len() - FOOTER_SIZE;
+    let version = u16::from_le_bytes([payload[body_len], payload[body_len + 1]]);
+    let mut crc = [0u8; 8];
+    crc.copy_from_slice(&payload[body_len + 2..]);
+
+    if version > RDB_VERSION || crc64::crc64(0, &payload[..body_len + 2]) != u64::from_le_bytes(crc)
+    {
+        return Err(RedisError::InvalidDumpPayload);
+    }
+
+    let mut body = Cursor::new(&payload[..body_len]);
+    let value_type = ValueType::try_from(body.next_u8()?)?;
+    let value = rdb_loader::read_object(&mut body, value_type)?;
+
+    if body.position() as usize != body_len {
+        return Err(RedisError::InvalidDumpPayload);
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{
+        stream::{ConsumerGroup, RedisStream},
+        stream_entry::StreamEntry,
+        stream_key::StreamKey,
+    };
+
+    use super::*;
+
+    #[test]
+    fn stream_round_trips_with_groups_and_pending_entries() {
+        let mut stream = RedisStream::new();
+        for sequence in 1..=3 {
+            stream.push(StreamEntry {
+                id: StreamKey::new(1, sequence),
+                fields: vec![("field".into(), sequence.to_string())],
+            });
+        }
+        let mut group = ConsumerGroup::new(StreamKey::new(1, 2));
+        group.touch_consumer("alice", 100);
+        group.touch_consumer("bob", 200);
+        group.deliver("alice", StreamKey::new(1, 1), 100);
+        group.deliver("alice", StreamKey::new(1, 1), 150);
+        group.deliver("bob", StreamKey::new(1, 2), 200);
+        stream.groups.insert("group".into(), group);
+
+        let payload = serialize(&ValueContainer::Stream(stream)).unwrap();
+        let ValueContainer::Stream(restored) = deserialize(&payload).unwrap() else {
+            panic!("restored value is not a stream");
+        };
+
+        let ids: Vec<StreamKey> = restored.entries().iter().map(|entry| entry.id).collect();
+        assert_eq!(
+            ids,
+            (1..=3)
+                .map(|seq| StreamKey::new(1, seq))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            restored.entries()[2].fields,
+            vec![("field".into(), "3".into())]
+        );
+        assert_eq!(restored.last_id(), Some(StreamKey::new(1, 3)));
+
+        let group = &restored.groups["group"];
+        assert_eq!(group.last_delivered, StreamKey::new(1, 2));
+        assert_eq!(group.consumers["alice"].seen_at, 100);
+        assert_eq!(group.consumers["bob"].seen_at, 200);
+
+        let pending = &group.pending[&StreamKey::new(1, 1)];
+        assert_eq!(pending.consumer, "alice");
+        assert_eq!(pending.delivered_at, 150);
+        assert_eq!(pending.delivery_count, 2);
+        assert_eq!(group.pending[&StreamKey::new(1, 2)].consumer, "bob");
+        assert_eq!(group.pending.len(), 2);
+    }
+}
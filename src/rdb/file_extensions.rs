@@ -1,15 +1,15 @@
-use std::{
-    fs::File,
-    io::{Read, Seek},
-};
+use std::io::{ErrorKind, Read, Seek};
 
-use crate::types::redis_error::RedisError;
+use crate::{types::redis_error::RedisError, utils};
 
 pub trait FileExt {
     fn next_string(&mut self, len: usize) -> Result<String, RedisError>;
+    fn next_bytes(&mut self, len: usize) -> Result<Vec<u8>, RedisError>;
     fn next_u64(&mut self) -> Result<u64, RedisError>;
     fn next_u32(&mut self) -> Result<u32, RedisError>;
     fn next_u8(&mut self) -> Result<u8, RedisError>;
+    fn next_u32_be(&mut self) -> Result<u32, RedisError>;
+    fn next_u64_be(&mut self) -> Result<u64, RedisError>;
 
     fn next_i32(&mut self) -> Result<i32, RedisError>;
     fn next_i16(&mut self) -> Result<i16, RedisError>;
@@ -17,14 +17,23 @@ pub trait FileExt {
     fn peek(&mut self) -> Result<u8, RedisError>;
 }
 
-impl FileExt for File {
+impl<T: Read + Seek> FileExt for T {
     fn next_string(&mut self, len: usize) -> Result<String, RedisError> {
-        let mut buffer = vec![0; len];
+        Ok(utils::bytes_to_string(&self.next_bytes(len)?))
+    }
 
-        self.read_exact(&mut buffer)
-            .map_err(|err| RedisError::IOError(err))?;
-        let parsed = String::from_utf8(buffer).map_err(|_| RedisError::ParsingError)?;
-        Ok(parsed)
+    // Lengths come from the payload, so the buffer only grows with the bytes
+    // actually read instead of being allocated up front.
+    fn next_bytes(&mut self, len: usize) -> Result<Vec<u8>, RedisError> {
+        let mut buffer = Vec::new();
+        self.by_ref()
+            .take(len as u64)
+            .read_to_end(&mut buffer)
+            .map_err(RedisError::IOError)?;
+        if buffer.len() < len {
+            return Err(RedisError::IOError(ErrorKind::UnexpectedEof.into()));
+        }
+        Ok(buffer)
     }
 
     fn next_u64(&mut self) -> Result<u64, RedisError> {
@@ -52,6 +61,18 @@ impl FileExt for File {
         Ok(buffer[0])
     }
 
+    fn next_u32_be(&mut self) -> Result<u32, RedisError> {
+        let mut buffer = [0u8; 4];
+        self.read_exact(&mut buffer).map_err(RedisError::IOError)?;
+        Ok(u32::from_be_bytes(buffer))
+    }
+
+    fn next_u64_be(&mut self) -> Result<u64, RedisError> {
+        let mut buffer = [0u8; 8];
+        self.read_exact(&mut buffer).map_err(RedisError::IOError)?;
+        Ok(u64::from_be_bytes(buffer))
+    }
+
     fn next_i32(&mut self) -> Result<i32, RedisError> {
         let mut buffer = [0u8; 4];
         self.read_exact(&mut buffer)
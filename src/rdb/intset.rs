@@ -0,0 +1,28 @@
+use crate::types::redis_error::RedisError;
+
+const INTSET_HEADER_SIZE: usize = 8;
+
+// Decodes an intset blob: a little-endian encoding width (2, 4 or 8 bytes), a
+// member count and the sorted members themselves.
+pub(crate) fn decode(bytes: &[u8]) -> Result<Vec<i64>, RedisError> {
+    if bytes.len() < INTSET_HEADER_SIZE {
+        return Err(RedisError::RDBInvalidIntSet);
+    }
+
+    let width = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+    let len = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+
+    if !matches!(width, 2 | 4 | 8) || bytes.len() != INTSET_HEADER_SIZE + width * len {
+        return Err(RedisError::RDBInvalidIntSet);
+    }
+
+    let ints = bytes[INTSET_HEADER_SIZE..]
+        .chunks_exact(width)
+        .map(|chunk| match width {
+            2 => i16::from_le_bytes([chunk[0], chunk[1]]) as i64,
+            4 => i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as i64,
+            _ => i64::from_le_bytes(chunk.try_into().unwrap_or_default()),
+        })
+        .collect();
+    Ok(ints)
+}
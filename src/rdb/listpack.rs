@@ -0,0 +1,93 @@
+use crate::{types::redis_error::RedisError, utils};
+
+const LISTPACK_HEADER_SIZE: usize = 6;
+const LISTPACK_EOF: u8 = 0xFF;
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<Vec<String>, RedisError> {
+    if bytes.len() < LISTPACK_HEADER_SIZE + 1 {
+        return Err(RedisError::RDBInvalidListpack);
+    }
+
+    let mut entries = Vec::new();
+    let mut idx = LISTPACK_HEADER_SIZE;
+
+    loop {
+        let encoding = *bytes.get(idx).ok_or(RedisError::RDBInvalidListpack)?;
+        if encoding == LISTPACK_EOF {
+            break;
+        }
+
+        let (entry, entry_len) = decode_entry(&bytes[idx..])?;
+        entries.push(entry);
+        idx += entry_len + backlen_size(entry_len);
+    }
+
+    Ok(entries)
+}
+
+fn decode_entry(bytes: &[u8]) -> Result<(String, usize), RedisError> {
+    let encoding = bytes[0];
+
+    let (value, len) = if encoding & 0x80 == 0 {
+        ((encoding & 0x7F) as i64, 1)
+    } else if encoding & 0xC0 == 0x80 {
+        let str_len = (encoding & 0x3F) as usize;
+        return Ok((read_str(bytes, 1, str_len)?, 1 + str_len));
+    } else if encoding & 0xE0 == 0xC0 {
+        let raw = (((encoding & 0x1F) as i64) << 8) | *byte_at(bytes, 1)? as i64;
+        (sign_extend(raw, 13), 2)
+    } else if encoding & 0xF0 == 0xE0 {
+        let str_len = (((encoding & 0x0F) as usize) << 8) | *byte_at(bytes, 1)? as usize;
+        return Ok((read_str(bytes, 2, str_len)?, 2 + str_len));
+    } else {
+        match encoding {
+            0xF0 => {
+                let str_len = read_uint(bytes, 4)? as usize;
+                return Ok((read_str(bytes, 5, str_len)?, 5 + str_len));
+            }
+            0xF1 => (sign_extend(read_uint(bytes, 2)? as i64, 16), 3),
+            0xF2 => (sign_extend(read_uint(bytes, 3)? as i64, 24), 4),
+            0xF3 => (sign_extend(read_uint(bytes, 4)? as i64, 32), 5),
+            0xF4 => (read_uint(bytes, 8)? as i64, 9),
+            _ => return Err(RedisError::RDBInvalidListpack),
+        }
+    };
+
+    Ok((value.to_string(), len))
+}
+
+fn byte_at(bytes: &[u8], idx: usize) -> Result<&u8, RedisError> {
+    bytes.get(idx).ok_or(RedisError::RDBInvalidListpack)
+}
+
+fn read_str(bytes: &[u8], start: usize, len: usize) -> Result<String, RedisError> {
+    let raw = bytes
+        .get(start..start + len)
+        .ok_or(RedisError::RDBInvalidListpack)?;
+    Ok(utils::bytes_to_string(raw))
+}
+
+fn read_uint(bytes: &[u8], width: usize) -> Result<u64, RedisError> {
+    let raw = bytes
+        .get(1..1 + width)
+        .ok_or(RedisError::RDBInvalidListpack)?;
+    Ok(raw
+        .iter()
+        .rev()
+        .fold(0u64, |acc, byte| (acc << 8) | *byte as u64))
+}
+
+fn sign_extend(value: i64, bits: u32) -> i64 {
+    let shift = 64 - bits;
+    (value << shift) >> shift
+}
+
+fn backlen_size(entry_len: usize) -> usize {
+    match entry_len {
+        0..=127 => 1,
+        128..=16382 => 2,
+        16383..=2097150 => 3,
+        2097151..=268435454 => 4,
+        _ => 5,
+    }
+}
@@ -0,0 +1,48 @@
+use crate::types::redis_error::RedisError;
+
+// Decompresses an LZF block as written by Redis for compressed RDB strings.
+pub(crate) fn decompress(input: &[u8], expected_len: usize) -> Result<Vec<u8>, RedisError> {
+    let mut output = Vec::new();
+    let mut idx = 0;
+
+    while idx < input.len() {
+        let ctrl = input[idx] as usize;
+        idx += 1;
+
+        if ctrl < 32 {
+            let literal_len = ctrl + 1;
+            let literal = input
+                .get(idx..idx + literal_len)
+                .ok_or(RedisError::RDBInvalidLZFString)?;
+            output.extend_from_slice(literal);
+            idx += literal_len;
+            continue;
+        }
+
+        let mut ref_len = ctrl >> 5;
+        if ref_len == 7 {
+            ref_len += *input.get(idx).ok_or(RedisError::RDBInvalidLZFString)? as usize;
+            idx += 1;
+        }
+        ref_len += 2;
+
+        let low = *input.get(idx).ok_or(RedisError::RDBInvalidLZFString)? as usize;
+        idx += 1;
+        let offset = ((ctrl & 0x1f) << 8) + low + 1;
+
+        if offset > output.len() {
+            return Err(RedisError::RDBInvalidLZFString);
+        }
+
+        let start = output.len() - offset;
+        for i in 0..ref_len {
+            output.push(output[start + i]);
+        }
+    }
+
+    if output.len() != expected_len {
+        return Err(RedisError::RDBInvalidLZFString);
+    }
+
+    Ok(output)
+}
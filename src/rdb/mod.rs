@@ -1,3 +1,9 @@
+mod crc64;
+pub mod dump_payload;
 mod file_extensions;
+mod intset;
+mod listpack;
+mod lzf;
 pub mod rdb_loader;
 pub(crate) mod rdb_types;
+mod rdb_writer;
@@ -1,17 +1,36 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, VecDeque},
     fs::{self},
 };
 
-use crate::types::{
-    entry_value::EntryValue, redis_error::RedisError, value_container::ValueContainer,
+use crate::{
+    types::{
+        bloom_filter::BloomFilter,
+        cuckoo_filter::CuckooFilter,
+        entry_value::EntryValue,
+        hash::RedisHash,
+        json::JsonValue,
+        redis_error::RedisError,
+        set::RedisSet,
+        sorted_set::SortedSet,
+        stream::{Consumer, ConsumerGroup, PendingEntry, RedisStream},
+        stream_entry::StreamEntry,
+        stream_key::StreamKey,
+        time_series::TimeSeries,
+        value_container::ValueContainer,
+        vector_set::VectorSet,
+    },
+    utils,
 };
 
 use super::{
     file_extensions::FileExt,
-    rdb_types::{IntegerStringLen, OpCodes, SizeEncodedValue},
+    intset, listpack, lzf,
+    rdb_types::{IntegerStringLen, OpCodes, SizeEncodedValue, ValueType},
 };
 
+const QUICKLIST_NODE_PLAIN: usize = 1;
+
 pub fn load(dir: &String, filename: &String) -> Result<HashMap<String, EntryValue>, RedisError> {
     let path = build_path(dir, filename);
 
@@ -38,6 +57,10 @@ fn read_database(file: &mut impl FileExt) -> Result<HashMap<String, EntryValue>,
 
     let db_header: OpCodes = file.next_u8()?.try_into()?;
 
+    if db_header == OpCodes::EOF {
+        return Ok(entries);
+    }
+
     if db_header == OpCodes::SelectDb {
         let db_index = decode_size(file)?;
         println!("Db Selected: {:?}", db_index);
@@ -46,29 +69,31 @@ fn read_database(file: &mut impl FileExt) -> Result<HashMap<String, EntryValue>,
     _ = read_database_size(file)?;
 
     loop {
-        let mut op_code: OpCodes = file.next_u8()?.try_into()?;
-        println!("Readed: {:?}", op_code);
-        let exp = match op_code {
-            OpCodes::ExpireTime => Some(file.next_u32()? as u128),
-            OpCodes::ExpireTimeMs => Some(file.next_u64()? as u128),
-            _ => None,
+        let mut op_code = file.next_u8()?;
+        let exp = if op_code == OpCodes::ExpireTime as u8 {
+            Some(file.next_u32()? as u128 * 1000)
+        } else if op_code == OpCodes::ExpireTimeMs as u8 {
+            Some(file.next_u64()? as u128)
+        } else {
+            None
         };
 
         if exp.is_some() {
-            op_code = file.next_u8()?.try_into()?;
+            op_code = file.next_u8()?;
         }
 
-        if op_code == OpCodes::StringValue {
-            let k = read_string(file)?;
-            let v = read_string(file)?;
-            let entry = EntryValue {
-                expires_at: exp,
-                value: ValueContainer::String(v),
-            };
-            entries.insert(k, entry);
-        } else {
+        if op_code == OpCodes::EOF as u8 || op_code == OpCodes::SelectDb as u8 {
             break;
         }
+
+        let value_type = ValueType::try_from(op_code)?;
+        let k = read_string(file)?;
+        let v = read_object(file, value_type)?;
+        let entry = EntryValue {
+            expires_at: exp,
+            value: v,
+        };
+        entries.insert(k, entry);
     }
 
     Ok(entries)
@@ -102,6 +127,204 @@ fn read_metadata(file: &mut impl FileExt) -> Result<HashMap<String, String>, Red
     Ok(metadata)
 }
 
+pub(crate) fn read_object(
+    file: &mut impl FileExt,
+    value_type: ValueType,
+) -> Result<ValueContainer, RedisError> {
+    match value_type {
+        ValueType::String => Ok(ValueContainer::String(read_string(file)?)),
+        ValueType::List => {
+            let len = read_length(file)?;
+            let mut items = VecDeque::new();
+            for _ in 0..len {
+                items.push_back(read_string(file)?);
+            }
+            Ok(ValueContainer::List(items))
+        }
+        ValueType::Set => {
+            let len = read_length(file)?;
+            let mut set = RedisSet::new();
+            for _ in 0..len {
+                set.insert(read_string(file)?);
+            }
+            Ok(ValueContainer::Set(set))
+        }
+        ValueType::SetIntSet => {
+            let ints = intset::decode(&read_bytes(file)?)?;
+            Ok(ValueContainer::Set(
+                ints.into_iter().map(|int| int.to_string()).collect(),
+            ))
+        }
+        ValueType::SetListpack => {
+            let members = listpack::decode(&read_bytes(file)?)?;
+            Ok(ValueContainer::Set(members.into_iter().collect()))
+        }
+        ValueType::SortedSet | ValueType::SortedSet2 => {
+            let len = read_length(file)?;
+            let mut zset = SortedSet::new();
+            for _ in 0..len {
+                let member = read_string(file)?;
+                let score = match value_type {
+                    ValueType::SortedSet2 => f64::from_bits(file.next_u64()?),
+                    _ => read_score(file)?,
+                };
+                zset.insert(member, score);
+            }
+            Ok(ValueContainer::SortedSet(zset))
+        }
+        ValueType::SortedSetListpack => {
+            let entries = listpack::decode(&read_bytes(file)?)?;
+            if !entries.len().is_multiple_of(2) {
+                return Err(RedisError::RDBInvalidListpack);
+            }
+
+            let mut entries = entries.into_iter();
+            let mut zset = SortedSet::new();
+            while let (Some(member), Some(score)) = (entries.next(), entries.next()) {
+                let score = score.parse::<f64>().map_err(|_| RedisError::ParsingError)?;
+                zset.insert(member, score);
+            }
+            Ok(ValueContainer::SortedSet(zset))
+        }
+        ValueType::Hash => {
+            let len = read_length(file)?;
+            let mut hash = RedisHash::new();
+            for _ in 0..len {
+                hash.insert(read_string(file)?, read_string(file)?);
+            }
+            Ok(ValueContainer::Hash(hash))
+        }
+        ValueType::HashMetadata => {
+            let min_expire = file.next_u64()? as u128;
+            let len = read_length(file)?;
+            let mut hash = RedisHash::new();
+            for _ in 0..len {
+                let ttl = read_length(file)? as u128;
+                let field = read_string(file)?;
+                hash.insert(field.clone(), read_string(file)?);
+                if ttl > 0 {
+                    hash.set_expiration(&field, min_expire + ttl - 1);
+                }
+            }
+            Ok(ValueContainer::Hash(hash))
+        }
+        ValueType::HashListpack => {
+            let entries = listpack::decode(&read_bytes(file)?)?;
+            if !entries.len().is_multiple_of(2) {
+                return Err(RedisError::RDBInvalidListpack);
+            }
+
+            let mut entries = entries.into_iter();
+            let mut hash = RedisHash::new();
+            while let (Some(field), Some(value)) = (entries.next(), entries.next()) {
+                hash.insert(field, value);
+            }
+            Ok(ValueContainer::Hash(hash))
+        }
+        ValueType::ListQuicklist2 => {
+            let nodes = read_length(file)?;
+            let mut items = VecDeque::new();
+            for _ in 0..nodes {
+                let container = read_length(file)?;
+                let node = read_bytes(file)?;
+                if container == QUICKLIST_NODE_PLAIN {
+                    items.push_back(utils::bytes_to_string(&node));
+                } else {
+                    let entries = listpack::decode(&node)?;
+                    items.extend(entries);
+                }
+            }
+            Ok(ValueContainer::List(items))
+        }
+        ValueType::Json => {
+            let json =
+                JsonValue::parse(&read_string(file)?).map_err(|_| RedisError::ParsingError)?;
+            Ok(ValueContainer::Json(json))
+        }
+        ValueType::Bloom => Ok(ValueContainer::BloomFilter(BloomFilter::from_bytes(
+            &read_bytes(file)?,
+        )?)),
+        ValueType::Cuckoo => Ok(ValueContainer::CuckooFilter(CuckooFilter::from_bytes(
+            &read_bytes(file)?,
+        )?)),
+        ValueType::TimeSeries => Ok(ValueContainer::TimeSeries(TimeSeries::from_bytes(
+            &read_bytes(file)?,
+        )?)),
+        ValueType::VectorSet => Ok(ValueContainer::VectorSet(VectorSet::from_bytes(
+            &read_bytes(file)?,
+        )?)),
+        ValueType::Stream => {
+            let len = read_length(file)?;
+            let mut entries = Vec::new();
+            for _ in 0..len {
+                let id = read_stream_id(file)?;
+                let fields_len = read_length(file)?;
+                let mut fields = Vec::new();
+                for _ in 0..fields_len {
+                    fields.push((read_string(file)?, read_string(file)?));
+                }
+                entries.push(StreamEntry { id, fields });
+            }
+            let last_id = Some(read_stream_id(file)?).filter(|id| *id != StreamKey::new(0, 0));
+
+            let mut groups = BTreeMap::new();
+            for _ in 0..read_length(file)? {
+                let name = read_string(file)?;
+                let mut group = ConsumerGroup::new(read_stream_id(file)?);
+                for _ in 0..read_length(file)? {
+                    let id = read_stream_id(file)?;
+                    let pending = PendingEntry {
+                        consumer: read_string(file)?,
+                        delivered_at: file.next_u64()? as u128,
+                        delivery_count: read_length(file)? as u64,
+                    };
+                    group.pending.insert(id, pending);
+                }
+                for _ in 0..read_length(file)? {
+                    let name = read_string(file)?;
+                    let seen_at = file.next_u64()? as u128;
+                    group.consumers.insert(name, Consumer { seen_at });
+                }
+                groups.insert(name, group);
+            }
+            Ok(ValueContainer::Stream(RedisStream::from_parts(
+                entries, last_id, groups,
+            )))
+        }
+    }
+}
+
+// Old-style sorted set scores are length-prefixed strings, with the
+// lengths 253-255 standing for NaN, +inf and -inf.
+fn read_score(file: &mut impl FileExt) -> Result<f64, RedisError> {
+    match file.next_u8()? {
+        253 => Ok(f64::NAN),
+        254 => Ok(f64::INFINITY),
+        255 => Ok(f64::NEG_INFINITY),
+        len => file
+            .next_string(len as usize)?
+            .parse::<f64>()
+            .map_err(|_| RedisError::ParsingError),
+    }
+}
+
+fn read_stream_id(file: &mut impl FileExt) -> Result<StreamKey, RedisError> {
+    let miliseconds_time = file.next_u64()? as u128;
+    Ok(StreamKey::new(miliseconds_time, file.next_u64()?))
+}
+
+fn read_length(file: &mut impl FileExt) -> Result<usize, RedisError> {
+    match decode_size(file)? {
+        SizeEncodedValue::Size(len) => Ok(len),
+        _ => Err(RedisError::ParsingError),
+    }
+}
+
+fn read_bytes(file: &mut impl FileExt) -> Result<Vec<u8>, RedisError> {
+    let str = read_string(file)?;
+    Ok(utils::string_to_bytes(&str))
+}
+
 fn read_string(file: &mut impl FileExt) -> Result<String, RedisError> {
     let decoded_size = decode_size(file)?;
 
@@ -112,7 +335,12 @@ fn read_string(file: &mut impl FileExt) -> Result<String, RedisError> {
             IntegerStringLen::DoubleWord => file.next_i16()?.to_string(),
             IntegerStringLen::QuadWord => file.next_i32()?.to_string(),
         },
-        SizeEncodedValue::LZFString => panic!("LZF String not implemented"),
+        SizeEncodedValue::LZFString => {
+            let compressed_len = read_length(file)?;
+            let len = read_length(file)?;
+            let compressed = file.next_bytes(compressed_len)?;
+            utils::bytes_to_string(&lzf::decompress(&compressed, len)?)
+        }
     };
     Ok(str)
 }
@@ -128,10 +356,11 @@ fn decode_size(file: &mut impl FileExt) -> Result<SizeEncodedValue, RedisError>
             let str_size = ((remaining as u16) << 8) | (ext as u16);
             Ok(SizeEncodedValue::Size(str_size.into()))
         }
-        2 => {
-            let str_size = file.next_u32()?;
-            Ok(SizeEncodedValue::Size(str_size as usize))
-        }
+        2 => match remaining {
+            0 => Ok(SizeEncodedValue::Size(file.next_u32_be()? as usize)),
+            1 => Ok(SizeEncodedValue::Size(file.next_u64_be()? as usize)),
+            _ => Err(RedisError::RDBDecodeSizeError(size, size_mode, remaining)),
+        },
         3 => match remaining {
             0 => Ok(SizeEncodedValue::IntegerString(
                 IntegerStringLen::SingleWord,
@@ -23,7 +23,35 @@ pub(crate) enum OpCodes {
     ExpireTimeMs = 0xFC,
     ResizeDb = 0xFB,
     Metadata = 0xFA,
-    StringValue = 0x00,
+}
+
+#[repr(u8)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub(crate) enum ValueType {
+    String = 0,
+    List = 1,
+    Set = 2,
+    SortedSet = 3,
+    Hash = 4,
+    SortedSet2 = 5,
+    SetIntSet = 11,
+    HashListpack = 16,
+    SortedSetListpack = 17,
+    ListQuicklist2 = 18,
+    SetListpack = 20,
+    HashMetadata = 24,
+    // Not a Redis type: JSON documents are stored as their serialized text.
+    Json = 200,
+    // Bloom and cuckoo filters are stored as a single string of their bytes.
+    Bloom = 201,
+    Cuckoo = 202,
+    // Time series keep their Gorilla compressed chunks as they are.
+    TimeSeries = 203,
+    // Vector sets keep their vectors only, the graph is rebuilt on load.
+    VectorSet = 204,
+    // Streams are written entry by entry with their consumer groups rather
+    // than as Redis' listpack radix tree.
+    Stream = 205,
 }
 
 impl TryInto<OpCodes> for u8 {
@@ -37,8 +65,35 @@ impl TryInto<OpCodes> for u8 {
             0xFC => Ok(OpCodes::ExpireTimeMs),
             0xFB => Ok(OpCodes::ResizeDb),
             0xFA => Ok(OpCodes::Metadata),
-            0x00 => Ok(OpCodes::StringValue),
             _ => Err(RedisError::InvalidOpCode),
         }
     }
 }
+
+impl TryFrom<u8> for ValueType {
+    type Error = RedisError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ValueType::String),
+            1 => Ok(ValueType::List),
+            2 => Ok(ValueType::Set),
+            3 => Ok(ValueType::SortedSet),
+            4 => Ok(ValueType::Hash),
+            5 => Ok(ValueType::SortedSet2),
+            11 => Ok(ValueType::SetIntSet),
+            16 => Ok(ValueType::HashListpack),
+            17 => Ok(ValueType::SortedSetListpack),
+            18 => Ok(ValueType::ListQuicklist2),
+            20 => Ok(ValueType::SetListpack),
+            24 => Ok(ValueType::HashMetadata),
+            200 => Ok(ValueType::Json),
+            201 => Ok(ValueType::Bloom),
+            202 => Ok(ValueType::Cuckoo),
+            203 => Ok(ValueType::TimeSeries),
+            204 => Ok(ValueType::VectorSet),
+            205 => Ok(ValueType::Stream),
+            _ => Err(RedisError::RDBUnsupportedValueType(value)),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{
+    types::{redis_error::RedisError, stream_key::StreamKey, value_container::ValueContainer},
+    utils,
+};
+
+use super::rdb_types::ValueType;
+
+pub(crate) fn value_type(value: &ValueContainer) -> Result<ValueType, RedisError> {
+    match value {
+        ValueContainer::String(_) | ValueContainer::Integer(_) | ValueContainer::Boolean(_) => {
+            Ok(ValueType::String)
+        }
+        ValueContainer::Array(_) | ValueContainer::List(_) => Ok(ValueType::List),
+        ValueContainer::Hash(hash) if hash.has_expirations() => Ok(ValueType::HashMetadata),
+        ValueContainer::Hash(_) => Ok(ValueType::Hash),
+        ValueContainer::Set(_) => Ok(ValueType::Set),
+        ValueContainer::SortedSet(_) => Ok(ValueType::SortedSet2),
+        ValueContainer::Json(_) => Ok(ValueType::Json),
+        ValueContainer::BloomFilter(_) => Ok(ValueType::Bloom),
+        ValueContainer::CuckooFilter(_) => Ok(ValueType::Cuckoo),
+        ValueContainer::TimeSeries(_) => Ok(ValueType::TimeSeries),
+        ValueContainer::VectorSet(_) => Ok(ValueType::VectorSet),
+        ValueContainer::Stream(_) => Ok(ValueType::Stream),
+        ValueContainer::Null => Err(RedisError::RDBUnsupportedValue),
+    }
+}
+
+pub(crate) fn write_object(buffer: &mut Vec<u8>, value: &ValueContainer) -> Result<(), RedisError> {
+    match value {
+        ValueContainer::String(_) | ValueContainer::Integer(_) | ValueContainer::Boolean(_) => {
+            let str: String = value.into();
+            write_string(buffer, &str);
+        }
+        ValueContainer::Array(items) => {
+            write_length(buffer, items.len());
+            for item in items {
+                let str: String = item.into();
+                write_string(buffer, &str);
+            }
+        }
+        ValueContainer::List(items) => {
+            write_length(buffer, items.len());
+            for item in items {
+                write_string(buffer, item);
+            }
+        }
+        ValueContainer::Hash(hash) if hash.has_expirations() => {
+            // Field TTLs are stored relative to the earliest one, 0 meaning none.
+            let min_expire = hash
+                .iter()
+                .filter_map(|(field, _)| hash.expires_at(field))
+                .min()
+                .unwrap_or_default();
+            buffer.extend_from_slice(&(min_expire as u64).to_le_bytes());
+
+            write_length(buffer, hash.len());
+            for (field, value) in hash.iter() {
+                let ttl = hash.expires_at(field).map_or(0, |at| at - min_expire + 1);
+                write_length(buffer, ttl as usize);
+                write_string(buffer, field);
+                write_string(buffer, value);
+            }
+        }
+        ValueContainer::Hash(hash) => {
+            write_length(buffer, hash.len());
+            for (field, value) in hash.iter() {
+                write_string(buffer, field);
+                write_string(buffer, value);
+            }
+        }
+        ValueContainer::Set(set) => {
+            write_length(buffer, set.len());
+            for member in set.iter() {
+                write_string(buffer, &member);
+            }
+        }
+        ValueContainer::SortedSet(zset) => {
+            write_length(buffer, zset.len());
+            for (member, score) in zset.iter() {
+                write_string(buffer, member);
+                buffer.extend_from_slice(&score.to_le_bytes());
+            }
+        }
+        ValueContainer::Json(json) => write_string(buffer, &json.to_json()),
+        ValueContainer::BloomFilter(bf) => {
+            write_string(buffer, &utils::bytes_to_string(&bf.to_bytes()))
+        }
+        ValueContainer::CuckooFilter(cf) => {
+            write_string(buffer, &utils::bytes_to_string(&cf.to_bytes()))
+        }
+        ValueContainer::TimeSeries(ts) => {
+            write_string(buffer, &utils::bytes_to_string(&ts.to_bytes()))
+        }
+        ValueContainer::VectorSet(vset) => {
+            write_string(buffer, &utils::bytes_to_string(&vset.to_bytes()))
+        }
+        ValueContainer::Stream(stream) => {
+            write_length(buffer, stream.len());
+            for entry in stream.entries() {
+                write_stream_id(buffer, &entry.id);
+                write_length(buffer, entry.fields.len());
+                for (field, value) in &entry.fields {
+                    write_string(buffer, field);
+                    write_string(buffer, value);
+                }
+            }
+            // 0-0 stands for a stream that never had an entry.
+            write_stream_id(buffer, &stream.last_id().unwrap_or(StreamKey::new(0, 0)));
+
+            write_length(buffer, stream.groups.len());
+            for (name, group) in &stream.groups {
+                write_string(buffer, name);
+                write_stream_id(buffer, &group.last_delivered);
+                write_length(buffer, group.pending.len());
+                for (id, pending) in &group.pending {
+                    write_stream_id(buffer, id);
+                    write_string(buffer, &pending.consumer);
+                    buffer.extend_from_slice(&(pending.delivered_at as u64).to_le_bytes());
+                    write_length(buffer, pending.delivery_count as usize);
+                }
+                write_length(buffer, group.consumers.len());
+                for (name, consumer) in &group.consumers {
+                    write_string(buffer, name);
+                    buffer.extend_from_slice(&(consumer.seen_at as u64).to_le_bytes());
+                }
+            }
+        }
+        ValueContainer::Null => return Err(RedisError::RDBUnsupportedValue),
+    }
+    Ok(())
+}
+
+fn write_stream_id(buffer: &mut Vec<u8>, id: &StreamKey) {
+    buffer.extend_from_slice(&(id.miliseconds_time as u64).to_le_bytes());
+    buffer.extend_from_slice(&id.sequence_number.to_le_bytes());
+}
+
+pub(crate) fn write_length(buffer: &mut Vec<u8>, len: usize) {
+    if len < 1 << 6 {
+        buffer.push(len as u8);
+    } else if len < 1 << 14 {
+        buffer.push(0x40 | (len >> 8) as u8);
+        buffer.push((len & 0xFF) as u8);
+    } else if len <= u32::MAX as usize {
+        buffer.push(0x80);
+        buffer.extend_from_slice(&(len as u32).to_be_bytes());
+    } else {
+        buffer.push(0x81);
+        buffer.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+}
+
+pub(crate) fn write_string(buffer: &mut Vec<u8>, value: &str) {
+    if let Some(int) = as_encodable_integer(value) {
+        if let Ok(int) = i8::try_from(int) {
+            buffer.push(0xC0);
+            buffer.extend_from_slice(&int.to_le_bytes());
+        } else if let Ok(int) = i16::try_from(int) {
+            buffer.push(0xC1);
+            buffer.extend_from_slice(&int.to_le_bytes());
+        } else {
+            buffer.push(0xC2);
+            buffer.extend_from_slice(&int.to_le_bytes());
+        }
+        return;
+    }
+
+    let bytes = utils::string_to_bytes(value);
+    write_length(buffer, bytes.len());
+    buffer.extend_from_slice(&bytes);
+}
+
+fn as_encodable_integer(value: &str) -> Option<i32> {
+    if value.is_empty() || value.len() > 11 {
+        return None;
+    }
+    let int = value.parse::<i32>().ok()?;
+    if int.to_string() == value {
+        Some(int)
+    } else {
+        None
+    }
+}
@@ -48,14 +48,14 @@ impl Into<String> for &RespTk {
             RespTk::BulkString(content) => format!(
                 "{}{}{}{}{}",
                 BULKS_STRING_ID,
-                content.len(),
+                content.chars().count(),
                 delimiter,
                 content,
                 delimiter
             ),
             RespTk::Array(content) => {
                 let arr: Vec<String> = content.into_iter().map(|t| t.into()).collect();
-                format!("{}{}{}", ARRAY_ID, arr.len(), arr.join(""))
+                format!("{}{}{}{}", ARRAY_ID, arr.len(), delimiter, arr.join(""))
             }
             RespTk::Null => "_\r\n".into(),
             RespTk::Boolean(value) => {
@@ -227,7 +227,12 @@ fn parse_bulk_string(buffer: &mut Peekable<Iter<u8>>) -> Option<RespTk> {
     if len == -1 {
         return Some(RespTk::Null);
     }
-    let content = read_until_delimitier(buffer);
+    let content: String = buffer
+        .by_ref()
+        .take(len as usize)
+        .map(|b| *b as char)
+        .collect();
+    let _ = read_until_delimitier(buffer);
     Some(RespTk::BulkString(content))
 }
 
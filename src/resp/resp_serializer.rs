@@ -7,7 +7,14 @@ pub fn to_resp_integer(input: i64) -> String {
 }
 
 pub fn to_resp_bulk(input: String) -> String {
-    format!("${}\r\n{}\r\n", input.len(), input)
+    format!("${}\r\n{}\r\n", input.chars().count(), input)
+}
+
+pub fn to_resp_bulk_bytes(input: &[u8]) -> Vec<u8> {
+    let mut result = format!("${}\r\n", input.len()).into_bytes();
+    result.extend_from_slice(input);
+    result.extend_from_slice(b"\r\n");
+    result
 }
 
 pub fn to_err_string(input: String) -> String {
@@ -17,7 +24,15 @@ pub fn to_err_string(input: String) -> String {
 pub fn to_resp_array(inputs: Vec<String>) -> String {
     let mut result = format!("*{}\r\n", inputs.len());
     for input in inputs {
-        result.push_str(&format!("${}\r\n{}\r\n", input.len(), input));
+        result.push_str(&format!("${}\r\n{}\r\n", input.chars().count(), input));
+    }
+    result
+}
+
+pub fn to_resp_nested_array(items: Vec<String>) -> String {
+    let mut result = format!("*{}\r\n", items.len());
+    for item in items {
+        result.push_str(&item);
     }
     result
 }
@@ -26,6 +41,10 @@ pub fn null_resp_string() -> String {
     String::from("$-1\r\n")
 }
 
+pub fn null_resp_array() -> String {
+    String::from("*-1\r\n")
+}
+
 pub fn slc_objects_to_resp(objs: &[impl RespSerializer]) -> String {
     let mut result = format!("*{}\r\n", objs.len());
     for obj in objs {
@@ -39,6 +39,9 @@ pub async fn execute_command(
         }
         return resp_serializer::to_resp_string("OK".to_owned()).into();
     } else {
+        // Blocking commands may wait indefinitely, so other clients must not
+        // be kept from the transaction map meanwhile.
+        drop(transations);
         return process_command(app.clone(), token, context).await;
     }
 }
@@ -47,6 +50,18 @@ async fn process_command(
     app: Arc<RedisApp>,
     token: &RespTk,
     context: ConnectionContext,
+) -> ExecResponse {
+    let response = dispatch_command(app.clone(), token, context).await;
+
+    let mut mem = app.memory.lock().await;
+    app.apply_writes(&mut mem);
+    response
+}
+
+async fn dispatch_command(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: ConnectionContext,
 ) -> ExecResponse {
     let cmd_name = token.get_command_name();
 
@@ -55,16 +70,210 @@ async fn process_command(
         "ECHO" => commands::echo_command::execute_echo(token),
         "GET" => commands::get_command::execute_get(app, token).await,
         "SET" => commands::set_command::execute_set(app, token).await,
+        "MGET" => commands::mget_command::execute_mget(app, token).await,
+        "MSET" => commands::mset_command::execute_mset(app, token).await,
+        "MSETNX" => commands::mset_command::execute_msetnx(app, token).await,
+        "GETSET" => commands::getset_command::execute_getset(app, token).await,
+        "DEL" => commands::del_command::execute_del(app, token).await,
+        "GETDEL" => commands::getdel_command::execute_getdel(app, token).await,
+        "GETEX" => commands::getex_command::execute_getex(app, token).await,
+        "SETNX" => commands::setnx_command::execute_setnx(app, token).await,
+        "SETEX" => commands::setex_command::execute_setex(app, token).await,
+        "PSETEX" => commands::setex_command::execute_psetex(app, token).await,
+        "SETBIT" => commands::setbit_command::execute_setbit(app, token).await,
+        "GETBIT" => commands::getbit_command::execute_getbit(app, token).await,
+        "BITCOUNT" => commands::bitcount_command::execute_bitcount(app, token).await,
+        "BITPOS" => commands::bitpos_command::execute_bitpos(app, token).await,
+        "BITOP" => commands::bitop_command::execute_bitop(app, token).await,
+        "BITFIELD" => commands::bitfield_command::execute_bitfield(app, token).await,
+        "BITFIELD_RO" => commands::bitfield_command::execute_bitfield_ro(app, token).await,
+        "PFADD" => commands::pfadd_command::execute_pfadd(app, token).await,
+        "PFCOUNT" => commands::pfcount_command::execute_pfcount(app, token).await,
+        "PFMERGE" => commands::pfmerge_command::execute_pfmerge(app, token).await,
+        "PFDEBUG" => commands::pfdebug_command::execute_pfdebug(app, token).await,
+        "LPUSH" => commands::push_command::execute_lpush(app, token).await,
+        "RPUSH" => commands::push_command::execute_rpush(app, token).await,
+        "LPUSHX" => commands::push_command::execute_lpushx(app, token).await,
+        "RPUSHX" => commands::push_command::execute_rpushx(app, token).await,
+        "LPOP" => commands::pop_command::execute_lpop(app, token).await,
+        "RPOP" => commands::pop_command::execute_rpop(app, token).await,
+        "LRANGE" => commands::lrange_command::execute_lrange(app, token).await,
+        "LLEN" => commands::llen_command::execute_llen(app, token).await,
+        "LINDEX" => commands::lindex_command::execute_lindex(app, token).await,
+        "LSET" => commands::lset_command::execute_lset(app, token).await,
+        "LREM" => commands::lrem_command::execute_lrem(app, token).await,
+        "LINSERT" => commands::linsert_command::execute_linsert(app, token).await,
+        "LTRIM" => commands::ltrim_command::execute_ltrim(app, token).await,
+        "LPOS" => commands::lpos_command::execute_lpos(app, token).await,
+        "LMOVE" => commands::lmove_command::execute_lmove(app, token).await,
+        "RPOPLPUSH" => commands::lmove_command::execute_rpoplpush(app, token).await,
+        "BLPOP" => commands::blpop_command::execute_blpop(app, token, &context).await,
+        "BRPOP" => commands::blpop_command::execute_brpop(app, token, &context).await,
+        "BLMPOP" => commands::blpop_command::execute_blmpop(app, token, &context).await,
+        "BLMOVE" => commands::blmove_command::execute_blmove(app, token, &context).await,
+        "BRPOPLPUSH" => commands::blmove_command::execute_brpoplpush(app, token, &context).await,
+        "HSET" => commands::hset_command::execute_hset(app, token).await,
+        "HMSET" => commands::hset_command::execute_hmset(app, token).await,
+        "HSETNX" => commands::hset_command::execute_hsetnx(app, token).await,
+        "HGET" => commands::hget_command::execute_hget(app, token).await,
+        "HMGET" => commands::hget_command::execute_hmget(app, token).await,
+        "HDEL" => commands::hdel_command::execute_hdel(app, token).await,
+        "HEXISTS" => commands::hexists_command::execute_hexists(app, token).await,
+        "HLEN" => commands::hlen_command::execute_hlen(app, token).await,
+        "HKEYS" => commands::hgetall_command::execute_hkeys(app, token).await,
+        "HVALS" => commands::hgetall_command::execute_hvals(app, token).await,
+        "HGETALL" => commands::hgetall_command::execute_hgetall(app, token).await,
+        "HINCRBY" => commands::hincrby_command::execute_hincrby(app, token).await,
+        "HINCRBYFLOAT" => commands::hincrby_command::execute_hincrbyfloat(app, token).await,
+        "HSTRLEN" => commands::hstrlen_command::execute_hstrlen(app, token).await,
+        "HRANDFIELD" => commands::hrandfield_command::execute_hrandfield(app, token).await,
+        "HSCAN" => commands::hscan_command::execute_hscan(app, token).await,
+        "HEXPIRE" => commands::hexpire_command::execute_hexpire(app, token).await,
+        "HPEXPIRE" => commands::hexpire_command::execute_hpexpire(app, token).await,
+        "HEXPIREAT" => commands::hexpire_command::execute_hexpireat(app, token).await,
+        "HPEXPIREAT" => commands::hexpire_command::execute_hpexpireat(app, token).await,
+        "HTTL" => commands::httl_command::execute_httl(app, token).await,
+        "HPTTL" => commands::httl_command::execute_hpttl(app, token).await,
+        "HEXPIRETIME" => commands::httl_command::execute_hexpiretime(app, token).await,
+        "HPEXPIRETIME" => commands::httl_command::execute_hpexpiretime(app, token).await,
+        "HPERSIST" => commands::hpersist_command::execute_hpersist(app, token).await,
+        "SADD" => commands::sadd_command::execute_sadd(app, token).await,
+        "SREM" => commands::srem_command::execute_srem(app, token).await,
+        "SMEMBERS" => commands::smembers_command::execute_smembers(app, token).await,
+        "SISMEMBER" => commands::sismember_command::execute_sismember(app, token).await,
+        "SMISMEMBER" => commands::sismember_command::execute_smismember(app, token).await,
+        "SCARD" => commands::scard_command::execute_scard(app, token).await,
+        "SPOP" => commands::spop_command::execute_spop(app, token).await,
+        "SRANDMEMBER" => commands::srandmember_command::execute_srandmember(app, token).await,
+        "SMOVE" => commands::smove_command::execute_smove(app, token).await,
+        "SINTER" => commands::sinter_command::execute_sinter(app, token).await,
+        "SINTERSTORE" => commands::sinter_command::execute_sinterstore(app, token).await,
+        "SINTERCARD" => commands::sinter_command::execute_sintercard(app, token).await,
+        "SUNION" => commands::sunion_command::execute_sunion(app, token).await,
+        "SUNIONSTORE" => commands::sunion_command::execute_sunionstore(app, token).await,
+        "SDIFF" => commands::sdiff_command::execute_sdiff(app, token).await,
+        "SDIFFSTORE" => commands::sdiff_command::execute_sdiffstore(app, token).await,
+        "SSCAN" => commands::sscan_command::execute_sscan(app, token).await,
+        "ZADD" => commands::zadd_command::execute_zadd(app, token).await,
+        "ZINCRBY" => commands::zincrby_command::execute_zincrby(app, token).await,
+        "ZREM" => commands::zrem_command::execute_zrem(app, token).await,
+        "ZSCORE" => commands::zscore_command::execute_zscore(app, token).await,
+        "ZMSCORE" => commands::zscore_command::execute_zmscore(app, token).await,
+        "ZCARD" => commands::zcard_command::execute_zcard(app, token).await,
+        "ZCOUNT" => commands::zcount_command::execute_zcount(app, token).await,
+        "ZLEXCOUNT" => commands::zcount_command::execute_zlexcount(app, token).await,
+        "ZRANK" => commands::zrank_command::execute_zrank(app, token).await,
+        "ZREVRANK" => commands::zrank_command::execute_zrevrank(app, token).await,
+        "ZRANGE" => commands::zrange_command::execute_zrange(app, token).await,
+        "ZRANGESTORE" => commands::zrange_command::execute_zrangestore(app, token).await,
+        "ZREMRANGEBYRANK" => {
+            commands::zremrangeby_command::execute_zremrangebyrank(app, token).await
+        }
+        "ZREMRANGEBYSCORE" => {
+            commands::zremrangeby_command::execute_zremrangebyscore(app, token).await
+        }
+        "ZREMRANGEBYLEX" => commands::zremrangeby_command::execute_zremrangebylex(app, token).await,
+        "ZPOPMIN" => commands::zpop_command::execute_zpopmin(app, token).await,
+        "ZPOPMAX" => commands::zpop_command::execute_zpopmax(app, token).await,
+        "ZMPOP" => commands::bzpop_command::execute_zmpop(app, token).await,
+        "BZPOPMIN" => commands::bzpop_command::execute_bzpopmin(app, token, &context).await,
+        "BZPOPMAX" => commands::bzpop_command::execute_bzpopmax(app, token, &context).await,
+        "BZMPOP" => commands::bzpop_command::execute_bzmpop(app, token, &context).await,
+        "ZRANDMEMBER" => commands::zrandmember_command::execute_zrandmember(app, token).await,
+        "ZSCAN" => commands::zscan_command::execute_zscan(app, token).await,
+        "ZUNION" => commands::zsetop_command::execute_zunion(app, token).await,
+        "ZINTER" => commands::zsetop_command::execute_zinter(app, token).await,
+        "ZDIFF" => commands::zsetop_command::execute_zdiff(app, token).await,
+        "ZUNIONSTORE" => commands::zsetop_command::execute_zunionstore(app, token).await,
+        "ZINTERSTORE" => commands::zsetop_command::execute_zinterstore(app, token).await,
+        "ZDIFFSTORE" => commands::zsetop_command::execute_zdiffstore(app, token).await,
+        "ZINTERCARD" => commands::zsetop_command::execute_zintercard(app, token).await,
+        "GEOADD" => commands::geoadd_command::execute_geoadd(app, token).await,
+        "GEOPOS" => commands::geopos_command::execute_geopos(app, token).await,
+        "GEODIST" => commands::geodist_command::execute_geodist(app, token).await,
+        "GEOHASH" => commands::geohash_command::execute_geohash(app, token).await,
+        "GEOSEARCH" => commands::geosearch_command::execute_geosearch(app, token).await,
+        "GEOSEARCHSTORE" => commands::geosearch_command::execute_geosearchstore(app, token).await,
+        "JSON.SET" => commands::json_set_command::execute_json_set(app, token).await,
+        "JSON.GET" => commands::json_get_command::execute_json_get(app, token).await,
+        "JSON.MGET" => commands::json_get_command::execute_json_mget(app, token).await,
+        "JSON.DEL" => commands::json_del_command::execute_json_del(app, token).await,
+        "JSON.TYPE" => commands::json_type_command::execute_json_type(app, token).await,
+        "JSON.NUMINCRBY" => {
+            commands::json_numincrby_command::execute_json_numincrby(app, token).await
+        }
+        "JSON.STRAPPEND" => {
+            commands::json_strappend_command::execute_json_strappend(app, token).await
+        }
+        "JSON.ARRAPPEND" => {
+            commands::json_arrappend_command::execute_json_arrappend(app, token).await
+        }
+        "JSON.ARRINSERT" => {
+            commands::json_arrinsert_command::execute_json_arrinsert(app, token).await
+        }
+        "JSON.ARRPOP" => commands::json_arrpop_command::execute_json_arrpop(app, token).await,
+        "JSON.OBJKEYS" => commands::json_objkeys_command::execute_json_objkeys(app, token).await,
+        "JSON.MERGE" => commands::json_merge_command::execute_json_merge(app, token).await,
+        "BF.RESERVE" => commands::bf_reserve_command::execute_bf_reserve(app, token).await,
+        "BF.ADD" => commands::bf_add_command::execute_bf_add(app, token).await,
+        "BF.MADD" => commands::bf_add_command::execute_bf_madd(app, token).await,
+        "BF.EXISTS" => commands::bf_exists_command::execute_bf_exists(app, token).await,
+        "BF.MEXISTS" => commands::bf_exists_command::execute_bf_mexists(app, token).await,
+        "BF.INFO" => commands::bf_info_command::execute_bf_info(app, token).await,
+        "CF.ADD" => commands::cf_add_command::execute_cf_add(app, token).await,
+        "CF.EXISTS" => commands::cf_exists_command::execute_cf_exists(app, token).await,
+        "CF.DEL" => commands::cf_del_command::execute_cf_del(app, token).await,
+        "TS.CREATE" => commands::ts_create_command::execute_ts_create(app, token).await,
+        "TS.ADD" => commands::ts_add_command::execute_ts_add(app, token).await,
+        "TS.MADD" => commands::ts_add_command::execute_ts_madd(app, token).await,
+        "TS.RANGE" => commands::ts_range_command::execute_ts_range(app, token).await,
+        "TS.REVRANGE" => commands::ts_range_command::execute_ts_revrange(app, token).await,
+        "TS.MRANGE" => commands::ts_range_command::execute_ts_mrange(app, token).await,
+        "TS.CREATERULE" => commands::ts_createrule_command::execute_ts_createrule(app, token).await,
+        "FT.CREATE" => commands::ft_create_command::execute_ft_create(app, token).await,
+        "FT.SEARCH" => commands::ft_search_command::execute_ft_search(app, token).await,
+        "FT.AGGREGATE" => commands::ft_aggregate_command::execute_ft_aggregate(app, token).await,
+        "FT.DROPINDEX" => commands::ft_dropindex_command::execute_ft_dropindex(app, token).await,
+        "VADD" => commands::vadd_command::execute_vadd(app, token).await,
+        "VREM" => commands::vrem_command::execute_vrem(app, token).await,
+        "VSIM" => commands::vsim_command::execute_vsim(app, token).await,
+        "VCARD" => commands::vcard_command::execute_vcard(app, token).await,
+        "VDIM" => commands::vdim_command::execute_vdim(app, token).await,
+        "VGETATTR" => commands::vgetattr_command::execute_vgetattr(app, token).await,
+        "VSETATTR" => commands::vsetattr_command::execute_vsetattr(app, token).await,
+        "XGROUP" => commands::xgroup_command::execute_xgroup(app, token).await,
+        "XREADGROUP" => {
+            commands::xreadgroup_command::execute_xreadgroup(app, token, &context).await
+        }
+        "XACK" => commands::xack_command::execute_xack(app, token).await,
+        "XPENDING" => commands::xpending_command::execute_xpending(app, token).await,
+        "XCLAIM" => commands::xclaim_command::execute_xclaim(app, token).await,
+        "XAUTOCLAIM" => commands::xautoclaim_command::execute_xautoclaim(app, token).await,
+        "XTRIM" => commands::xtrim_command::execute_xtrim(app, token).await,
+        "XDEL" => commands::xdel_command::execute_xdel(app, token).await,
+        "XLEN" => commands::xlen_command::execute_xlen(app, token).await,
         "CONFIG" => commands::config_command::execute_config(app, token).await,
         "KEYS" => commands::keys_command::execute_keys(app, token).await,
         "TYPE" => commands::type_command::execute_type(app, token).await,
         "XADD" => commands::xadd_command::execute_xadd(app, token).await,
         "XRANGE" => commands::xrange_command::execute_xrange(app, token).await,
-        "XREAD" => commands::xread_command::execute_xread(app, token).await,
-        "INC" => commands::command_inc::execute_inc(app, token).await,
+        "XREVRANGE" => commands::xrange_command::execute_xrevrange(app, token).await,
+        "XREAD" => commands::xread_command::execute_xread(app, token, &context).await,
+        "INC" | "INCR" => commands::incr_command::execute_incr(app, token).await,
+        "DECR" => commands::incr_command::execute_decr(app, token).await,
+        "INCRBY" => commands::incr_command::execute_incrby(app, token).await,
+        "DECRBY" => commands::incr_command::execute_decrby(app, token).await,
+        "INCRBYFLOAT" => commands::incr_command::execute_incrbyfloat(app, token).await,
+        "APPEND" => commands::append_command::execute_append(app, token).await,
+        "STRLEN" => commands::strlen_command::execute_strlen(app, token).await,
+        "GETRANGE" => commands::getrange_command::execute_getrange(app, token).await,
+        "SETRANGE" => commands::setrange_command::execute_setrange(app, token).await,
+        "LCS" => commands::lcs_command::execute_lcs(app, token).await,
         "INFO" => commands::info_command::execute_info(app, token).await,
         "REPLCONF" => commands::replconf_command::execute_replconf(app, token, context).await,
         "PSYNC" => commands::psync_command::execute_psync(app, token).await,
+        "DUMP" => commands::dump_command::execute_dump(app, token).await,
+        "RESTORE" => commands::restore_command::execute_restore(app, token).await,
         _ => commands::invalid_command::execute_invalid(),
     }
 }
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, value_container::ValueContainer},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_append(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(suffix)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let mut value = match command_utils::get_string_value(&mem, &key) {
+        Ok(value) => value.unwrap_or_default(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    value.push_str(&suffix);
+    let len = utils::string_to_bytes(&value).len();
+    command_utils::set_string_value(&mut mem, &key, ValueContainer::String(value));
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bloom_filter::BloomFilter, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_bf_add(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, item] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let bf = match command_utils::get_or_create_value(&mut mem, key, BloomFilter::default) {
+        Ok(bf) => bf,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    match bf.add(&utils::string_to_bytes(item)) {
+        Ok(added) => {
+            if added {
+                app.buffer_command(token).await;
+            }
+            resp_serializer::to_resp_integer(added as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+// Replies one integer per item, or the error for items a full nonscaling
+// filter could not take.
+pub async fn execute_bf_madd(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, items @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if items.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mut mem = app.memory.lock().await;
+    let bf = match command_utils::get_or_create_value(&mut mem, key, BloomFilter::default) {
+        Ok(bf) => bf,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut updated = false;
+    let replies = items
+        .iter()
+        .map(|item| match bf.add(&utils::string_to_bytes(item)) {
+            Ok(added) => {
+                updated |= added;
+                resp_serializer::to_resp_integer(added as i64)
+            }
+            Err(err) => resp_serializer::to_err_string(err.into()),
+        })
+        .collect();
+
+    if updated {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_nested_array(replies).into()
+}
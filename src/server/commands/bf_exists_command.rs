@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bloom_filter::BloomFilter, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_bf_exists(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, item] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<BloomFilter>(&mem, key) {
+        Ok(bf) => {
+            let exists = bf.is_some_and(|bf| bf.contains(&utils::string_to_bytes(item)));
+            resp_serializer::to_resp_integer(exists as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub async fn execute_bf_mexists(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, items @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if items.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mem = app.memory.lock().await;
+    let bf = match command_utils::get_value::<BloomFilter>(&mem, key) {
+        Ok(bf) => bf,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let replies = items
+        .iter()
+        .map(|item| {
+            let exists = bf.is_some_and(|bf| bf.contains(&utils::string_to_bytes(item)));
+            resp_serializer::to_resp_integer(exists as i64)
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(replies).into()
+}
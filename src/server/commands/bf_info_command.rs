@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bloom_filter::BloomFilter, execution_response::ExecResponse},
+};
+
+use super::command_utils;
+
+const FIELDS: [(&str, &str); 5] = [
+    ("CAPACITY", "Capacity"),
+    ("SIZE", "Size"),
+    ("FILTERS", "Number of filters"),
+    ("ITEMS", "Number of items inserted"),
+    ("EXPANSION", "Expansion rate"),
+];
+
+pub async fn execute_bf_info(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, field) = match args.as_slice() {
+        [key] => (key, None),
+        [key, field] => (key, Some(field.to_uppercase())),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let bf = match command_utils::get_value::<BloomFilter>(&mem, key) {
+        Ok(Some(bf)) => bf,
+        Ok(None) => return resp_serializer::to_err_string("ERR not found".into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let items = match field {
+        Some(field) => match FIELDS.iter().find(|(name, _)| *name == field) {
+            Some((name, _)) => vec![field_value(bf, name)],
+            None => {
+                return resp_serializer::to_err_string("ERR Invalid information value".into())
+                    .into()
+            }
+        },
+        None => FIELDS
+            .iter()
+            .flat_map(|(name, label)| {
+                [
+                    resp_serializer::to_resp_string(label.to_string()),
+                    field_value(bf, name),
+                ]
+            })
+            .collect(),
+    };
+    resp_serializer::to_resp_nested_array(items).into()
+}
+
+fn field_value(bf: &BloomFilter, name: &str) -> String {
+    match name {
+        "CAPACITY" => resp_serializer::to_resp_integer(bf.capacity() as i64),
+        "SIZE" => resp_serializer::to_resp_integer(bf.size() as i64),
+        "FILTERS" => resp_serializer::to_resp_integer(bf.filter_count() as i64),
+        "ITEMS" => resp_serializer::to_resp_integer(bf.items() as i64),
+        _ => match bf.expansion() {
+            Some(expansion) => resp_serializer::to_resp_integer(expansion as i64),
+            None => resp_serializer::null_resp_string(),
+        },
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        bloom_filter::{self, BloomFilter},
+        entry_value::EntryValue,
+        execution_response::ExecResponse,
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_bf_reserve(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, error_rate, capacity, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(error_rate) = command_utils::parse_float(error_rate) else {
+        return resp_serializer::to_err_string("ERR bad error rate".into()).into();
+    };
+    if error_rate <= 0.0 || error_rate >= 1.0 {
+        return resp_serializer::to_err_string("ERR (0 < error rate range < 1)".into()).into();
+    }
+
+    let Some(capacity) = command_utils::parse_integer(capacity) else {
+        return resp_serializer::to_err_string("ERR bad capacity".into()).into();
+    };
+    if capacity <= 0 {
+        return resp_serializer::to_err_string("ERR (capacity should be larger than 0)".into())
+            .into();
+    }
+
+    let mut options = options;
+    let mut expansion = None;
+    let mut nonscaling = false;
+    loop {
+        match options {
+            [] => break,
+            [option, rest @ ..] if option.eq_ignore_ascii_case("NONSCALING") => {
+                nonscaling = true;
+                options = rest;
+            }
+            [option, value, rest @ ..] if option.eq_ignore_ascii_case("EXPANSION") => {
+                match command_utils::parse_integer(value) {
+                    Some(value) if value >= 1 && value <= u32::MAX as i64 => {
+                        expansion = Some(value as u32)
+                    }
+                    Some(_) => {
+                        return resp_serializer::to_err_string(
+                            "ERR expansion should be greater or equal to 1".into(),
+                        )
+                        .into()
+                    }
+                    None => {
+                        return resp_serializer::to_err_string("ERR bad expansion".into()).into()
+                    }
+                }
+                options = rest;
+            }
+            _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        }
+    }
+
+    let expansion = match (nonscaling, expansion) {
+        (true, Some(_)) => {
+            return resp_serializer::to_err_string("ERR Nonscaling filters cannot expand".into())
+                .into()
+        }
+        (true, None) => None,
+        (false, expansion) => Some(expansion.unwrap_or(bloom_filter::DEFAULT_EXPANSION)),
+    };
+
+    let mut mem = app.memory.lock().await;
+    if mem.get(key).is_some_and(|entry| !entry.is_expired()) {
+        return resp_serializer::to_err_string("ERR item exists".into()).into();
+    }
+
+    mem.insert(
+        key.to_owned(),
+        EntryValue {
+            value: ValueContainer::BloomFilter(BloomFilter::new(
+                error_rate,
+                capacity as u64,
+                expansion,
+            )),
+            expires_at: None,
+        },
+    );
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".into()).into()
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bitmap, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_bitcount(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let Some(key) = command_utils::get_next_arg_string(&mut args) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let range_args: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+    if range_args.len() == 1 || range_args.len() > 3 {
+        return resp_serializer::to_err_string("ERR syntax error".into()).into();
+    }
+
+    let range = match command_utils::parse_bit_range(&range_args) {
+        Ok(range) => range,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let bytes = match command_utils::get_string_value(&mem, &key) {
+        Ok(value) => utils::string_to_bytes(&value.unwrap_or_default()),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let count = range
+        .resolve(bytes.len())
+        .map_or(0, |(first, last)| bitmap::count_bits(&bytes, first, last));
+
+    resp_serializer::to_resp_integer(count as i64).into()
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bitmap, execution_response::ExecResponse, value_container::ValueContainer},
+    utils,
+};
+
+use super::command_utils;
+
+const INVALID_TYPE_ERROR: &str =
+    "ERR Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is.";
+
+#[derive(Clone, Copy)]
+enum Overflow {
+    Wrap,
+    Sat,
+    Fail,
+}
+
+#[derive(Clone, Copy)]
+struct FieldType {
+    signed: bool,
+    bits: usize,
+}
+
+enum FieldOperation {
+    Get(FieldType, usize),
+    Set(FieldType, usize, i64, Overflow),
+    IncrBy(FieldType, usize, i64, Overflow),
+}
+
+pub async fn execute_bitfield(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    bitfield(app, token, false).await
+}
+
+pub async fn execute_bitfield_ro(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    bitfield(app, token, true).await
+}
+
+async fn bitfield(app: Arc<RedisApp>, token: &RespTk, read_only: bool) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let Some(key) = command_utils::get_next_arg_string(&mut args) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let operations = match get_operations(&mut args) {
+        Ok(operations) => operations,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let writes = operations
+        .iter()
+        .any(|op| !matches!(op, FieldOperation::Get(..)));
+
+    if read_only && writes {
+        return resp_serializer::to_err_string(
+            "ERR BITFIELD_RO only supports the GET subcommand".into(),
+        )
+        .into();
+    }
+
+    let mut mem = app.memory.lock().await;
+    let current = match command_utils::get_string_value(&mem, &key) {
+        Ok(value) => value,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut bytes = utils::string_to_bytes(current.as_deref().unwrap_or_default());
+    let mut replies = Vec::with_capacity(operations.len());
+
+    for operation in operations {
+        let reply = match operation {
+            FieldOperation::Get(field, offset) => Some(read_field(&bytes, field, offset)),
+            FieldOperation::Set(field, offset, value, overflow) => {
+                let old = read_field(&bytes, field, offset);
+                let value = if field.signed {
+                    value as i128
+                } else {
+                    value as u64 as i128
+                };
+                apply_overflow(field, value, overflow).map(|value| {
+                    bitmap::set_bits(&mut bytes, offset, field.bits, value as u64);
+                    old
+                })
+            }
+            FieldOperation::IncrBy(field, offset, increment, overflow) => {
+                let old = read_field(&bytes, field, offset);
+                apply_overflow(field, old as i128 + increment as i128, overflow).map(|value| {
+                    bitmap::set_bits(&mut bytes, offset, field.bits, value as u64);
+                    value as i64
+                })
+            }
+        };
+
+        replies.push(match reply {
+            Some(value) => resp_serializer::to_resp_integer(value),
+            None => resp_serializer::null_resp_string(),
+        });
+    }
+
+    if writes {
+        command_utils::set_string_value(
+            &mut mem,
+            &key,
+            ValueContainer::String(utils::bytes_to_string(&bytes)),
+        );
+        app.buffer_command(token).await;
+    }
+
+    resp_serializer::to_resp_nested_array(replies).into()
+}
+
+fn get_operations<'a>(
+    args: &mut impl Iterator<Item = &'a RespTk>,
+) -> Result<Vec<FieldOperation>, String> {
+    let mut operations = Vec::new();
+    let mut overflow = Overflow::Wrap;
+    let next = |args: &mut dyn Iterator<Item = &'a RespTk>| {
+        args.next()
+            .and_then(|tk| tk.get_content_string())
+            .ok_or("ERR syntax error".to_owned())
+    };
+
+    while let Some(subcommand) = command_utils::get_next_arg_string(args) {
+        let subcommand = subcommand.to_uppercase();
+
+        if subcommand == "OVERFLOW" {
+            overflow = match next(args)?.to_uppercase().as_str() {
+                "WRAP" => Overflow::Wrap,
+                "SAT" => Overflow::Sat,
+                "FAIL" => Overflow::Fail,
+                _ => return Err("ERR Invalid OVERFLOW type specified".into()),
+            };
+            continue;
+        }
+
+        if !matches!(subcommand.as_str(), "GET" | "SET" | "INCRBY") {
+            return Err("ERR syntax error".into());
+        }
+
+        let field = parse_field_type(&next(args)?)?;
+        let offset = parse_field_offset(&next(args)?, field)?;
+
+        let operation = match subcommand.as_str() {
+            "GET" => FieldOperation::Get(field, offset),
+            "SET" => FieldOperation::Set(field, offset, parse_value(&next(args)?)?, overflow),
+            _ => FieldOperation::IncrBy(field, offset, parse_value(&next(args)?)?, overflow),
+        };
+        operations.push(operation);
+    }
+
+    Ok(operations)
+}
+
+fn parse_field_type(value: &str) -> Result<FieldType, String> {
+    let signed = match value.chars().next() {
+        Some('i') | Some('I') => true,
+        Some('u') | Some('U') => false,
+        _ => return Err(INVALID_TYPE_ERROR.into()),
+    };
+
+    let max_bits = if signed { 64 } else { 63 };
+    match value[1..].parse::<usize>() {
+        Ok(bits) if (1..=max_bits).contains(&bits) => Ok(FieldType { signed, bits }),
+        _ => Err(INVALID_TYPE_ERROR.into()),
+    }
+}
+
+fn parse_field_offset(value: &str, field: FieldType) -> Result<usize, String> {
+    let (multiplier, offset) = match value.strip_prefix('#') {
+        Some(offset) => (field.bits, offset),
+        None => (1, value),
+    };
+
+    offset
+        .parse::<usize>()
+        .ok()
+        .and_then(|offset| offset.checked_mul(multiplier))
+        .and_then(|offset| command_utils::parse_bit_offset(&(offset + field.bits - 1).to_string()))
+        .map(|last_bit| last_bit + 1 - field.bits)
+        .ok_or("ERR bit offset is not an integer or out of range".into())
+}
+
+fn parse_value(value: &str) -> Result<i64, String> {
+    command_utils::parse_integer(value).ok_or("ERR value is not an integer or out of range".into())
+}
+
+fn read_field(bytes: &[u8], field: FieldType, offset: usize) -> i64 {
+    let raw = bitmap::get_bits(bytes, offset, field.bits);
+    if field.signed && field.bits < 64 {
+        let shift = 64 - field.bits;
+        ((raw << shift) as i64) >> shift
+    } else {
+        raw as i64
+    }
+}
+
+fn apply_overflow(field: FieldType, value: i128, overflow: Overflow) -> Option<i128> {
+    let (min, max) = if field.signed {
+        (
+            -(1i128 << (field.bits - 1)),
+            (1i128 << (field.bits - 1)) - 1,
+        )
+    } else {
+        (0, (1i128 << field.bits) - 1)
+    };
+
+    if (min..=max).contains(&value) {
+        return Some(value);
+    }
+
+    match overflow {
+        Overflow::Fail => None,
+        Overflow::Sat => Some(value.clamp(min, max)),
+        Overflow::Wrap => {
+            let wrapped = value.rem_euclid(1i128 << field.bits);
+            if field.signed && wrapped > max {
+                Some(wrapped - (1i128 << field.bits))
+            } else {
+                Some(wrapped)
+            }
+        }
+    }
+}
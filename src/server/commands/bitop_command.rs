@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        entry_value::EntryValue, execution_response::ExecResponse, value_container::ValueContainer,
+    },
+    utils,
+};
+
+use super::command_utils;
+
+#[derive(PartialEq, Eq)]
+enum BitOperation {
+    And,
+    Or,
+    Xor,
+    Not,
+    Diff,
+}
+
+pub async fn execute_bitop(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(operation), Some(dest_key)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let source_keys: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+    if source_keys.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let operation = match operation.to_uppercase().as_str() {
+        "AND" => BitOperation::And,
+        "OR" => BitOperation::Or,
+        "XOR" => BitOperation::Xor,
+        "NOT" => BitOperation::Not,
+        "DIFF" => BitOperation::Diff,
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    if operation == BitOperation::Not && source_keys.len() != 1 {
+        return resp_serializer::to_err_string(
+            "ERR BITOP NOT must be called with a single source key.".into(),
+        )
+        .into();
+    }
+
+    if operation == BitOperation::Diff && source_keys.len() < 2 {
+        return resp_serializer::to_err_string(
+            "ERR BITOP DIFF must be called with at least two source keys.".into(),
+        )
+        .into();
+    }
+
+    let mut mem = app.memory.lock().await;
+    let mut sources = Vec::with_capacity(source_keys.len());
+    for key in source_keys.iter() {
+        match command_utils::get_string_value(&mem, key) {
+            Ok(value) => sources.push(utils::string_to_bytes(&value.unwrap_or_default())),
+            Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+        }
+    }
+
+    let result = apply_operation(&operation, &sources);
+    let len = result.len();
+
+    if result.is_empty() {
+        mem.remove(&dest_key);
+    } else {
+        mem.insert(
+            dest_key,
+            EntryValue {
+                value: ValueContainer::String(utils::bytes_to_string(&result)),
+                expires_at: None,
+            },
+        );
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
+
+fn apply_operation(operation: &BitOperation, sources: &[Vec<u8>]) -> Vec<u8> {
+    let len = sources.iter().map(|source| source.len()).max().unwrap_or(0);
+    let byte_at = |source: &Vec<u8>, idx: usize| source.get(idx).copied().unwrap_or(0);
+
+    (0..len)
+        .map(|idx| {
+            let mut bytes = sources.iter().map(|source| byte_at(source, idx));
+            let first = bytes.next().unwrap_or(0);
+            match operation {
+                BitOperation::And => bytes.fold(first, |acc, byte| acc & byte),
+                BitOperation::Or => bytes.fold(first, |acc, byte| acc | byte),
+                BitOperation::Xor => bytes.fold(first, |acc, byte| acc ^ byte),
+                BitOperation::Not => !first,
+                BitOperation::Diff => first & !bytes.fold(0, |acc, byte| acc | byte),
+            }
+        })
+        .collect()
+}
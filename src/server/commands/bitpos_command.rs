@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bitmap, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_bitpos(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(bit)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let bit = match bit.as_str() {
+        "0" => 0,
+        "1" => 1,
+        _ => {
+            return resp_serializer::to_err_string("ERR The bit argument must be 1 or 0.".into())
+                .into()
+        }
+    };
+
+    let mut range_args: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+    if range_args.len() > 3 {
+        return resp_serializer::to_err_string("ERR syntax error".into()).into();
+    }
+
+    let end_given = range_args.len() > 1;
+    if range_args.len() == 1 {
+        range_args.push("-1".into());
+    }
+
+    let range = match command_utils::parse_bit_range(&range_args) {
+        Ok(range) => range,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let bytes = match command_utils::get_string_value(&mem, &key) {
+        Ok(Some(value)) => utils::string_to_bytes(&value),
+        Ok(None) => {
+            let pos = if bit == 1 { -1 } else { 0 };
+            return resp_serializer::to_resp_integer(pos).into();
+        }
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let Some((first, last)) = range.resolve(bytes.len()) else {
+        return resp_serializer::to_resp_integer(-1).into();
+    };
+
+    let pos = match bitmap::find_bit(&bytes, bit, first, last) {
+        Some(pos) => pos as i64,
+        None if bit == 0 && !end_given => last as i64 + 1,
+        None => -1,
+    };
+
+    resp_serializer::to_resp_integer(pos).into()
+}
@@ -0,0 +1,127 @@
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        connection_context::ConnectionContext, execution_response::ExecResponse,
+        keyspace::Keyspace, waiter_registry::WaiterOutcome,
+    },
+};
+
+use super::{
+    command_utils::{self, ListEnd},
+    lmove_command,
+};
+
+pub async fn execute_blmove(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [source, destination, from, to, timeout] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (Some(from), Some(to)) = (ListEnd::parse(from), ListEnd::parse(to)) else {
+        return resp_serializer::to_err_string("ERR syntax error".into()).into();
+    };
+
+    let timeout = match command_utils::parse_timeout(timeout) {
+        Ok(timeout) => timeout,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    blmove(app, context, source, destination, from, to, timeout).await
+}
+
+pub async fn execute_brpoplpush(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [source, destination, timeout] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let timeout = match command_utils::parse_timeout(timeout) {
+        Ok(timeout) => timeout,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    blmove(
+        app,
+        context,
+        source,
+        destination,
+        ListEnd::Right,
+        ListEnd::Left,
+        timeout,
+    )
+    .await
+}
+
+async fn blmove(
+    app: Arc<RedisApp>,
+    context: &ConnectionContext,
+    source: &str,
+    destination: &str,
+    from: ListEnd,
+    to: ListEnd,
+    timeout: Option<Duration>,
+) -> ExecResponse {
+    let mem = app.memory.lock().await;
+    if let Err(err) = check_types(&mem, source, destination) {
+        return resp_serializer::to_err_string(err.into()).into();
+    }
+    drop(mem);
+
+    let destination = destination.to_owned();
+    let handler = Box::new(move |mem: &mut Keyspace, key: &str| {
+        let response = match lmove_command::move_element(mem, key, &destination, from, to) {
+            Ok(Some(element)) => resp_serializer::to_resp_bulk(element),
+            // Either key holding another type keeps the client blocked.
+            Ok(None) | Err(_) => return None,
+        };
+
+        let command = vec![
+            "LMOVE".to_owned(),
+            key.to_owned(),
+            destination.clone(),
+            from.as_str().to_owned(),
+            to.as_str().to_owned(),
+        ];
+        Some(WaiterOutcome {
+            response,
+            commands: vec![command_utils::to_command_token(command)],
+            ready_keys: vec![destination.clone()],
+        })
+    });
+
+    match app
+        .serve_or_block(vec![source.to_owned()], timeout, handler, context)
+        .await
+    {
+        Some(response) => response.into(),
+        None => resp_serializer::null_resp_string().into(),
+    }
+}
+
+// The destination's type only matters once there is an element to move.
+fn check_types(mem: &Keyspace, source: &str, destination: &str) -> Result<(), &'static str> {
+    if command_utils::get_value::<VecDeque<String>>(mem, source)?.is_some() {
+        command_utils::get_value::<VecDeque<String>>(mem, destination)?;
+    }
+    Ok(())
+}
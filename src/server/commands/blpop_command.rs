@@ -0,0 +1,183 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        connection_context::ConnectionContext,
+        execution_response::ExecResponse,
+        keyspace::Keyspace,
+        waiter_registry::{WaiterHandler, WaiterOutcome},
+    },
+};
+
+use super::{
+    command_utils::{self, ListEnd},
+    pop_command,
+};
+
+pub async fn execute_blpop(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    bpop(app, token, context, ListEnd::Left).await
+}
+
+pub async fn execute_brpop(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    bpop(app, token, context, ListEnd::Right).await
+}
+
+pub async fn execute_blmpop(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [timeout, num_keys, rest @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if rest.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let timeout = match command_utils::parse_timeout(timeout) {
+        Ok(timeout) => timeout,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let (keys, end, count) = match parse_mpop_args(num_keys, rest) {
+        Ok(parsed) => parsed,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    if let Err(err) = command_utils::check_blocking_keys::<VecDeque<String>>(&mem, &keys) {
+        return resp_serializer::to_err_string(err.into()).into();
+    }
+    drop(mem);
+
+    match app
+        .serve_or_block(keys, timeout, pop_handler(end, Some(count)), context)
+        .await
+    {
+        Some(response) => response.into(),
+        None => resp_serializer::null_resp_array().into(),
+    }
+}
+
+async fn bpop(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+    end: ListEnd,
+) -> ExecResponse {
+    let mut keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (Some(timeout), false) = (keys.pop(), keys.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let timeout = match command_utils::parse_timeout(&timeout) {
+        Ok(timeout) => timeout,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    if let Err(err) = command_utils::check_blocking_keys::<VecDeque<String>>(&mem, &keys) {
+        return resp_serializer::to_err_string(err.into()).into();
+    }
+    drop(mem);
+
+    match app
+        .serve_or_block(keys, timeout, pop_handler(end, None), context)
+        .await
+    {
+        Some(response) => response.into(),
+        None => resp_serializer::null_resp_array().into(),
+    }
+}
+
+fn parse_mpop_args(
+    num_keys: &str,
+    args: &[String],
+) -> Result<(Vec<String>, ListEnd, usize), &'static str> {
+    let num_keys = command_utils::parse_integer(num_keys)
+        .ok_or("ERR value is not an integer or out of range")?;
+    if num_keys <= 0 {
+        return Err("ERR numkeys should be greater than 0");
+    }
+
+    let num_keys = num_keys as usize;
+    if num_keys >= args.len() {
+        return Err("ERR syntax error");
+    }
+
+    let (keys, options) = args.split_at(num_keys);
+    let end = ListEnd::parse(&options[0]).ok_or("ERR syntax error")?;
+
+    let count = match &options[1..] {
+        [] => 1,
+        [option, count] if option.eq_ignore_ascii_case("COUNT") => {
+            match command_utils::parse_integer(count) {
+                Some(count) if count > 0 => count as usize,
+                _ => return Err("ERR count should be greater than 0"),
+            }
+        }
+        _ => return Err("ERR syntax error"),
+    };
+
+    Ok((keys.to_vec(), end, count))
+}
+
+// Pops from the first non-empty key. With a count the reply follows the
+// LMPOP shape of key plus elements, otherwise the BLPOP key/element pair.
+fn pop_handler(end: ListEnd, count: Option<usize>) -> WaiterHandler {
+    Box::new(move |mem: &mut Keyspace, key: &str| {
+        // A key replaced by another type keeps the client blocked.
+        let list = command_utils::get_value_mut::<VecDeque<String>>(mem, key).ok()??;
+
+        let elements = pop_command::pop_elements(list, end, count.unwrap_or(1));
+        if elements.is_empty() {
+            return None;
+        }
+        command_utils::remove_if_empty(mem, key);
+
+        let pop = match end {
+            ListEnd::Left => "LPOP",
+            ListEnd::Right => "RPOP",
+        };
+        let mut command = vec![pop.to_owned(), key.to_owned()];
+
+        let response = match count {
+            Some(_) => {
+                command.push(elements.len().to_string());
+                resp_serializer::to_resp_nested_array(vec![
+                    resp_serializer::to_resp_bulk(key.to_owned()),
+                    resp_serializer::to_resp_array(elements),
+                ])
+            }
+            None => resp_serializer::to_resp_array(
+                [key.to_owned()].into_iter().chain(elements).collect(),
+            ),
+        };
+
+        Some(WaiterOutcome {
+            response,
+            commands: vec![command_utils::to_command_token(command)],
+            ready_keys: vec![],
+        })
+    })
+}
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        connection_context::ConnectionContext,
+        execution_response::ExecResponse,
+        keyspace::Keyspace,
+        sorted_set::SortedSet,
+        waiter_registry::{WaiterHandler, WaiterOutcome},
+    },
+};
+
+use super::{command_utils, zpop_command};
+
+pub async fn execute_bzpopmin(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    bzpop(app, token, context, false).await
+}
+
+pub async fn execute_bzpopmax(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    bzpop(app, token, context, true).await
+}
+
+pub async fn execute_zmpop(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [num_keys, rest @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if rest.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let (keys, max, count) = match parse_mpop_args(num_keys, rest) {
+        Ok(parsed) => parsed,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut handler = pop_handler(max, Some(count));
+    let mut mem = app.memory.lock().await;
+    for key in &keys {
+        if let Some(outcome) = handler(&mut mem, key) {
+            for command in &outcome.commands {
+                app.buffer_command(command).await;
+            }
+            return outcome.response.into();
+        }
+    }
+    resp_serializer::null_resp_array().into()
+}
+
+pub async fn execute_bzmpop(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [timeout, num_keys, rest @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if rest.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let timeout = match command_utils::parse_timeout(timeout) {
+        Ok(timeout) => timeout,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let (keys, max, count) = match parse_mpop_args(num_keys, rest) {
+        Ok(parsed) => parsed,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    if let Err(err) = command_utils::check_blocking_keys::<SortedSet>(&mem, &keys) {
+        return resp_serializer::to_err_string(err.into()).into();
+    }
+    drop(mem);
+
+    match app
+        .serve_or_block(keys, timeout, pop_handler(max, Some(count)), context)
+        .await
+    {
+        Some(response) => response.into(),
+        None => resp_serializer::null_resp_array().into(),
+    }
+}
+
+async fn bzpop(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+    max: bool,
+) -> ExecResponse {
+    let mut keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (Some(timeout), false) = (keys.pop(), keys.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let timeout = match command_utils::parse_timeout(&timeout) {
+        Ok(timeout) => timeout,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    if let Err(err) = command_utils::check_blocking_keys::<SortedSet>(&mem, &keys) {
+        return resp_serializer::to_err_string(err.into()).into();
+    }
+    drop(mem);
+
+    match app
+        .serve_or_block(keys, timeout, pop_handler(max, None), context)
+        .await
+    {
+        Some(response) => response.into(),
+        None => resp_serializer::null_resp_array().into(),
+    }
+}
+
+// Parses `numkeys key [key ...] MIN|MAX [COUNT count]`, returning whether to
+// pop the highest scores.
+fn parse_mpop_args(
+    num_keys: &str,
+    args: &[String],
+) -> Result<(Vec<String>, bool, usize), &'static str> {
+    let num_keys = command_utils::parse_integer(num_keys)
+        .ok_or("ERR value is not an integer or out of range")?;
+    if num_keys <= 0 {
+        return Err("ERR numkeys should be greater than 0");
+    }
+
+    let num_keys = num_keys as usize;
+    if num_keys >= args.len() {
+        return Err("ERR syntax error");
+    }
+
+    let (keys, options) = args.split_at(num_keys);
+    let max = match options[0].to_uppercase().as_str() {
+        "MIN" => false,
+        "MAX" => true,
+        _ => return Err("ERR syntax error"),
+    };
+
+    let count = match &options[1..] {
+        [] => 1,
+        [option, count] if option.eq_ignore_ascii_case("COUNT") => {
+            match command_utils::parse_integer(count) {
+                Some(count) if count > 0 => count as usize,
+                _ => return Err("ERR count should be greater than 0"),
+            }
+        }
+        _ => return Err("ERR syntax error"),
+    };
+
+    Ok((keys.to_vec(), max, count))
+}
+
+// Pops from the first non-empty key. With a count the reply follows the
+// ZMPOP shape of key plus member/score pairs, otherwise the BZPOPMIN
+// key/member/score triple.
+fn pop_handler(max: bool, count: Option<usize>) -> WaiterHandler {
+    Box::new(move |mem: &mut Keyspace, key: &str| {
+        let zset = command_utils::get_value_mut::<SortedSet>(mem, key).ok()??;
+
+        let popped = zpop_command::pop_entries(zset, max, count.unwrap_or(1));
+        if popped.is_empty() {
+            return None;
+        }
+        command_utils::remove_if_empty(mem, key);
+
+        let pop = if max { "ZPOPMAX" } else { "ZPOPMIN" };
+        let command = vec![pop.to_owned(), key.to_owned(), popped.len().to_string()];
+
+        let response = match count {
+            Some(_) => {
+                let pairs = popped
+                    .into_iter()
+                    .map(|(member, score)| {
+                        resp_serializer::to_resp_array(vec![
+                            member,
+                            command_utils::format_float(score),
+                        ])
+                    })
+                    .collect();
+                resp_serializer::to_resp_nested_array(vec![
+                    resp_serializer::to_resp_bulk(key.to_owned()),
+                    resp_serializer::to_resp_nested_array(pairs),
+                ])
+            }
+            None => resp_serializer::to_resp_array(
+                [key.to_owned()]
+                    .into_iter()
+                    .chain(zpop_command::flatten_entries(popped))
+                    .collect(),
+            ),
+        };
+
+        Some(WaiterOutcome {
+            response,
+            commands: vec![command_utils::to_command_token(command)],
+            ready_keys: vec![],
+        })
+    })
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{cuckoo_filter::CuckooFilter, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+// Cuckoo filters keep duplicates, so every successful add replies 1.
+pub async fn execute_cf_add(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, item] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let cf = match command_utils::get_or_create_value(&mut mem, key, CuckooFilter::default) {
+        Ok(cf) => cf,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    if let Err(err) = cf.add(&utils::string_to_bytes(item)) {
+        return resp_serializer::to_err_string(err.into()).into();
+    }
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(1).into()
+}
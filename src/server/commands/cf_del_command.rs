@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{cuckoo_filter::CuckooFilter, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_cf_del(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, item] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let cf = match command_utils::get_value_mut::<CuckooFilter>(&mut mem, key) {
+        Ok(Some(cf)) => cf,
+        Ok(None) => return resp_serializer::to_err_string("ERR Not found".into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let deleted = cf.delete(&utils::string_to_bytes(item));
+    if deleted {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(deleted as i64).into()
+}
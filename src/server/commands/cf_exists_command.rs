@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{cuckoo_filter::CuckooFilter, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_cf_exists(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, item] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<CuckooFilter>(&mem, key) {
+        Ok(cf) => {
+            let exists = cf.is_some_and(|cf| cf.contains(&utils::string_to_bytes(item)));
+            resp_serializer::to_resp_integer(exists as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
@@ -1,5 +1,477 @@
-use crate::resp_desserializer::RespTk;
+use std::time::Duration;
+
+use crate::{
+    resp_desserializer::RespTk,
+    types::{
+        entry_value::EntryValue,
+        hyperloglog::HyperLogLog,
+        keyspace::Keyspace,
+        set::RedisSet,
+        sorted_set::SortedSet,
+        value_container::{TypedValue, ValueContainer},
+    },
+    utils,
+};
+
+pub const WRONGTYPE_ERROR: &str =
+    "WRONGTYPE Operation against a key holding the wrong kind of value";
+pub const SCORE_RANGE_ERROR: &str = "ERR min or max is not a float";
+pub const LEX_RANGE_ERROR: &str = "ERR min or max not valid string range item";
+
+const MAX_BIT_OFFSET: i64 = 512 * 1024 * 1024 * 8;
+
+pub struct BitRange {
+    bounds: Option<(i64, i64)>,
+    bit_unit: bool,
+}
+
+impl BitRange {
+    pub fn resolve(&self, len: usize) -> Option<(usize, usize)> {
+        if len == 0 {
+            return None;
+        }
+
+        let Some((start, end)) = self.bounds else {
+            return Some((0, len * 8 - 1));
+        };
+
+        if self.bit_unit {
+            normalize_range(len as i64 * 8, start, end)
+        } else {
+            normalize_range(len as i64, start, end).map(|(start, end)| (start * 8, end * 8 + 7))
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum ListEnd {
+    Left,
+    Right,
+}
+
+impl ListEnd {
+    pub fn parse(value: &str) -> Option<ListEnd> {
+        match value.to_uppercase().as_str() {
+            "LEFT" => Some(ListEnd::Left),
+            "RIGHT" => Some(ListEnd::Right),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListEnd::Left => "LEFT",
+            ListEnd::Right => "RIGHT",
+        }
+    }
+}
+
+pub struct ScanOptions {
+    pub cursor: u64,
+    pub pattern: Option<String>,
+    pub count: usize,
+    pub no_values: bool,
+}
+
+impl ScanOptions {
+    pub fn matches(&self, member: &str) -> bool {
+        self.pattern
+            .as_ref()
+            .is_none_or(|pattern| utils::glob_match(pattern, member))
+    }
+}
 
 pub fn get_next_arg_string<'a>(args: &mut impl Iterator<Item = &'a RespTk>) -> Option<String> {
     args.next().and_then(|t| t.get_content_string())
 }
+
+pub fn arity_error(token: &RespTk) -> String {
+    format!(
+        "ERR wrong number of arguments for '{}' command",
+        token.get_command_name().to_lowercase()
+    )
+}
+
+pub fn parse_integer(value: &str) -> Option<i64> {
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|int| int.to_string() == value)
+}
+
+pub fn parse_bit_offset(value: &str) -> Option<usize> {
+    parse_integer(value)
+        .filter(|offset| (0..MAX_BIT_OFFSET).contains(offset))
+        .map(|offset| offset as usize)
+}
+
+pub fn parse_float(value: &str) -> Option<f64> {
+    if value.is_empty() || value.starts_with(char::is_whitespace) {
+        return None;
+    }
+    value.parse::<f64>().ok().filter(|float| !float.is_nan())
+}
+
+// Blocking timeouts are fractional seconds, where 0 means block forever.
+pub fn parse_timeout(value: &str) -> Result<Option<Duration>, &'static str> {
+    let seconds = parse_float(value)
+        .filter(|seconds| seconds.is_finite())
+        .ok_or("ERR timeout is not a float or out of range")?;
+
+    let millis = (seconds * 1000.0) as i64;
+    if millis < 0 {
+        return Err("ERR timeout is negative");
+    }
+
+    Ok((millis > 0).then(|| Duration::from_millis(millis as u64)))
+}
+
+// Formats like Redis's `%.17g`, with the shortest digits that round-trip:
+// exponents below -4 or from 17 up are written in scientific notation.
+pub fn format_float(value: f64) -> String {
+    if value == 0.0 {
+        return "0".into();
+    }
+
+    let scientific = format!("{:e}", value);
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return value.to_string();
+    };
+    match exponent.parse::<i32>() {
+        Ok(exponent) if !(-4..17).contains(&exponent) => {
+            let sign = if exponent < 0 { '-' } else { '+' };
+            format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+        }
+        _ => value.to_string(),
+    }
+}
+
+// INCRBYFLOAT and HINCRBYFLOAT reply in fixed notation however large or
+// small the value, with the shortest digits that round-trip.
+pub fn format_fixed_float(value: f64) -> String {
+    match value == 0.0 {
+        true => "0".into(),
+        false => value.to_string(),
+    }
+}
+
+pub fn to_command_token(args: Vec<String>) -> RespTk {
+    RespTk::Array(args.into_iter().map(RespTk::BulkString).collect())
+}
+
+pub fn get_string_value(mem: &Keyspace, key: &str) -> Result<Option<String>, &'static str> {
+    match mem.get(key).filter(|entry| !entry.is_expired()) {
+        Some(entry) => match &entry.value {
+            ValueContainer::String(str) => Ok(Some(str.to_owned())),
+            ValueContainer::Integer(int) => Ok(Some(int.to_string())),
+            _ => Err(WRONGTYPE_ERROR),
+        },
+        None => Ok(None),
+    }
+}
+
+pub fn get_hyperloglog(mem: &Keyspace, key: &str) -> Result<Option<HyperLogLog>, String> {
+    let Some(value) = get_string_value(mem, key)? else {
+        return Ok(None);
+    };
+
+    HyperLogLog::from_bytes(utils::string_to_bytes(&value))
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+// Looks up the value of type `T` at `key`, failing with WRONGTYPE when the key
+// holds another type. A hash whose fields all expired counts as missing.
+pub fn get_value<'a, T: TypedValue>(
+    mem: &'a Keyspace,
+    key: &str,
+) -> Result<Option<&'a T>, &'static str> {
+    let entry = mem.get(key).filter(|entry| !entry.is_expired());
+    match entry.map(|entry| &entry.value) {
+        Some(ValueContainer::Hash(hash)) if hash.is_empty() => Ok(None),
+        Some(value) => T::from_value(value).map(Some).ok_or(WRONGTYPE_ERROR),
+        None => Ok(None),
+    }
+}
+
+pub fn get_value_mut<'a, T: TypedValue>(
+    mem: &'a mut Keyspace,
+    key: &str,
+) -> Result<Option<&'a mut T>, &'static str> {
+    remove_expired_key(mem, key);
+
+    match mem.get_mut(key) {
+        Some(entry) => T::from_value_mut(&mut entry.value)
+            .map(Some)
+            .ok_or(WRONGTYPE_ERROR),
+        None => Ok(None),
+    }
+}
+
+// Inserts the value built by `create` when `key` is missing.
+pub fn get_or_create_value<'a, T: TypedValue>(
+    mem: &'a mut Keyspace,
+    key: &str,
+    create: impl FnOnce() -> T,
+) -> Result<&'a mut T, &'static str> {
+    remove_expired_key(mem, key);
+
+    let entry = mem.entry(key.to_owned()).or_insert_with(|| EntryValue {
+        value: create().into_value(),
+        expires_at: None,
+    });
+    T::from_value_mut(&mut entry.value).ok_or(WRONGTYPE_ERROR)
+}
+
+// Drops `key` once expired, and the expired fields of a hash at `key`.
+fn remove_expired_key(mem: &mut Keyspace, key: &str) {
+    if mem.get(key).is_some_and(|entry| entry.is_expired()) {
+        mem.remove(key);
+    }
+    remove_expired_fields(mem, key);
+}
+
+// A blocking command fails right away, like Redis does, when a key before
+// the first one holding a `T` has another type. Once blocked, keys changing
+// type just leave the client waiting.
+pub fn check_blocking_keys<T: TypedValue>(
+    mem: &Keyspace,
+    keys: &[String],
+) -> Result<(), &'static str> {
+    for key in keys {
+        if get_value::<T>(mem, key)?.is_some() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+// Replaces `key` with the result of a sorted set *STORE command, deleting it
+// when empty.
+pub fn store_sorted_set(mem: &mut Keyspace, key: &str, zset: SortedSet) -> usize {
+    let len = zset.len();
+    if zset.is_empty() {
+        mem.remove(key);
+    } else {
+        mem.insert(
+            key.to_owned(),
+            EntryValue {
+                value: ValueContainer::SortedSet(zset),
+                expires_at: None,
+            },
+        );
+    }
+    len
+}
+
+// Looks up every key up front so a WRONGTYPE anywhere fails the whole command.
+pub fn get_sets<'a>(
+    mem: &'a Keyspace,
+    keys: &[String],
+) -> Result<Vec<Option<&'a RedisSet>>, &'static str> {
+    keys.iter()
+        .map(|key| get_value::<RedisSet>(mem, key))
+        .collect()
+}
+
+// Replaces `key` with the result of a *STORE command, deleting it when empty.
+pub fn store_set(mem: &mut Keyspace, key: &str, set: RedisSet) -> usize {
+    let len = set.len();
+    if set.is_empty() {
+        mem.remove(key);
+    } else {
+        mem.insert(
+            key.to_owned(),
+            EntryValue {
+                value: ValueContainer::Set(set),
+                expires_at: None,
+            },
+        );
+    }
+    len
+}
+
+// Reclaims the expired fields of a hash, dropping the key if none are left.
+pub fn remove_expired_fields(mem: &mut Keyspace, key: &str) {
+    if let Some(ValueContainer::Hash(hash)) = mem.get_mut(key).map(|entry| &mut entry.value) {
+        hash.remove_expired();
+    }
+    remove_if_empty(mem, key);
+}
+
+// Collections never stay in the keyspace once their last element is removed.
+pub fn remove_if_empty(mem: &mut Keyspace, key: &str) {
+    let is_empty = match mem.get(key).map(|entry| &entry.value) {
+        Some(ValueContainer::List(list)) => list.is_empty(),
+        Some(ValueContainer::Hash(hash)) => hash.is_empty(),
+        Some(ValueContainer::Set(set)) => set.is_empty(),
+        Some(ValueContainer::SortedSet(zset)) => zset.is_empty(),
+        Some(ValueContainer::VectorSet(vset)) => vset.is_empty(),
+        _ => false,
+    };
+
+    if is_empty {
+        mem.remove(key);
+    }
+}
+
+pub fn set_hyperloglog(mem: &mut Keyspace, key: &str, hll: HyperLogLog) {
+    let value = utils::bytes_to_string(&hll.into_bytes());
+    set_string_value(mem, key, ValueContainer::String(value));
+}
+
+pub fn set_string_value(mem: &mut Keyspace, key: &str, value: ValueContainer) {
+    match mem.get_mut(key).filter(|entry| !entry.is_expired()) {
+        Some(entry) => entry.value = value,
+        None => {
+            mem.insert(
+                key.to_owned(),
+                EntryValue {
+                    value,
+                    expires_at: None,
+                },
+            );
+        }
+    }
+}
+
+pub fn get_expiration_time(command: &str, unit: &str, time: &str) -> Result<u128, String> {
+    let time = time
+        .parse::<i64>()
+        .map_err(|_| "ERR value is not an integer or out of range".to_owned())?;
+
+    if time <= 0 {
+        return Err(format!("ERR invalid expire time in '{}' command", command));
+    }
+
+    let time = time as u128;
+    let expires_at = match unit {
+        "EX" => utils::get_current_time_ms() + time * 1000,
+        "PX" => utils::get_current_time_ms() + time,
+        "EXAT" => time * 1000,
+        _ => time,
+    };
+
+    if expires_at > i64::MAX as u128 {
+        return Err(format!("ERR invalid expire time in '{}' command", command));
+    }
+
+    Ok(expires_at)
+}
+
+pub fn normalize_range(len: i64, mut start: i64, mut end: i64) -> Option<(usize, usize)> {
+    if start < 0 && end < 0 && start > end {
+        return None;
+    }
+    if start < 0 {
+        start += len;
+    }
+    if end < 0 {
+        end += len;
+    }
+    start = start.max(0);
+    end = end.max(0).min(len - 1);
+
+    if start > end || len == 0 {
+        return None;
+    }
+
+    Some((start as usize, end as usize))
+}
+
+// Unlike normalize_range, an end index before the start of the list selects nothing.
+pub fn normalize_list_range(len: usize, start: i64, end: i64) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 {
+        (start + len).max(0)
+    } else {
+        start
+    };
+    let end = if end < 0 { end + len } else { end.min(len - 1) };
+
+    if start > end || start >= len {
+        return None;
+    }
+
+    Some((start as usize, end as usize))
+}
+
+pub fn resolve_index(len: usize, index: i64) -> Option<usize> {
+    let index = if index < 0 { index + len as i64 } else { index };
+    (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+// Parses `cursor [MATCH pattern] [COUNT count]`, plus NOVALUES when allowed.
+pub fn parse_scan_args(
+    args: &[String],
+    allow_no_values: bool,
+) -> Result<ScanOptions, &'static str> {
+    let Some((cursor, options)) = args.split_first() else {
+        return Err("ERR syntax error");
+    };
+
+    let mut opts = ScanOptions {
+        cursor: cursor.parse::<u64>().map_err(|_| "ERR invalid cursor")?,
+        pattern: None,
+        count: 10,
+        no_values: false,
+    };
+
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.to_uppercase().as_str() {
+            "MATCH" => opts.pattern = Some(options.next().ok_or("ERR syntax error")?.to_owned()),
+            "COUNT" => {
+                let count = options.next().ok_or("ERR syntax error")?;
+                match parse_integer(count) {
+                    Some(count) if count >= 1 => opts.count = count as usize,
+                    Some(_) => return Err("ERR syntax error"),
+                    None => return Err("ERR value is not an integer or out of range"),
+                }
+            }
+            "NOVALUES" if allow_no_values => opts.no_values = true,
+            _ => return Err("ERR syntax error"),
+        }
+    }
+
+    Ok(opts)
+}
+
+// Parses the `FIELDS numfields field...` tail of the hash field TTL commands.
+pub fn parse_fields_arg(args: &[String]) -> Result<Vec<String>, &'static str> {
+    match args.first() {
+        Some(keyword) if keyword.eq_ignore_ascii_case("FIELDS") => {}
+        _ => return Err("ERR Mandatory argument FIELDS is missing or not at the right position"),
+    }
+
+    let count = match args.get(1).map(|count| parse_integer(count)) {
+        Some(Some(count)) if count > 0 => count as usize,
+        Some(Some(_)) => return Err("ERR Parameter `numFields` should be greater than 0"),
+        _ => return Err("ERR value is not an integer or out of range"),
+    };
+
+    if args.len() - 2 != count {
+        return Err("ERR The `numfields` parameter must match the number of arguments");
+    }
+
+    Ok(args[2..].to_vec())
+}
+
+pub fn parse_bit_range(args: &[String]) -> Result<BitRange, String> {
+    let bit_unit = match args.get(2).map(|unit| unit.to_uppercase()) {
+        None => false,
+        Some(unit) if unit == "BYTE" => false,
+        Some(unit) if unit == "BIT" => true,
+        Some(_) => return Err("ERR syntax error".into()),
+    };
+
+    let bounds = match (args.first(), args.get(1)) {
+        (Some(start), Some(end)) => match (parse_integer(start), parse_integer(end)) {
+            (Some(start), Some(end)) => Some((start, end)),
+            _ => return Err("ERR value is not an integer or out of range".into()),
+        },
+        _ => None,
+    };
+
+    Ok(BitRange { bounds, bit_unit })
+}
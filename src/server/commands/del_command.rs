@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_del(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if keys.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mut mem = app.memory.lock().await;
+    let mut removed = 0;
+    for key in &keys {
+        if let Some(entry) = mem.remove(key) {
+            if !entry.is_expired() {
+                removed += 1;
+            }
+        }
+    }
+
+    if removed > 0 {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(removed).into()
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::{
+    rdb::dump_payload, resp::resp_serializer, resp_desserializer::RespTk,
+    server::redis_app::RedisApp, types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_dump(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let Some(key) = command_utils::get_next_arg_string(&mut args) else {
+        return resp_serializer::to_err_string(
+            "ERR wrong number of arguments for 'dump' command".into(),
+        )
+        .into();
+    };
+
+    let Some(value) = app.get_entry(&key).await else {
+        return resp_serializer::null_resp_string().into();
+    };
+
+    match dump_payload::serialize(&value) {
+        Ok(payload) => resp_serializer::to_resp_bulk_bytes(&payload).into(),
+        Err(err) => resp_serializer::to_err_string(format!("ERR {}", err)).into(),
+    }
+}
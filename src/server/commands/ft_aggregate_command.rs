@@ -0,0 +1,335 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        hash::RedisHash,
+        search_index::{FieldKind, SearchIndex},
+    },
+};
+
+use super::{command_utils, ft_search_command};
+
+enum Reducer {
+    Count,
+    CountDistinct(String),
+    Sum(String),
+    Min(String),
+    Max(String),
+    Avg(String),
+}
+
+enum Step {
+    Load(Option<Vec<String>>),
+    GroupBy(Vec<String>, Vec<(Reducer, String)>),
+    SortBy(Vec<(String, bool)>, Option<usize>),
+    Limit(usize, usize),
+}
+
+// A result row: the properties shown in the reply, backed by the matching
+// hash for properties that are only read, until a GROUPBY replaces it.
+struct Row<'a> {
+    fields: Vec<(String, Option<String>)>,
+    hash: Option<&'a RedisHash>,
+}
+
+impl Row<'_> {
+    fn get(&self, index: &SearchIndex, name: &str) -> Option<String> {
+        if let Some((_, value)) = self.fields.iter().find(|(field, _)| field == name) {
+            return value.clone();
+        }
+        let field = index.field(name).map_or(name, |spec| &spec.name);
+        self.hash?.get(field).cloned()
+    }
+}
+
+pub async fn execute_ft_aggregate(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [name, query, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let steps = match parse_steps(options) {
+        Ok(steps) => steps,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    app.apply_writes(&mut mem);
+    let indexes = app.lock_search_indexes();
+    let Some(index) = indexes.get(name) else {
+        return resp_serializer::to_err_string(format!("{}: no such index", name)).into();
+    };
+
+    let mut matches = match ft_search_command::matching_documents(&mem, index, query) {
+        Ok(matches) => matches,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    matches.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut rows: Vec<Row> = matches
+        .into_iter()
+        .map(|(_, hash, _)| Row {
+            fields: vec![],
+            hash: Some(hash),
+        })
+        .collect();
+
+    for step in &steps {
+        rows = match step {
+            Step::Load(fields) => rows
+                .into_iter()
+                .map(|mut row| {
+                    let loaded: Vec<(String, Option<String>)> = match (fields, row.hash) {
+                        (None, Some(hash)) => hash
+                            .iter()
+                            .map(|(field, value)| (field.to_owned(), Some(value.to_owned())))
+                            .collect(),
+                        (None, None) => vec![],
+                        (Some(fields), _) => fields
+                            .iter()
+                            .map(|field| (field.to_owned(), row.get(index, field)))
+                            .collect(),
+                    };
+                    for (field, value) in loaded {
+                        if !row.fields.iter().any(|(name, _)| *name == field) {
+                            row.fields.push((field, value));
+                        }
+                    }
+                    row
+                })
+                .collect(),
+            Step::GroupBy(fields, reducers) => group_by(index, rows, fields, reducers),
+            Step::SortBy(fields, max) => {
+                rows.sort_by(|a, b| {
+                    fields
+                        .iter()
+                        .map(|(field, ascending)| {
+                            let numeric = index
+                                .field(field)
+                                .is_none_or(|spec| matches!(spec.kind, FieldKind::Numeric));
+                            let (x, y) = (a.get(index, field), b.get(index, field));
+                            match (&x, &y, ascending) {
+                                (Some(_), Some(_), false) => ft_search_command::compare_values(
+                                    y.as_ref(),
+                                    x.as_ref(),
+                                    numeric,
+                                ),
+                                _ => ft_search_command::compare_values(
+                                    x.as_ref(),
+                                    y.as_ref(),
+                                    numeric,
+                                ),
+                            }
+                        })
+                        .find(|ordering| ordering.is_ne())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+                if let Some(max) = max {
+                    rows.truncate(*max);
+                }
+                rows
+            }
+            Step::Limit(offset, limit) => rows.into_iter().skip(*offset).take(*limit).collect(),
+        };
+    }
+
+    let mut items = vec![resp_serializer::to_resp_integer(rows.len() as i64)];
+    items.extend(rows.into_iter().map(|row| {
+        resp_serializer::to_resp_nested_array(
+            row.fields
+                .into_iter()
+                .flat_map(|(field, value)| {
+                    [
+                        resp_serializer::to_resp_bulk(field),
+                        match value {
+                            Some(value) => resp_serializer::to_resp_bulk(value),
+                            None => resp_serializer::null_resp_string(),
+                        },
+                    ]
+                })
+                .collect(),
+        )
+    }));
+    resp_serializer::to_resp_nested_array(items).into()
+}
+
+fn group_by<'a>(
+    index: &SearchIndex,
+    rows: Vec<Row<'a>>,
+    fields: &[String],
+    reducers: &[(Reducer, String)],
+) -> Vec<Row<'a>> {
+    // Groups keep the order in which their first row was seen.
+    let mut groups: Vec<(Vec<Option<String>>, Vec<Row>)> = vec![];
+    for row in rows {
+        let key: Vec<Option<String>> = fields.iter().map(|field| row.get(index, field)).collect();
+        match groups.iter_mut().find(|(group, _)| *group == key) {
+            Some((_, members)) => members.push(row),
+            None => groups.push((key, vec![row])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, members)| {
+            let mut row_fields: Vec<(String, Option<String>)> =
+                fields.iter().cloned().zip(key).collect();
+            for (reducer, alias) in reducers {
+                row_fields.push((alias.to_owned(), Some(reduce(index, reducer, &members))));
+            }
+            Row {
+                fields: row_fields,
+                hash: None,
+            }
+        })
+        .collect()
+}
+
+fn reduce(index: &SearchIndex, reducer: &Reducer, rows: &[Row]) -> String {
+    let numbers = |field: &str| -> Vec<f64> {
+        rows.iter()
+            .filter_map(|row| row.get(index, field))
+            .filter_map(|value| value.parse::<f64>().ok())
+            .collect()
+    };
+
+    match reducer {
+        Reducer::Count => rows.len().to_string(),
+        Reducer::CountDistinct(field) => rows
+            .iter()
+            .filter_map(|row| row.get(index, field))
+            .collect::<HashSet<String>>()
+            .len()
+            .to_string(),
+        Reducer::Sum(field) => command_utils::format_float(numbers(field).iter().sum()),
+        Reducer::Min(field) => {
+            command_utils::format_float(numbers(field).into_iter().fold(f64::INFINITY, f64::min))
+        }
+        Reducer::Max(field) => command_utils::format_float(
+            numbers(field).into_iter().fold(f64::NEG_INFINITY, f64::max),
+        ),
+        Reducer::Avg(field) => {
+            let numbers = numbers(field);
+            match numbers.is_empty() {
+                true => "nan".to_owned(),
+                false => {
+                    command_utils::format_float(numbers.iter().sum::<f64>() / numbers.len() as f64)
+                }
+            }
+        }
+    }
+}
+
+fn parse_steps(mut args: &[String]) -> Result<Vec<Step>, String> {
+    let mut steps = vec![];
+
+    while let [option, rest @ ..] = args {
+        args = rest;
+        match option.to_uppercase().as_str() {
+            "LOAD" => match args {
+                [all, rest @ ..] if all == "*" => {
+                    steps.push(Step::Load(None));
+                    args = rest;
+                }
+                _ => {
+                    let (count, rest) = ft_search_command::parse_count(args, "LOAD")?;
+                    steps.push(Step::Load(Some(properties(&rest[..count]))));
+                    args = &rest[count..];
+                }
+            },
+            "GROUPBY" => {
+                let (count, rest) = ft_search_command::parse_count(args, "GROUPBY")?;
+                let fields = properties(&rest[..count]);
+                args = &rest[count..];
+
+                let mut reducers = vec![];
+                while let [option, function, rest @ ..] = args {
+                    if !option.eq_ignore_ascii_case("REDUCE") {
+                        break;
+                    }
+                    let (count, rest) = ft_search_command::parse_count(rest, "REDUCE")?;
+                    let reducer = parse_reducer(function, &properties(&rest[..count]))?;
+                    args = &rest[count..];
+
+                    let alias = match args {
+                        [option, alias, rest @ ..] if option.eq_ignore_ascii_case("AS") => {
+                            args = rest;
+                            alias.to_owned()
+                        }
+                        _ => format!(
+                            "__generated_alias{}{}",
+                            function.to_lowercase(),
+                            properties(&rest[..count]).join(",")
+                        ),
+                    };
+                    reducers.push((reducer, alias));
+                }
+                steps.push(Step::GroupBy(fields, reducers));
+            }
+            "SORTBY" => {
+                let (count, rest) = ft_search_command::parse_count(args, "SORTBY")?;
+                let mut fields: Vec<(String, bool)> = vec![];
+                for arg in &rest[..count] {
+                    match (arg.to_uppercase().as_str(), fields.last_mut()) {
+                        ("ASC", Some(last)) => last.1 = true,
+                        ("DESC", Some(last)) => last.1 = false,
+                        _ => fields.push((property(arg), true)),
+                    }
+                }
+                args = &rest[count..];
+
+                let mut max = None;
+                if let [option, value, rest @ ..] = args {
+                    if option.eq_ignore_ascii_case("MAX") {
+                        max = Some(
+                            command_utils::parse_integer(value)
+                                .filter(|max| *max >= 0)
+                                .ok_or("Bad arguments for MAX")?
+                                as usize,
+                        );
+                        args = rest;
+                    }
+                }
+                steps.push(Step::SortBy(fields, max));
+            }
+            "LIMIT" => {
+                let (offset, limit) = ft_search_command::parse_limit(args)?;
+                steps.push(Step::Limit(offset, limit));
+                args = &args[2..];
+            }
+            "DIALECT" if !args.is_empty() => args = &args[1..],
+            _ => return Err(format!("Unknown argument `{}`", option)),
+        }
+    }
+    Ok(steps)
+}
+
+fn parse_reducer(function: &str, args: &[String]) -> Result<Reducer, String> {
+    let reducer = match (function.to_uppercase().as_str(), args) {
+        ("COUNT", []) => Reducer::Count,
+        ("COUNT_DISTINCT", [field]) => Reducer::CountDistinct(field.to_owned()),
+        ("SUM", [field]) => Reducer::Sum(field.to_owned()),
+        ("MIN", [field]) => Reducer::Min(field.to_owned()),
+        ("MAX", [field]) => Reducer::Max(field.to_owned()),
+        ("AVG", [field]) => Reducer::Avg(field.to_owned()),
+        _ => return Err(format!("Bad arguments for reducer `{}`", function)),
+    };
+    Ok(reducer)
+}
+
+// Properties are written `@name`.
+fn property(arg: &str) -> String {
+    arg.strip_prefix('@').unwrap_or(arg).to_owned()
+}
+
+fn properties(args: &[String]) -> Vec<String> {
+    args.iter().map(|arg| property(arg)).collect()
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        search_index::{FieldKind, FieldSpec, SearchIndex},
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_ft_create(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((name, options)) = args.split_first() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut index = match parse_index(name, options) {
+        Ok(index) => index,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    {
+        let mut indexes = app.lock_search_indexes();
+        if indexes.contains(name) {
+            return resp_serializer::to_err_string("Index already exists".into()).into();
+        }
+
+        // Hashes written before the index existed are indexed right away.
+        for (key, entry) in mem.iter().filter(|(_, entry)| !entry.is_expired()) {
+            if let ValueContainer::Hash(hash) = &entry.value {
+                index.update(key, Some(hash));
+            }
+        }
+        indexes.insert(index);
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".into()).into()
+}
+
+fn parse_index(name: &str, mut options: &[String]) -> Result<SearchIndex, String> {
+    let mut prefixes = vec![];
+    loop {
+        match options {
+            [option, target, rest @ ..] if option.eq_ignore_ascii_case("ON") => {
+                if !target.eq_ignore_ascii_case("HASH") {
+                    return Err("Only HASH indexes are supported".into());
+                }
+                options = rest;
+            }
+            [option, count, rest @ ..] if option.eq_ignore_ascii_case("PREFIX") => {
+                let count = command_utils::parse_integer(count)
+                    .filter(|count| *count >= 0 && *count as usize <= rest.len())
+                    .ok_or("Bad arguments for PREFIX")? as usize;
+                prefixes.extend(rest[..count].iter().cloned());
+                options = &rest[count..];
+            }
+            [option, fields @ ..] if option.eq_ignore_ascii_case("SCHEMA") => {
+                let schema = parse_schema(fields)?;
+                return Ok(SearchIndex::new(name.to_owned(), prefixes, schema));
+            }
+            [option, ..] => return Err(format!("Unknown argument `{}`", option)),
+            [] => return Err("Fields arguments are missing".into()),
+        }
+    }
+}
+
+fn parse_schema(mut args: &[String]) -> Result<Vec<FieldSpec>, String> {
+    let mut schema: Vec<FieldSpec> = vec![];
+
+    while let [name, rest @ ..] = args {
+        args = rest;
+        let alias = match args {
+            [option, alias, rest @ ..] if option.eq_ignore_ascii_case("AS") => {
+                args = rest;
+                alias
+            }
+            _ => name,
+        };
+
+        let Some((kind, rest)) = args.split_first() else {
+            return Err(format!("Field `{}` does not have a type", name));
+        };
+        args = rest;
+
+        let kind = match kind.to_uppercase().as_str() {
+            "TEXT" => {
+                let mut weight = 1.0;
+                if let [option, value, rest @ ..] = args {
+                    if option.eq_ignore_ascii_case("WEIGHT") {
+                        weight = command_utils::parse_float(value)
+                            .filter(|weight| *weight >= 0.0)
+                            .ok_or("Bad arguments for WEIGHT")?;
+                        args = rest;
+                    }
+                }
+                FieldKind::Text { weight }
+            }
+            "TAG" => {
+                let mut separator = ',';
+                let mut case_sensitive = false;
+                loop {
+                    match args {
+                        [option, value, rest @ ..] if option.eq_ignore_ascii_case("SEPARATOR") => {
+                            let mut chars = value.chars();
+                            separator = match (chars.next(), chars.next()) {
+                                (Some(separator), None) => separator,
+                                _ => return Err("Tag separator must be a single character".into()),
+                            };
+                            args = rest;
+                        }
+                        [option, rest @ ..] if option.eq_ignore_ascii_case("CASESENSITIVE") => {
+                            case_sensitive = true;
+                            args = rest;
+                        }
+                        _ => break,
+                    }
+                }
+                FieldKind::Tag {
+                    separator,
+                    case_sensitive,
+                }
+            }
+            "NUMERIC" => FieldKind::Numeric,
+            _ => return Err(format!("Invalid field type for field `{}`", name)),
+        };
+
+        // Every field keeps its value reachable for sorting, so SORTABLE
+        // is accepted but changes nothing.
+        if let [option, rest @ ..] = args {
+            if option.eq_ignore_ascii_case("SORTABLE") {
+                args = rest;
+            }
+        }
+
+        if schema.iter().any(|field| field.alias == *alias) {
+            return Err(format!("Duplicate field in schema - {}", alias));
+        }
+        schema.push(FieldSpec {
+            name: name.to_owned(),
+            alias: alias.to_owned(),
+            kind,
+        });
+    }
+
+    match schema.is_empty() {
+        true => Err("Fields arguments are missing".into()),
+        false => Ok(schema),
+    }
+}
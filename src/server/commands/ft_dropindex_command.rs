@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, value_container::ValueContainer},
+};
+
+use super::command_utils;
+
+// With DD the indexed hashes are deleted along with the index.
+pub async fn execute_ft_dropindex(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (name, delete_docs) = match args.as_slice() {
+        [name] => (name, false),
+        [name, option] if option.eq_ignore_ascii_case("DD") => (name, true),
+        [_, _] => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let Some(index) = app.lock_search_indexes().remove(name) else {
+        return resp_serializer::to_err_string("Unknown Index name".into()).into();
+    };
+
+    if delete_docs {
+        for key in index.keys() {
+            // Keys overwritten with another type since were not indexed docs.
+            if matches!(mem.get(key), Some(entry) if matches!(entry.value, ValueContainer::Hash(_)))
+            {
+                mem.remove(key);
+            }
+        }
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".into()).into()
+}
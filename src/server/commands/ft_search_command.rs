@@ -0,0 +1,222 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        hash::RedisHash,
+        keyspace::Keyspace,
+        search_index::{FieldKind, SearchIndex},
+        search_query,
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+struct SearchOptions {
+    no_content: bool,
+    with_scores: bool,
+    return_fields: Option<Vec<String>>,
+    sort_by: Option<(String, bool)>,
+    offset: usize,
+    limit: usize,
+}
+
+pub async fn execute_ft_search(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [name, query, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let options = match parse_options(options) {
+        Ok(options) => options,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    app.apply_writes(&mut mem);
+    let indexes = app.lock_search_indexes();
+    let Some(index) = indexes.get(name) else {
+        return resp_serializer::to_err_string(format!("{}: no such index", name)).into();
+    };
+
+    let mut matches = match matching_documents(&mem, index, query) {
+        Ok(matches) => matches,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    match &options.sort_by {
+        Some((field, ascending)) => {
+            let Some(spec) = index.field(field) else {
+                return resp_serializer::to_err_string(format!(
+                    "Property `{}` not loaded nor in schema",
+                    field
+                ))
+                .into();
+            };
+            let numeric = matches!(spec.kind, FieldKind::Numeric);
+            matches.sort_by(|a, b| {
+                let (x, y) = (a.1.get(&spec.name), b.1.get(&spec.name));
+                // Documents without the field go last either way.
+                let ordering = match (x, y, ascending) {
+                    (Some(_), Some(_), false) => compare_values(y, x, numeric),
+                    _ => compare_values(x, y, numeric),
+                };
+                ordering.then_with(|| a.0.cmp(b.0))
+            });
+        }
+        None => matches.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.0.cmp(b.0))),
+    }
+
+    let mut items = vec![resp_serializer::to_resp_integer(matches.len() as i64)];
+    for (key, hash, score) in matches.into_iter().skip(options.offset).take(options.limit) {
+        items.push(resp_serializer::to_resp_bulk(key.to_owned()));
+        if options.with_scores {
+            items.push(resp_serializer::to_resp_bulk(command_utils::format_float(
+                score,
+            )));
+        }
+        if options.no_content {
+            continue;
+        }
+
+        let fields: Vec<(String, String)> = match &options.return_fields {
+            Some(names) => names
+                .iter()
+                .filter_map(|name| {
+                    let field = index.field(name).map_or(name, |spec| &spec.name);
+                    hash.get(field)
+                        .map(|value| (name.to_owned(), value.to_owned()))
+                })
+                .collect(),
+            None => hash
+                .iter()
+                .map(|(field, value)| (field.to_owned(), value.to_owned()))
+                .collect(),
+        };
+        items.push(resp_serializer::to_resp_array(
+            fields
+                .into_iter()
+                .flat_map(|(field, value)| [field, value])
+                .collect(),
+        ));
+    }
+    resp_serializer::to_resp_nested_array(items).into()
+}
+
+// Runs the query against the index and resolves every match to its hash,
+// skipping keys that stopped being hashes without the index noticing.
+pub fn matching_documents<'a>(
+    mem: &'a Keyspace,
+    index: &SearchIndex,
+    query: &str,
+) -> Result<Vec<(&'a String, &'a RedisHash, f64)>, String> {
+    let query = search_query::parse(query)?;
+    let scores = index.search(&query)?;
+
+    Ok(scores
+        .into_iter()
+        .filter_map(|(key, score)| {
+            let (key, entry) = mem.get_key_value(&key)?;
+            match &entry.value {
+                ValueContainer::Hash(hash) if !entry.is_expired() => Some((key, hash, score)),
+                _ => None,
+            }
+        })
+        .collect())
+}
+
+// Orders two field values, numerically when both parse as numbers, with
+// missing values last.
+pub fn compare_values(a: Option<&String>, b: Option<&String>, numeric: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match (numeric, a.parse::<f64>(), b.parse::<f64>()) {
+            (true, Ok(a), Ok(b)) => a.total_cmp(&b),
+            _ => a.cmp(b),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn parse_options(mut options: &[String]) -> Result<SearchOptions, String> {
+    let mut parsed = SearchOptions {
+        no_content: false,
+        with_scores: false,
+        return_fields: None,
+        sort_by: None,
+        offset: 0,
+        limit: 10,
+    };
+
+    while let [option, rest @ ..] = options {
+        options = rest;
+        match option.to_uppercase().as_str() {
+            "NOCONTENT" => parsed.no_content = true,
+            "WITHSCORES" => parsed.with_scores = true,
+            "RETURN" => {
+                let (count, rest) = parse_count(options, "RETURN")?;
+                parsed.return_fields = Some(rest[..count].to_vec());
+                options = &rest[count..];
+            }
+            "SORTBY" => {
+                let [field, rest @ ..] = options else {
+                    return Err("Bad arguments for SORTBY".into());
+                };
+                options = rest;
+                let mut ascending = true;
+                if let [order, rest @ ..] = options {
+                    if order.eq_ignore_ascii_case("ASC") || order.eq_ignore_ascii_case("DESC") {
+                        ascending = order.eq_ignore_ascii_case("ASC");
+                        options = rest;
+                    }
+                }
+                let field = field.strip_prefix('@').unwrap_or(field);
+                parsed.sort_by = Some((field.to_owned(), ascending));
+            }
+            "LIMIT" => {
+                let (offset, limit) = parse_limit(options)?;
+                parsed.offset = offset;
+                parsed.limit = limit;
+                options = &options[2..];
+            }
+            "DIALECT" if !options.is_empty() => options = &options[1..],
+            _ => return Err(format!("Unknown argument `{}`", option)),
+        }
+    }
+    Ok(parsed)
+}
+
+// Reads the `count` prefixing a list of `count` arguments.
+pub fn parse_count<'a>(args: &'a [String], option: &str) -> Result<(usize, &'a [String]), String> {
+    let [count, rest @ ..] = args else {
+        return Err(format!("Bad arguments for {}", option));
+    };
+    let count = command_utils::parse_integer(count)
+        .filter(|count| *count >= 0 && *count as usize <= rest.len())
+        .ok_or_else(|| format!("Bad arguments for {}", option))?;
+    Ok((count as usize, rest))
+}
+
+pub fn parse_limit(args: &[String]) -> Result<(usize, usize), String> {
+    let [offset, limit, ..] = args else {
+        return Err("Bad arguments for LIMIT".into());
+    };
+    match (
+        command_utils::parse_integer(offset),
+        command_utils::parse_integer(limit),
+    ) {
+        (Some(offset), Some(limit)) if offset >= 0 && limit >= 0 => {
+            Ok((offset as usize, limit as usize))
+        }
+        _ => Err("Bad arguments for LIMIT".into()),
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, geohash, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_geoadd(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, args)) = args.split_first().filter(|(_, args)| args.len() >= 3) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut only_new = false;
+    let mut only_existing = false;
+    let mut changed = false;
+    let mut args = args;
+    while let Some((option, rest)) = args.split_first() {
+        match option.to_uppercase().as_str() {
+            "NX" => only_new = true,
+            "XX" => only_existing = true,
+            "CH" => changed = true,
+            _ => break,
+        }
+        args = rest;
+    }
+
+    if args.is_empty() || !args.len().is_multiple_of(3) {
+        return resp_serializer::to_err_string("ERR syntax error".into()).into();
+    }
+    if only_new && only_existing {
+        return resp_serializer::to_err_string(
+            "ERR XX and NX options at the same time are not compatible".into(),
+        )
+        .into();
+    }
+
+    let mut elements = Vec::with_capacity(args.len() / 3);
+    for triple in args.chunks_exact(3) {
+        let (longitude, latitude) = match parse_position(&triple[0], &triple[1]) {
+            Ok(position) => position,
+            Err(err) => return resp_serializer::to_err_string(err).into(),
+        };
+        let Some(score) = geohash::encode(longitude, latitude) else {
+            return resp_serializer::to_err_string("ERR syntax error".into()).into();
+        };
+        elements.push((score as f64, triple[2].to_owned()));
+    }
+
+    let mut mem = app.memory.lock().await;
+    let zset = match command_utils::get_or_create_value(&mut mem, key, SortedSet::new) {
+        Ok(zset) => zset,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut added = 0;
+    let mut updated = 0;
+    for (score, member) in elements {
+        match zset.score(&member) {
+            Some(_) if only_new => {}
+            Some(current) => {
+                if score != current {
+                    zset.insert(member, score);
+                    updated += 1;
+                }
+            }
+            None if only_existing => {}
+            None => {
+                zset.insert(member, score);
+                added += 1;
+            }
+        }
+    }
+    command_utils::remove_if_empty(&mut mem, key);
+
+    if added + updated > 0 {
+        app.buffer_command(token).await;
+        app.signal_key_ready(&mut mem, key).await;
+    }
+
+    let reply = if changed { added + updated } else { added };
+    resp_serializer::to_resp_integer(reply).into()
+}
+
+pub fn parse_position(longitude: &str, latitude: &str) -> Result<(f64, f64), String> {
+    let (Some(longitude), Some(latitude)) = (
+        command_utils::parse_float(longitude),
+        command_utils::parse_float(latitude),
+    ) else {
+        return Err("ERR value is not a valid float".into());
+    };
+
+    if !geohash::is_valid_position(longitude, latitude) {
+        return Err(format!(
+            "ERR invalid longitude,latitude pair {:.6},{:.6}",
+            longitude, latitude
+        ));
+    }
+    Ok((longitude, latitude))
+}
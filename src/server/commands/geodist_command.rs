@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, geohash, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_geodist(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, first, second, unit) = match args.as_slice() {
+        [key, first, second] => (key, first, second, "m"),
+        [key, first, second, unit] => (key, first, second, unit.as_str()),
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let conversion = match parse_distance_unit(unit) {
+        Ok(conversion) => conversion,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let zset = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(Some(zset)) => zset,
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let (Some(first), Some(second)) = (zset.score(first), zset.score(second)) else {
+        return resp_serializer::null_resp_string().into();
+    };
+
+    let (lon1, lat1) = geohash::decode(first as u64);
+    let (lon2, lat2) = geohash::decode(second as u64);
+    let distance = geohash::distance(lon1, lat1, lon2, lat2) / conversion;
+    resp_serializer::to_resp_bulk(format_distance(distance)).into()
+}
+
+// Returns how many meters one `unit` is.
+pub fn parse_distance_unit(unit: &str) -> Result<f64, &'static str> {
+    match unit.to_lowercase().as_str() {
+        "m" => Ok(1.0),
+        "km" => Ok(1000.0),
+        "ft" => Ok(0.3048),
+        "mi" => Ok(1609.34),
+        _ => Err("ERR unsupported unit provided. please use M, KM, FT, MI"),
+    }
+}
+
+pub fn format_distance(value: f64) -> String {
+    format!("{:.4}", value)
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, geohash, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_geohash(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, members)) = args.split_first() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    let zset = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(zset) => zset,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let hashes = members
+        .iter()
+        .map(|member| match zset.and_then(|zset| zset.score(member)) {
+            Some(score) => {
+                let (longitude, latitude) = geohash::decode(score as u64);
+                resp_serializer::to_resp_bulk(geohash::to_geohash_string(longitude, latitude))
+            }
+            None => resp_serializer::null_resp_string(),
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(hashes).into()
+}
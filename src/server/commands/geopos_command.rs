@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, geohash, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_geopos(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, members)) = args.split_first() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    let zset = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(zset) => zset,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let positions = members
+        .iter()
+        .map(|member| match zset.and_then(|zset| zset.score(member)) {
+            Some(score) => {
+                let (longitude, latitude) = geohash::decode(score as u64);
+                resp_serializer::to_resp_array(vec![
+                    format_coordinate(longitude),
+                    format_coordinate(latitude),
+                ])
+            }
+            None => resp_serializer::null_resp_array(),
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(positions).into()
+}
+
+// Coordinates are printed with 17 decimals and no trailing zeros.
+pub fn format_coordinate(value: f64) -> String {
+    let formatted = format!("{:.17}", value);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+    match formatted {
+        "-0" => "0".into(),
+        formatted => formatted.into(),
+    }
+}
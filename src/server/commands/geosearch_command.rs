@@ -0,0 +1,314 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        geohash::{self, GeoSearch, GeoShape},
+        sorted_set::{ScoreRange, SortedSet},
+    },
+};
+
+use super::{command_utils, geoadd_command, geodist_command, geopos_command};
+
+enum Center {
+    Member(String),
+    Position(f64, f64),
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Sort {
+    None,
+    Asc,
+    Desc,
+}
+
+struct SearchArgs {
+    center: Center,
+    shape: GeoShape,
+    conversion: f64,
+    sort: Sort,
+    count: Option<usize>,
+    any: bool,
+    with_coord: bool,
+    with_dist: bool,
+    with_hash: bool,
+    store_dist: bool,
+}
+
+struct GeoPoint {
+    member: String,
+    score: f64,
+    distance: f64,
+    longitude: f64,
+    latitude: f64,
+}
+
+pub async fn execute_geosearch(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, args)) = args.split_first().filter(|(_, args)| args.len() >= 5) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let opts = match parse_args(args, false, token.get_command_name()) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let points = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(Some(zset)) => match search(zset, &opts) {
+            Ok(points) => points,
+            Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+        },
+        Ok(None) => vec![],
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    if !opts.with_coord && !opts.with_dist && !opts.with_hash {
+        let members = points.into_iter().map(|point| point.member).collect();
+        return resp_serializer::to_resp_array(members).into();
+    }
+
+    let items = points
+        .into_iter()
+        .map(|point| {
+            let mut item = vec![resp_serializer::to_resp_bulk(point.member)];
+            if opts.with_dist {
+                item.push(resp_serializer::to_resp_bulk(
+                    geodist_command::format_distance(point.distance / opts.conversion),
+                ));
+            }
+            if opts.with_hash {
+                item.push(resp_serializer::to_resp_integer(point.score as i64));
+            }
+            if opts.with_coord {
+                item.push(resp_serializer::to_resp_array(vec![
+                    geopos_command::format_coordinate(point.longitude),
+                    geopos_command::format_coordinate(point.latitude),
+                ]));
+            }
+            resp_serializer::to_resp_nested_array(item)
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(items).into()
+}
+
+pub async fn execute_geosearchstore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [destination, source, args @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if args.len() < 5 {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let opts = match parse_args(args, true, token.get_command_name()) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let points = match command_utils::get_value::<SortedSet>(&mem, source) {
+        Ok(Some(zset)) => match search(zset, &opts) {
+            Ok(points) => points,
+            Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+        },
+        Ok(None) => vec![],
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut result = SortedSet::new();
+    for point in points {
+        let score = match opts.store_dist {
+            true => point.distance / opts.conversion,
+            false => point.score,
+        };
+        result.insert(point.member, score);
+    }
+
+    let len = command_utils::store_sorted_set(&mut mem, destination, result);
+    app.buffer_command(token).await;
+    app.signal_key_ready(&mut mem, destination).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
+
+// Parses `FROMMEMBER member | FROMLONLAT lon lat`, `BYRADIUS radius unit |
+// BYBOX width height unit` and the ordering/output options, where only
+// GEOSEARCHSTORE takes STOREDIST and only GEOSEARCH returns the WITH* fields.
+fn parse_args(args: &[String], store: bool, command: &str) -> Result<SearchArgs, String> {
+    let mut center = None;
+    let mut shape = None;
+    let mut opts = SearchArgs {
+        center: Center::Position(0.0, 0.0),
+        shape: GeoShape::Radius(0.0),
+        conversion: 1.0,
+        sort: Sort::None,
+        count: None,
+        any: false,
+        with_coord: false,
+        with_dist: false,
+        with_hash: false,
+        store_dist: false,
+    };
+
+    let mut idx = 0;
+    while idx < args.len() {
+        let remaining = args.len() - idx - 1;
+        match args[idx].to_uppercase().as_str() {
+            "WITHCOORD" => opts.with_coord = true,
+            "WITHDIST" => opts.with_dist = true,
+            "WITHHASH" => opts.with_hash = true,
+            "STOREDIST" if store => opts.store_dist = true,
+            "ANY" => opts.any = true,
+            "ASC" => opts.sort = Sort::Asc,
+            "DESC" => opts.sort = Sort::Desc,
+            "COUNT" if remaining >= 1 => {
+                let count = command_utils::parse_integer(&args[idx + 1])
+                    .ok_or("ERR value is not an integer or out of range")?;
+                if count <= 0 {
+                    return Err("ERR COUNT must be > 0".into());
+                }
+                opts.count = Some(count as usize);
+                idx += 1;
+            }
+            "FROMMEMBER" if remaining >= 1 && center.is_none() => {
+                center = Some(Center::Member(args[idx + 1].to_owned()));
+                idx += 1;
+            }
+            "FROMLONLAT" if remaining >= 2 && center.is_none() => {
+                let (longitude, latitude) =
+                    geoadd_command::parse_position(&args[idx + 1], &args[idx + 2])?;
+                center = Some(Center::Position(longitude, latitude));
+                idx += 2;
+            }
+            "BYRADIUS" if remaining >= 2 && shape.is_none() => {
+                let radius =
+                    command_utils::parse_float(&args[idx + 1]).ok_or("ERR need numeric radius")?;
+                if radius < 0.0 {
+                    return Err("ERR radius cannot be negative".into());
+                }
+                opts.conversion = geodist_command::parse_distance_unit(&args[idx + 2])?;
+                shape = Some(GeoShape::Radius(radius * opts.conversion));
+                idx += 2;
+            }
+            "BYBOX" if remaining >= 3 && shape.is_none() => {
+                let width =
+                    command_utils::parse_float(&args[idx + 1]).ok_or("ERR need numeric width")?;
+                let height =
+                    command_utils::parse_float(&args[idx + 2]).ok_or("ERR need numeric height")?;
+                if width < 0.0 || height < 0.0 {
+                    return Err("ERR height or width cannot be negative".into());
+                }
+                opts.conversion = geodist_command::parse_distance_unit(&args[idx + 3])?;
+                shape = Some(GeoShape::Box {
+                    width: width * opts.conversion,
+                    height: height * opts.conversion,
+                });
+                idx += 3;
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+        idx += 1;
+    }
+
+    if store && (opts.with_coord || opts.with_dist || opts.with_hash) {
+        return Err(
+            "ERR GEOSEARCHSTORE is not compatible with WITHDIST, WITHHASH and WITHCOORD options"
+                .into(),
+        );
+    }
+
+    let Some(center) = center else {
+        return Err(format!(
+            "ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for {}",
+            command.to_lowercase()
+        ));
+    };
+    let Some(shape) = shape else {
+        return Err(format!(
+            "ERR exactly one of BYRADIUS and BYBOX can be specified for {}",
+            command.to_lowercase()
+        ));
+    };
+    if opts.any && opts.count.is_none() {
+        return Err("ERR the ANY argument requires COUNT argument".into());
+    }
+
+    // The closest entries can only be picked after sorting, unless ANY
+    // allows returning the first matches found.
+    if opts.count.is_some() && opts.sort == Sort::None && !opts.any {
+        opts.sort = Sort::Asc;
+    }
+
+    opts.center = center;
+    opts.shape = shape;
+    Ok(opts)
+}
+
+fn search(zset: &SortedSet, opts: &SearchArgs) -> Result<Vec<GeoPoint>, &'static str> {
+    let (longitude, latitude) = match &opts.center {
+        Center::Position(longitude, latitude) => (*longitude, *latitude),
+        Center::Member(member) => {
+            let score = zset
+                .score(member)
+                .ok_or("ERR could not decode requested zset member")?;
+            geohash::decode(score as u64)
+        }
+    };
+
+    let search = GeoSearch {
+        longitude,
+        latitude,
+        shape: opts.shape,
+    };
+    let limit = opts.count.filter(|_| opts.any);
+
+    let mut points = vec![];
+    'cells: for (min, max) in search.score_ranges() {
+        let range = ScoreRange {
+            min: min as f64,
+            max: max as f64,
+            min_exclusive: false,
+            max_exclusive: true,
+        };
+
+        for (member, score) in zset.range_by_score(&range, false, 0, None) {
+            let (longitude, latitude) = geohash::decode(score as u64);
+            let Some(distance) = search.distance_if_within(longitude, latitude) else {
+                continue;
+            };
+
+            points.push(GeoPoint {
+                member,
+                score,
+                distance,
+                longitude,
+                latitude,
+            });
+            if limit.is_some_and(|limit| points.len() >= limit) {
+                break 'cells;
+            }
+        }
+    }
+
+    match opts.sort {
+        Sort::Asc => points.sort_by(|a, b| a.distance.total_cmp(&b.distance)),
+        Sort::Desc => points.sort_by(|a, b| b.distance.total_cmp(&a.distance)),
+        Sort::None => {}
+    }
+    if let Some(count) = opts.count {
+        points.truncate(count);
+    }
+    Ok(points)
+}
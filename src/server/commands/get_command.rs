@@ -5,16 +5,17 @@ use crate::{
     types::execution_response::ExecResponse,
 };
 
+use super::command_utils;
+
 pub async fn execute_get(app: Arc<RedisApp>, tk: &RespTk) -> ExecResponse {
-    if let Some(key) = tk
-        .get_command_args()
-        .next()
-        .and_then(|t| t.get_content_string())
-    {
-        if let Some(entry) = app.get_entry(&key).await {
-            let value: String = entry.into();
-            return resp_serializer::to_resp_string(value).into();
-        }
+    let Some(key) = command_utils::get_next_arg_string(&mut tk.get_command_args()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(tk)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_string_value(&mem, &key) {
+        Ok(Some(value)) => resp_serializer::to_resp_bulk(value).into(),
+        Ok(None) => resp_serializer::null_resp_string().into(),
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
     }
-    resp_serializer::null_resp_string().into()
 }
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bitmap, execution_response::ExecResponse},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_getbit(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(offset)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(offset) = command_utils::parse_bit_offset(&offset) else {
+        return resp_serializer::to_err_string(
+            "ERR bit offset is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_string_value(&mem, &key) {
+        Ok(value) => {
+            let bytes = utils::string_to_bytes(&value.unwrap_or_default());
+            resp_serializer::to_resp_integer(bitmap::get_bit(&bytes, offset) as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_getdel(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let Some(key) = command_utils::get_next_arg_string(&mut token.get_command_args()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    match command_utils::get_string_value(&mem, &key) {
+        Ok(Some(value)) => {
+            mem.remove(&key);
+            app.buffer_command(token).await;
+            resp_serializer::to_resp_bulk(value).into()
+        }
+        Ok(None) => resp_serializer::null_resp_string().into(),
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+enum GetExExpiration {
+    Unchanged,
+    Persist,
+    At(u128),
+}
+
+pub async fn execute_getex(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let Some(key) = command_utils::get_next_arg_string(&mut args) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let expiration = match get_expiration(&mut args) {
+        Ok(expiration) => expiration,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let value = match command_utils::get_string_value(&mem, &key) {
+        Ok(Some(value)) => value,
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let replicated = match expiration {
+        GetExExpiration::Unchanged => None,
+        GetExExpiration::Persist => {
+            if let Some(entry) = mem.get_mut(&key) {
+                entry.expires_at = None;
+            }
+            Some(vec!["GETEX".into(), key, "PERSIST".into()])
+        }
+        GetExExpiration::At(at) => {
+            if let Some(entry) = mem.get_mut(&key) {
+                entry.expires_at = Some(at);
+            }
+            Some(vec!["GETEX".into(), key, "PXAT".into(), at.to_string()])
+        }
+    };
+
+    if let Some(replicated) = replicated {
+        app.buffer_command(&command_utils::to_command_token(replicated))
+            .await;
+    }
+
+    resp_serializer::to_resp_bulk(value).into()
+}
+
+fn get_expiration<'a>(
+    args: &mut impl Iterator<Item = &'a RespTk>,
+) -> Result<GetExExpiration, String> {
+    let mut expiration = GetExExpiration::Unchanged;
+
+    while let Some(opt) = command_utils::get_next_arg_string(args) {
+        if !matches!(expiration, GetExExpiration::Unchanged) {
+            return Err("ERR syntax error".into());
+        }
+
+        expiration = match opt.to_uppercase().as_str() {
+            "PERSIST" => GetExExpiration::Persist,
+            unit @ ("EX" | "PX" | "EXAT" | "PXAT") => {
+                let time = command_utils::get_next_arg_string(args)
+                    .ok_or("ERR syntax error".to_owned())?;
+                GetExExpiration::At(command_utils::get_expiration_time("getex", unit, &time)?)
+            }
+            _ => return Err("ERR syntax error".into()),
+        };
+    }
+
+    Ok(expiration)
+}
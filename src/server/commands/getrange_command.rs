@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse, utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_getrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(start), Some(end)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (Some(start), Some(end)) = (
+        command_utils::parse_integer(&start),
+        command_utils::parse_integer(&end),
+    ) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mem = app.memory.lock().await;
+    let value = match command_utils::get_string_value(&mem, &key) {
+        Ok(value) => utils::string_to_bytes(&value.unwrap_or_default()),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let range = command_utils::normalize_range(value.len() as i64, start, end)
+        .map(|(start, end)| utils::bytes_to_string(&value[start..=end]))
+        .unwrap_or_default();
+
+    resp_serializer::to_resp_bulk(range).into()
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{entry_value::EntryValue, execution_response::ExecResponse},
+};
+
+use super::command_utils;
+
+pub async fn execute_getset(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(value)) = (
+        command_utils::get_next_arg_string(&mut args),
+        args.next().map(|tk| tk.get_value()),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let old_value = match command_utils::get_string_value(&mem, &key) {
+        Ok(old_value) => old_value,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    mem.insert(
+        key,
+        EntryValue {
+            value,
+            expires_at: None,
+        },
+    );
+    app.buffer_command(token).await;
+
+    match old_value {
+        Some(old_value) => resp_serializer::to_resp_bulk(old_value).into(),
+        None => resp_serializer::null_resp_string().into(),
+    }
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+};
+
+use super::command_utils;
+
+pub async fn execute_hdel(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let key = command_utils::get_next_arg_string(&mut args);
+    let fields: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+
+    let Some(key) = key.filter(|_| !fields.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let hash = match command_utils::get_value_mut::<RedisHash>(&mut mem, &key) {
+        Ok(Some(hash)) => hash,
+        Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let removed = fields.iter().filter(|field| hash.remove(field)).count();
+    command_utils::remove_if_empty(&mut mem, &key);
+
+    if removed > 0 {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(removed as i64).into()
+}
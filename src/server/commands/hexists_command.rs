@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+};
+
+use super::command_utils;
+
+pub async fn execute_hexists(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(field), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<RedisHash>(&mem, &key) {
+        Ok(hash) => {
+            let exists = hash.is_some_and(|hash| hash.contains(&field));
+            resp_serializer::to_resp_integer(exists as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
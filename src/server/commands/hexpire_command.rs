@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+    utils,
+};
+
+use super::command_utils;
+
+// Field TTLs are kept within the 48-bit range Redis accepts.
+const MAX_EXPIRE_TIME_MS: u128 = (1 << 48) - 1;
+
+const NO_FIELD: i64 = -2;
+const CONDITION_NOT_MET: i64 = 0;
+const EXPIRATION_SET: i64 = 1;
+const FIELD_DELETED: i64 = 2;
+
+#[derive(Clone, Copy)]
+enum ExpireCondition {
+    Always,
+    IfNoExpiration,
+    IfHasExpiration,
+    IfGreater,
+    IfLess,
+}
+
+impl ExpireCondition {
+    fn parse(value: &str) -> Option<ExpireCondition> {
+        match value.to_uppercase().as_str() {
+            "NX" => Some(ExpireCondition::IfNoExpiration),
+            "XX" => Some(ExpireCondition::IfHasExpiration),
+            "GT" => Some(ExpireCondition::IfGreater),
+            "LT" => Some(ExpireCondition::IfLess),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&'static str> {
+        match self {
+            ExpireCondition::Always => None,
+            ExpireCondition::IfNoExpiration => Some("NX"),
+            ExpireCondition::IfHasExpiration => Some("XX"),
+            ExpireCondition::IfGreater => Some("GT"),
+            ExpireCondition::IfLess => Some("LT"),
+        }
+    }
+
+    // A field without a TTL counts as never expiring, so only LT can shorten it.
+    fn allows(&self, current: Option<u128>, new: u128) -> bool {
+        match (self, current) {
+            (ExpireCondition::Always, _) => true,
+            (ExpireCondition::IfNoExpiration, current) => current.is_none(),
+            (ExpireCondition::IfHasExpiration, current) => current.is_some(),
+            (ExpireCondition::IfGreater, Some(current)) => new > current,
+            (ExpireCondition::IfGreater, None) => false,
+            (ExpireCondition::IfLess, Some(current)) => new < current,
+            (ExpireCondition::IfLess, None) => true,
+        }
+    }
+}
+
+pub async fn execute_hexpire(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    expire_fields(app, token, "hexpire", |time| {
+        utils::get_current_time_ms() + time * 1000
+    })
+    .await
+}
+
+pub async fn execute_hpexpire(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    expire_fields(app, token, "hpexpire", |time| {
+        utils::get_current_time_ms() + time
+    })
+    .await
+}
+
+pub async fn execute_hexpireat(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    expire_fields(app, token, "hexpireat", |time| time * 1000).await
+}
+
+pub async fn execute_hpexpireat(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    expire_fields(app, token, "hpexpireat", |time| time).await
+}
+
+async fn expire_fields(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    command: &str,
+    to_expires_at: impl Fn(u128) -> u128,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if args.len() < 5 {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let (condition, fields_at) = match ExpireCondition::parse(&args[2]) {
+        Some(condition) => (condition, 3),
+        None => (ExpireCondition::Always, 2),
+    };
+
+    let fields = match command_utils::parse_fields_arg(&args[fields_at..]) {
+        Ok(fields) => fields,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let expires_at = match command_utils::parse_integer(&args[1]) {
+        Some(time) if time < 0 => {
+            return resp_serializer::to_err_string("ERR invalid expire time, must be >= 0".into())
+                .into()
+        }
+        Some(time) => to_expires_at(time as u128),
+        None => {
+            return resp_serializer::to_err_string(
+                "ERR value is not an integer or out of range".into(),
+            )
+            .into()
+        }
+    };
+
+    if expires_at > MAX_EXPIRE_TIME_MS {
+        return resp_serializer::to_err_string(format!(
+            "ERR invalid expire time in '{}' command",
+            command
+        ))
+        .into();
+    }
+
+    let key = &args[0];
+    let mut mem = app.memory.lock().await;
+    let hash = match command_utils::get_value_mut::<RedisHash>(&mut mem, key) {
+        Ok(Some(hash)) => hash,
+        Ok(None) => return to_integer_array(vec![NO_FIELD; fields.len()]),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let now = utils::get_current_time_ms();
+    let results: Vec<i64> = fields
+        .iter()
+        .map(|field| {
+            if !hash.contains(field) {
+                NO_FIELD
+            } else if !condition.allows(hash.expires_at(field), expires_at) {
+                CONDITION_NOT_MET
+            } else if expires_at <= now {
+                hash.remove(field);
+                FIELD_DELETED
+            } else {
+                hash.set_expiration(field, expires_at);
+                EXPIRATION_SET
+            }
+        })
+        .collect();
+    command_utils::remove_if_empty(&mut mem, key);
+
+    if results
+        .iter()
+        .any(|result| *result == EXPIRATION_SET || *result == FIELD_DELETED)
+    {
+        // Replicas get the absolute time so they expire the fields at the same instant.
+        let mut command = vec![
+            "HPEXPIREAT".to_owned(),
+            key.to_owned(),
+            expires_at.to_string(),
+        ];
+        command.extend(condition.as_str().map(str::to_owned));
+        command.extend(args[fields_at..].iter().cloned());
+        app.buffer_command(&command_utils::to_command_token(command))
+            .await;
+    }
+
+    to_integer_array(results)
+}
+
+pub(crate) fn to_integer_array(results: Vec<i64>) -> ExecResponse {
+    let items = results
+        .into_iter()
+        .map(resp_serializer::to_resp_integer)
+        .collect();
+    resp_serializer::to_resp_nested_array(items).into()
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+};
+
+use super::command_utils;
+
+pub async fn execute_hget(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(field), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<RedisHash>(&mem, &key) {
+        Ok(hash) => match hash.and_then(|hash| hash.get(&field)) {
+            Some(value) => resp_serializer::to_resp_bulk(value.to_owned()).into(),
+            None => resp_serializer::null_resp_string().into(),
+        },
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub async fn execute_hmget(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let key = command_utils::get_next_arg_string(&mut args);
+    let fields: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+
+    let Some(key) = key.filter(|_| !fields.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    let hash = match command_utils::get_value::<RedisHash>(&mem, &key) {
+        Ok(hash) => hash,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let values = fields
+        .iter()
+        .map(|field| match hash.and_then(|hash| hash.get(field)) {
+            Some(value) => resp_serializer::to_resp_bulk(value.to_owned()),
+            None => resp_serializer::null_resp_string(),
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(values).into()
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+};
+
+use super::command_utils;
+
+pub async fn execute_hgetall(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    read_hash(app, token, |hash| {
+        hash.iter()
+            .flat_map(|(field, value)| [field.to_owned(), value.to_owned()])
+            .collect()
+    })
+    .await
+}
+
+pub async fn execute_hkeys(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    read_hash(app, token, |hash| {
+        hash.iter().map(|(field, _)| field.to_owned()).collect()
+    })
+    .await
+}
+
+pub async fn execute_hvals(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    read_hash(app, token, |hash| {
+        hash.iter().map(|(_, value)| value.to_owned()).collect()
+    })
+    .await
+}
+
+async fn read_hash(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    reader: impl Fn(&RedisHash) -> Vec<String>,
+) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), None) = (command_utils::get_next_arg_string(&mut args), args.next()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<RedisHash>(&mem, &key) {
+        Ok(hash) => resp_serializer::to_resp_array(hash.map(reader).unwrap_or_default()).into(),
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
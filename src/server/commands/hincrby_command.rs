@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+};
+
+use super::command_utils;
+
+pub async fn execute_hincrby(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(field), Some(increment), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(increment) = command_utils::parse_integer(&increment) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let hash = match command_utils::get_or_create_value(&mut mem, &key, RedisHash::new) {
+        Ok(hash) => hash,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let value = match hash
+        .get(&field)
+        .map(|value| command_utils::parse_integer(value))
+    {
+        Some(Some(value)) => value,
+        Some(None) => {
+            return resp_serializer::to_err_string("ERR hash value is not an integer".into()).into()
+        }
+        None => 0,
+    };
+
+    let Some(result) = value.checked_add(increment) else {
+        return resp_serializer::to_err_string("ERR increment or decrement would overflow".into())
+            .into();
+    };
+
+    hash.insert(field, result.to_string());
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(result).into()
+}
+
+pub async fn execute_hincrbyfloat(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(field), Some(increment), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(increment) = command_utils::parse_float(&increment) else {
+        return resp_serializer::to_err_string("ERR value is not a valid float".into()).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let hash = match command_utils::get_or_create_value(&mut mem, &key, RedisHash::new) {
+        Ok(hash) => hash,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let value = match hash
+        .get(&field)
+        .map(|value| command_utils::parse_float(value))
+    {
+        Some(Some(value)) => value,
+        Some(None) => {
+            return resp_serializer::to_err_string("ERR hash value is not a float".into()).into()
+        }
+        None => 0.0,
+    };
+
+    let result = value + increment;
+    if !result.is_finite() {
+        return resp_serializer::to_err_string(
+            "ERR increment would produce NaN or Infinity".into(),
+        )
+        .into();
+    }
+
+    let result = command_utils::format_fixed_float(result);
+    hash.insert(field.clone(), result.clone());
+
+    // Replicas get the computed value so float formatting can't diverge.
+    let replicated =
+        command_utils::to_command_token(vec!["HSET".into(), key, field, result.clone()]);
+    app.buffer_command(&replicated).await;
+
+    resp_serializer::to_resp_bulk(result).into()
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+};
+
+use super::{command_utils, hexpire_command};
+
+const NO_FIELD: i64 = -2;
+const NO_EXPIRATION: i64 = -1;
+const PERSISTED: i64 = 1;
+
+pub async fn execute_hpersist(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if args.len() < 4 {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let fields = match command_utils::parse_fields_arg(&args[1..]) {
+        Ok(fields) => fields,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let hash = match command_utils::get_value_mut::<RedisHash>(&mut mem, &args[0]) {
+        Ok(Some(hash)) => hash,
+        Ok(None) => return hexpire_command::to_integer_array(vec![NO_FIELD; fields.len()]),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let results: Vec<i64> = fields
+        .iter()
+        .map(|field| {
+            if !hash.contains(field) {
+                NO_FIELD
+            } else if hash.persist(field) {
+                PERSISTED
+            } else {
+                NO_EXPIRATION
+            }
+        })
+        .collect();
+
+    if results.contains(&PERSISTED) {
+        app.buffer_command(token).await;
+    }
+    hexpire_command::to_integer_array(results)
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_hscan(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, args)) = args.split_first().filter(|(_, args)| !args.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let opts = match command_utils::parse_scan_args(args, true) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let hash = match command_utils::get_value::<RedisHash>(&mem, key) {
+        Ok(hash) => hash,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    // Compact hashes are small enough to be returned in a single call.
+    let (cursor, page) = match hash {
+        Some(hash) if hash.is_compact() => (0, hash.iter().collect()),
+        Some(hash) => utils::scan_page(hash.iter(), opts.cursor, opts.count),
+        None => (0, vec![]),
+    };
+
+    let items = page
+        .into_iter()
+        .filter(|(field, _)| opts.matches(field))
+        .flat_map(|(field, value)| {
+            let value = (!opts.no_values).then(|| value.to_owned());
+            std::iter::once(field.to_owned()).chain(value)
+        })
+        .collect();
+
+    resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_bulk(cursor.to_string()),
+        resp_serializer::to_resp_array(items),
+    ])
+    .into()
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+};
+
+use super::command_utils;
+
+pub async fn execute_hset(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    match hset(app, token).await {
+        Ok(added) => resp_serializer::to_resp_integer(added as i64).into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
+
+pub async fn execute_hmset(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    match hset(app, token).await {
+        Ok(_) => resp_serializer::to_resp_string("OK".to_owned()).into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
+
+pub async fn execute_hsetnx(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(field), Some(value), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let hash = match command_utils::get_or_create_value(&mut mem, &key, RedisHash::new) {
+        Ok(hash) => hash,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    if hash.contains(&field) {
+        return resp_serializer::to_resp_integer(0).into();
+    }
+
+    hash.insert(field, value);
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(1).into()
+}
+
+async fn hset(app: Arc<RedisApp>, token: &RespTk) -> Result<usize, String> {
+    let mut args = token.get_command_args();
+    let key = command_utils::get_next_arg_string(&mut args);
+    let pairs: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+
+    let Some(key) = key.filter(|_| !pairs.is_empty() && pairs.len().is_multiple_of(2)) else {
+        return Err(command_utils::arity_error(token));
+    };
+
+    let mut mem = app.memory.lock().await;
+    let hash = command_utils::get_or_create_value(&mut mem, &key, RedisHash::new)?;
+
+    let mut pairs = pairs.into_iter();
+    let mut added = 0;
+    while let (Some(field), Some(value)) = (pairs.next(), pairs.next()) {
+        if hash.insert(field, value) {
+            added += 1;
+        }
+    }
+
+    app.buffer_command(token).await;
+    Ok(added)
+}
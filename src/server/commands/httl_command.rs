@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hash::RedisHash},
+    utils,
+};
+
+use super::{command_utils, hexpire_command};
+
+const NO_FIELD: i64 = -2;
+const NO_EXPIRATION: i64 = -1;
+
+pub async fn execute_httl(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    read_expirations(app, token, |expires_at| {
+        expires_at
+            .saturating_sub(utils::get_current_time_ms())
+            .div_ceil(1000)
+    })
+    .await
+}
+
+pub async fn execute_hpttl(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    read_expirations(app, token, |expires_at| {
+        expires_at.saturating_sub(utils::get_current_time_ms())
+    })
+    .await
+}
+
+pub async fn execute_hexpiretime(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    read_expirations(app, token, |expires_at| expires_at / 1000).await
+}
+
+pub async fn execute_hpexpiretime(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    read_expirations(app, token, |expires_at| expires_at).await
+}
+
+async fn read_expirations(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    reader: impl Fn(u128) -> u128,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if args.len() < 4 {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let fields = match command_utils::parse_fields_arg(&args[1..]) {
+        Ok(fields) => fields,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let hash = match command_utils::get_value::<RedisHash>(&mem, &args[0]) {
+        Ok(Some(hash)) => hash,
+        Ok(None) => return hexpire_command::to_integer_array(vec![NO_FIELD; fields.len()]),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let results = fields
+        .iter()
+        .map(|field| match hash.expires_at(field) {
+            _ if !hash.contains(field) => NO_FIELD,
+            Some(expires_at) => reader(expires_at) as i64,
+            None => NO_EXPIRATION,
+        })
+        .collect();
+    hexpire_command::to_integer_array(results)
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, value_container::ValueContainer},
+};
+
+use super::command_utils;
+
+const NOT_INTEGER_ERROR: &str = "ERR value is not an integer or out of range";
+const NOT_FLOAT_ERROR: &str = "ERR value is not a valid float";
+
+pub async fn execute_incr(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    match get_args(token).as_slice() {
+        [key] => incr_by(app, token, key, 1).await,
+        _ => resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    }
+}
+
+pub async fn execute_decr(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    match get_args(token).as_slice() {
+        [key] => incr_by(app, token, key, -1).await,
+        _ => resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    }
+}
+
+pub async fn execute_incrby(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args = get_args(token);
+    let [key, increment] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    match command_utils::parse_integer(increment) {
+        Some(increment) => incr_by(app, token, key, increment).await,
+        None => resp_serializer::to_err_string(NOT_INTEGER_ERROR.into()).into(),
+    }
+}
+
+pub async fn execute_decrby(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args = get_args(token);
+    let [key, decrement] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    match command_utils::parse_integer(decrement) {
+        Some(i64::MIN) => {
+            resp_serializer::to_err_string("ERR decrement would overflow".into()).into()
+        }
+        Some(decrement) => incr_by(app, token, key, -decrement).await,
+        None => resp_serializer::to_err_string(NOT_INTEGER_ERROR.into()).into(),
+    }
+}
+
+pub async fn execute_incrbyfloat(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args = get_args(token);
+    let [key, increment] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(increment) = command_utils::parse_float(increment) else {
+        return resp_serializer::to_err_string(NOT_FLOAT_ERROR.into()).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let current = match command_utils::get_string_value(&mem, key) {
+        Ok(current) => current,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let value = match current.map(|current| command_utils::parse_float(&current)) {
+        Some(Some(value)) => value,
+        Some(None) => return resp_serializer::to_err_string(NOT_FLOAT_ERROR.into()).into(),
+        None => 0.0,
+    };
+
+    let result = value + increment;
+    if !result.is_finite() {
+        return resp_serializer::to_err_string(
+            "ERR increment would produce NaN or Infinity".into(),
+        )
+        .into();
+    }
+
+    let result = command_utils::format_fixed_float(result);
+    command_utils::set_string_value(&mut mem, key, ValueContainer::String(result.clone()));
+
+    let replicated = command_utils::to_command_token(vec![
+        "SET".into(),
+        key.to_owned(),
+        result.clone(),
+        "KEEPTTL".into(),
+    ]);
+    app.buffer_command(&replicated).await;
+
+    resp_serializer::to_resp_bulk(result).into()
+}
+
+async fn incr_by(app: Arc<RedisApp>, token: &RespTk, key: &str, increment: i64) -> ExecResponse {
+    let mut mem = app.memory.lock().await;
+    let current = match command_utils::get_string_value(&mem, key) {
+        Ok(current) => current,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let value = match current.map(|current| command_utils::parse_integer(&current)) {
+        Some(Some(value)) => value,
+        Some(None) => return resp_serializer::to_err_string(NOT_INTEGER_ERROR.into()).into(),
+        None => 0,
+    };
+
+    let Some(result) = value.checked_add(increment) else {
+        return resp_serializer::to_err_string("ERR increment or decrement would overflow".into())
+            .into();
+    };
+
+    let new_value = match mem.get(key).map(|entry| &entry.value) {
+        Some(ValueContainer::Integer(_)) => ValueContainer::Integer(result),
+        _ => ValueContainer::String(result.to_string()),
+    };
+    command_utils::set_string_value(&mut mem, key, new_value);
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(result).into()
+}
+
+fn get_args(token: &RespTk) -> Vec<String> {
+    token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect()
+}
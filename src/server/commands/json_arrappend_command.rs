@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        json::{self, JsonValue},
+        json_path::JsonPath,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_json_arrappend(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, path, values @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if values.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let path = match JsonPath::parse(path) {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let values: Vec<JsonValue> = match values.iter().map(|value| JsonValue::parse(value)).collect()
+    {
+        Ok(values) => values,
+        Err(err) => return resp_serializer::to_err_string(format!("ERR {}", err)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::to_err_string(json::MISSING_KEY_ERROR.into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut changed = false;
+    let reply = path.reply_per_match(json, |value| match value {
+        JsonValue::Array(items) => {
+            items.extend(values.iter().cloned());
+            changed = true;
+            Ok(resp_serializer::to_resp_integer(items.len() as i64))
+        }
+        value => Err(value.wrong_type_error("array")),
+    });
+
+    if changed {
+        app.buffer_command(token).await;
+    }
+    match reply {
+        Ok(reply) => reply.into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
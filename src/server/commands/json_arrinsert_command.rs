@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        json::{self, JsonValue},
+        json_path::JsonPath,
+    },
+};
+
+use super::command_utils;
+
+const INDEX_ERROR: &str = "ERR index out of bounds";
+
+pub async fn execute_json_arrinsert(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, path, index, values @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if values.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let path = match JsonPath::parse(path) {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let Some(index) = command_utils::parse_integer(index) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+    let values: Vec<JsonValue> = match values.iter().map(|value| JsonValue::parse(value)).collect()
+    {
+        Ok(values) => values,
+        Err(err) => return resp_serializer::to_err_string(format!("ERR {}", err)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::to_err_string(json::MISSING_KEY_ERROR.into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    // Every match is checked first so a bad index leaves the document as is.
+    let out_of_bounds = path
+        .locate(json)
+        .iter()
+        .filter_map(|location| json.pointer(location))
+        .any(|value| match value {
+            JsonValue::Array(items) => insert_position(items.len(), index).is_none(),
+            _ => false,
+        });
+    if out_of_bounds {
+        return resp_serializer::to_err_string(INDEX_ERROR.into()).into();
+    }
+
+    let mut changed = false;
+    let reply = path.reply_per_match(json, |value| match value {
+        JsonValue::Array(items) => {
+            let position =
+                insert_position(items.len(), index).ok_or_else(|| INDEX_ERROR.to_owned())?;
+            items.splice(position..position, values.iter().cloned());
+            changed = true;
+            Ok(resp_serializer::to_resp_integer(items.len() as i64))
+        }
+        value => Err(value.wrong_type_error("array")),
+    });
+
+    if changed {
+        app.buffer_command(token).await;
+    }
+    match reply {
+        Ok(reply) => reply.into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
+
+// Negative indexes count from the end, -1 inserting before the last item.
+fn insert_position(len: usize, index: i64) -> Option<usize> {
+    let position = match index < 0 {
+        true => len as i64 + index,
+        false => index,
+    };
+    (0..=len as i64)
+        .contains(&position)
+        .then_some(position as usize)
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        json::{self, JsonValue},
+        json_path::JsonPath,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_json_arrpop(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, path, index) = match args.as_slice() {
+        [key] => (key, Ok(JsonPath::root()), None),
+        [key, path] => (key, JsonPath::parse(path), None),
+        [key, path, index] => (key, JsonPath::parse(path), Some(index)),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+    let path = match path {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let index = match index.map(|index| command_utils::parse_integer(index)) {
+        None => -1,
+        Some(Some(index)) => index,
+        Some(None) => {
+            return resp_serializer::to_err_string(
+                "ERR value is not an integer or out of range".into(),
+            )
+            .into()
+        }
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::to_err_string(json::MISSING_KEY_ERROR.into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut changed = false;
+    let reply = path.reply_per_match(json, |value| match value {
+        JsonValue::Array(items) if items.is_empty() => Ok(resp_serializer::null_resp_string()),
+        JsonValue::Array(items) => {
+            // Out of range indexes pop the first or last item.
+            let len = items.len() as i64;
+            let position = match index < 0 {
+                true => (len + index).max(0),
+                false => index.min(len - 1),
+            };
+            let popped = items.remove(position as usize);
+            changed = true;
+            Ok(resp_serializer::to_resp_bulk(popped.to_json()))
+        }
+        value => Err(value.wrong_type_error("array")),
+    });
+
+    if changed {
+        app.buffer_command(token).await;
+    }
+    match reply {
+        Ok(reply) => reply.into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, json::JsonValue, json_path::JsonPath},
+};
+
+use super::command_utils;
+
+pub async fn execute_json_del(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let path = match args.as_slice() {
+        [_] => Ok(JsonPath::root()),
+        [_, path] => JsonPath::parse(path),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+    let path = match path {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let key = &args[0];
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut locations = path.locate(json);
+    let deleted = if locations.iter().any(|location| location.is_empty()) {
+        mem.remove(key);
+        1
+    } else {
+        // Going backwards removes nested values before their parents and
+        // keeps the remaining array indexes valid.
+        locations.sort();
+        locations
+            .iter()
+            .rev()
+            .filter(|location| json.remove(location).is_some())
+            .count()
+    };
+
+    if deleted > 0 {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(deleted as i64).into()
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        json::{JsonFormat, JsonValue},
+        json_path::JsonPath,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_json_get(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, mut args)) = args.split_first() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut format = JsonFormat::COMPACT;
+    while let [option, value, rest @ ..] = args {
+        match option.to_uppercase().as_str() {
+            "INDENT" => format.indent = value.as_str(),
+            "NEWLINE" => format.newline = value.as_str(),
+            "SPACE" => format.space = value.as_str(),
+            _ => break,
+        }
+        args = rest;
+    }
+
+    let paths = match args.iter().map(|path| JsonPath::parse(path)).collect() {
+        Ok(paths) => paths,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let json = match command_utils::get_value::<JsonValue>(&mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    match select_paths(json, paths) {
+        Ok(value) => resp_serializer::to_resp_bulk(value.to_formatted_json(&format)).into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
+
+pub async fn execute_json_mget(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (Some(path), false) = (keys.pop(), keys.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let path = match JsonPath::parse(&path) {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let values = keys
+        .iter()
+        .map(|key| {
+            // Keys that are missing or hold another type reply null.
+            let Ok(Some(json)) = command_utils::get_value::<JsonValue>(&mem, key) else {
+                return resp_serializer::null_resp_string();
+            };
+            match select_paths(json, vec![path.clone()]) {
+                Ok(value) => resp_serializer::to_resp_bulk(value.to_json()),
+                Err(_) => resp_serializer::null_resp_string(),
+            }
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(values).into()
+}
+
+// A single legacy path selects its value, a single JSONPath the array of its
+// matches and several paths an object keyed by each path.
+fn select_paths(json: &JsonValue, paths: Vec<JsonPath>) -> Result<JsonValue, String> {
+    match paths.as_slice() {
+        [] => Ok(json.clone()),
+        [path] => select(json, path, path.is_legacy()),
+        paths => {
+            let legacy = paths.iter().all(|path| path.is_legacy());
+            let mut object = JsonValue::Object(vec![]);
+            for path in paths {
+                object.insert(path.as_str().to_owned(), select(json, path, legacy)?);
+            }
+            Ok(object)
+        }
+    }
+}
+
+fn select(json: &JsonValue, path: &JsonPath, legacy: bool) -> Result<JsonValue, String> {
+    let mut matches = path
+        .locate(json)
+        .into_iter()
+        .filter_map(|location| json.pointer(&location).cloned());
+
+    match legacy {
+        true => matches.next().ok_or_else(|| path.missing_error()),
+        false => Ok(JsonValue::Array(matches.collect())),
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        entry_value::EntryValue,
+        execution_response::ExecResponse,
+        json::{self, JsonValue},
+        json_path::JsonPath,
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_json_merge(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, path, patch] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let path = match JsonPath::parse(path) {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let patch = match JsonValue::parse(patch) {
+        Ok(patch) => patch,
+        Err(err) => return resp_serializer::to_err_string(format!("ERR {}", err)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(json) => json,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    match json {
+        Some(json) => {
+            let mut locations = path.locate(json);
+            if locations.iter().any(|location| location.is_empty()) && patch == JsonValue::Null {
+                mem.remove(key);
+            } else if !locations.is_empty() {
+                // Null removes the matched values, so go backwards to keep
+                // array indexes valid.
+                locations.sort();
+                for location in locations.iter().rev() {
+                    match &patch {
+                        JsonValue::Null => _ = json.remove(location),
+                        patch => {
+                            if let Some(target) = json.pointer_mut(location) {
+                                target.merge(patch.clone());
+                            }
+                        }
+                    }
+                }
+            } else if let (Some((parent, member)), false) =
+                (path.split_last_key(), patch == JsonValue::Null)
+            {
+                let mut value = JsonValue::Null;
+                value.merge(patch);
+                for location in parent.locate(json) {
+                    if let Some(parent) = json.pointer_mut(&location) {
+                        parent.insert(member.to_owned(), value.clone());
+                    }
+                }
+            }
+        }
+        None if !path.is_root() => {
+            return resp_serializer::to_err_string(json::ROOT_ERROR.into()).into()
+        }
+        None => {
+            let mut value = JsonValue::Null;
+            value.merge(patch);
+            mem.insert(
+                key.to_owned(),
+                EntryValue {
+                    value: ValueContainer::Json(value),
+                    expires_at: None,
+                },
+            );
+        }
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".into()).into()
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        json::{self, JsonValue},
+        json_path::JsonPath,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_json_numincrby(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, path, increment] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let path = match JsonPath::parse(path) {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let increment = match JsonValue::parse(increment) {
+        Ok(increment @ (JsonValue::Integer(_) | JsonValue::Float(_))) => increment,
+        Ok(_) => {
+            return resp_serializer::to_err_string(
+                "ERR wrong type of value - expected a number".into(),
+            )
+            .into()
+        }
+        Err(err) => return resp_serializer::to_err_string(format!("ERR {}", err)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::to_err_string(json::MISSING_KEY_ERROR.into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    // Every result is computed before writing so an overflow changes nothing.
+    let mut results = vec![];
+    for location in path.locate(json) {
+        let Some(value) = json.pointer(&location) else {
+            continue;
+        };
+        let result = match add(value, &increment) {
+            Some(Ok(sum)) => Some(sum),
+            Some(Err(err)) => return resp_serializer::to_err_string(err.into()).into(),
+            None if path.is_legacy() => {
+                let err = value.wrong_type_error("a number");
+                return resp_serializer::to_err_string(err).into();
+            }
+            None => None,
+        };
+        results.push((location, result));
+    }
+
+    if path.is_legacy() && results.is_empty() {
+        return resp_serializer::to_err_string(path.missing_error()).into();
+    }
+
+    for (location, result) in &results {
+        if let (Some(target), Some(result)) = (json.pointer_mut(location), result) {
+            *target = result.clone();
+        }
+    }
+    if results.iter().any(|(_, result)| result.is_some()) {
+        app.buffer_command(token).await;
+    }
+
+    let mut values = results
+        .into_iter()
+        .map(|(_, result)| result.unwrap_or(JsonValue::Null));
+    let reply = match path.is_legacy() {
+        true => values.next().unwrap_or(JsonValue::Null),
+        false => JsonValue::Array(values.collect()),
+    };
+    resp_serializer::to_resp_bulk(reply.to_json()).into()
+}
+
+// Integers stay integers unless the sum overflows. Returns None when `value`
+// is not a number.
+fn add(value: &JsonValue, increment: &JsonValue) -> Option<Result<JsonValue, &'static str>> {
+    let sum = match (value, increment) {
+        (JsonValue::Integer(a), JsonValue::Integer(b)) => match a.checked_add(*b) {
+            Some(sum) => return Some(Ok(JsonValue::Integer(sum))),
+            None => *a as f64 + *b as f64,
+        },
+        (JsonValue::Integer(a), JsonValue::Float(b)) => *a as f64 + b,
+        (JsonValue::Float(a), JsonValue::Integer(b)) => a + *b as f64,
+        (JsonValue::Float(a), JsonValue::Float(b)) => a + b,
+        _ => return None,
+    };
+
+    match sum.is_finite() {
+        true => Some(Ok(JsonValue::Float(sum))),
+        false => Some(Err("ERR result is not a finite number")),
+    }
+}
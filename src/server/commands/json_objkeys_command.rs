@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, json::JsonValue, json_path::JsonPath},
+};
+
+use super::command_utils;
+
+pub async fn execute_json_objkeys(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, path) = match args.as_slice() {
+        [key] => (key, Ok(JsonPath::root())),
+        [key, path] => (key, JsonPath::parse(path)),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+    let path = match path {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::null_resp_array().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let reply = path.reply_per_match(json, |value| match value {
+        JsonValue::Object(members) => Ok(resp_serializer::to_resp_array(
+            members.iter().map(|(key, _)| key.to_owned()).collect(),
+        )),
+        value => Err(value.wrong_type_error("object")),
+    });
+
+    match reply {
+        Ok(reply) => reply.into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        entry_value::EntryValue,
+        execution_response::ExecResponse,
+        json::{self, JsonValue},
+        json_path::JsonPath,
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_json_set(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, path, value, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (only_new, only_existing) = match options {
+        [] => (false, false),
+        [option] if option.eq_ignore_ascii_case("NX") => (true, false),
+        [option] if option.eq_ignore_ascii_case("XX") => (false, true),
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let path = match JsonPath::parse(path) {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let value = match JsonValue::parse(value) {
+        Ok(value) => value,
+        Err(err) => return resp_serializer::to_err_string(format!("ERR {}", err)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(json) => json,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let updated = match json {
+        Some(json) => set_value(json, &path, value, only_new, only_existing),
+        None if !path.is_root() => {
+            return resp_serializer::to_err_string(json::ROOT_ERROR.into()).into()
+        }
+        None if only_existing => false,
+        None => {
+            mem.insert(
+                key.to_owned(),
+                EntryValue {
+                    value: ValueContainer::Json(value),
+                    expires_at: None,
+                },
+            );
+            true
+        }
+    };
+
+    if !updated {
+        return resp_serializer::null_resp_string().into();
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".into()).into()
+}
+
+// Replaces every match of `path`, or adds the member the path names to its
+// parent objects when nothing matches.
+pub fn set_value(
+    json: &mut JsonValue,
+    path: &JsonPath,
+    value: JsonValue,
+    only_new: bool,
+    only_existing: bool,
+) -> bool {
+    let locations = path.locate(json);
+    if !locations.is_empty() {
+        if only_new {
+            return false;
+        }
+        for location in locations {
+            if let Some(target) = json.pointer_mut(&location) {
+                *target = value.clone();
+            }
+        }
+        return true;
+    }
+
+    if only_existing {
+        return false;
+    }
+    let Some((parent, key)) = path.split_last_key() else {
+        return false;
+    };
+
+    let mut updated = false;
+    for location in parent.locate(json) {
+        if let Some(parent) = json.pointer_mut(&location) {
+            updated |= parent.insert(key.to_owned(), value.clone());
+        }
+    }
+    updated
+}
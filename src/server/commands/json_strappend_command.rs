@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        json::{self, JsonValue},
+        json_path::JsonPath,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_json_strappend(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, path, value) = match args.as_slice() {
+        [key, value] => (key, Ok(JsonPath::root()), value),
+        [key, path, value] => (key, JsonPath::parse(path), value),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+    let path = match path {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let suffix = match JsonValue::parse(value) {
+        Ok(JsonValue::String(suffix)) => suffix,
+        Ok(_) => {
+            return resp_serializer::to_err_string(
+                "ERR wrong type of value - expected a string".into(),
+            )
+            .into()
+        }
+        Err(err) => return resp_serializer::to_err_string(format!("ERR {}", err)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let json = match command_utils::get_value_mut::<JsonValue>(&mut mem, key) {
+        Ok(Some(json)) => json,
+        Ok(None) => return resp_serializer::to_err_string(json::MISSING_KEY_ERROR.into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut changed = false;
+    let reply = path.reply_per_match(json, |value| match value {
+        JsonValue::String(str) => {
+            str.push_str(&suffix);
+            changed = true;
+            Ok(resp_serializer::to_resp_integer(str.chars().count() as i64))
+        }
+        value => Err(value.wrong_type_error("string")),
+    });
+
+    if changed {
+        app.buffer_command(token).await;
+    }
+    match reply {
+        Ok(reply) => reply.into(),
+        Err(err) => resp_serializer::to_err_string(err).into(),
+    }
+}
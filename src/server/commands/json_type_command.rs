@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, json::JsonValue, json_path::JsonPath},
+};
+
+use super::command_utils;
+
+pub async fn execute_json_type(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let path = match args.as_slice() {
+        [_] => Ok(JsonPath::root()),
+        [_, path] => JsonPath::parse(path),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+    let path = match path {
+        Ok(path) => path,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let json = match command_utils::get_value::<JsonValue>(&mem, &args[0]) {
+        Ok(Some(json)) => json,
+        Ok(None) if path.is_legacy() => return resp_serializer::null_resp_string().into(),
+        Ok(None) => return resp_serializer::to_resp_array(vec![]).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let types: Vec<String> = path
+        .locate(json)
+        .iter()
+        .filter_map(|location| json.pointer(location))
+        .map(|value| value.type_name().to_owned())
+        .collect();
+
+    match (path.is_legacy(), types.first()) {
+        (false, _) => resp_serializer::to_resp_array(types).into(),
+        (true, Some(name)) => resp_serializer::to_resp_string(name.to_owned()).into(),
+        (true, None) => resp_serializer::null_resp_string().into(),
+    }
+}
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse, utils,
+};
+
+use super::command_utils;
+
+struct LcsOptions {
+    len: bool,
+    idx: bool,
+    min_match_len: usize,
+    with_match_len: bool,
+}
+
+struct LcsMatch {
+    a_start: usize,
+    b_start: usize,
+    len: usize,
+}
+
+pub async fn execute_lcs(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key_a), Some(key_b)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let opts = match get_options(&mut args) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let (a, b) = {
+        let mem = app.memory.lock().await;
+        match (
+            command_utils::get_string_value(&mem, &key_a),
+            command_utils::get_string_value(&mem, &key_b),
+        ) {
+            (Ok(a), Ok(b)) => (
+                utils::string_to_bytes(&a.unwrap_or_default()),
+                utils::string_to_bytes(&b.unwrap_or_default()),
+            ),
+            _ => {
+                return resp_serializer::to_err_string(
+                    "ERR The specified keys must contain string values".into(),
+                )
+                .into()
+            }
+        }
+    };
+
+    if opts.len {
+        return resp_serializer::to_resp_integer(lcs_length(&a, &b) as i64).into();
+    }
+
+    let mut pairs = Vec::new();
+    lcs_pairs(&a, &b, 0, 0, &mut pairs);
+
+    if !opts.idx {
+        let lcs: Vec<u8> = pairs.iter().map(|(i, _)| a[*i]).collect();
+        return resp_serializer::to_resp_bulk(utils::bytes_to_string(&lcs)).into();
+    }
+
+    let matches: Vec<String> = group_matches(&pairs)
+        .iter()
+        .rev()
+        .filter(|m| m.len >= opts.min_match_len)
+        .map(|m| serialize_match(m, opts.with_match_len))
+        .collect();
+
+    resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_bulk("matches".into()),
+        resp_serializer::to_resp_nested_array(matches),
+        resp_serializer::to_resp_bulk("len".into()),
+        resp_serializer::to_resp_integer(pairs.len() as i64),
+    ])
+    .into()
+}
+
+fn get_options<'a>(args: &mut impl Iterator<Item = &'a RespTk>) -> Result<LcsOptions, String> {
+    let mut opts = LcsOptions {
+        len: false,
+        idx: false,
+        min_match_len: 0,
+        with_match_len: false,
+    };
+
+    while let Some(opt) = command_utils::get_next_arg_string(args) {
+        match opt.to_uppercase().as_str() {
+            "LEN" => opts.len = true,
+            "IDX" => opts.idx = true,
+            "WITHMATCHLEN" => opts.with_match_len = true,
+            "MINMATCHLEN" => {
+                let len = command_utils::get_next_arg_string(args)
+                    .ok_or("ERR syntax error".to_owned())?;
+                let len = command_utils::parse_integer(&len)
+                    .ok_or("ERR value is not an integer or out of range".to_owned())?;
+                opts.min_match_len = len.max(0) as usize;
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+
+    if opts.len && opts.idx {
+        return Err("ERR If you want both the length and indexes, please just use IDX.".into());
+    }
+
+    Ok(opts)
+}
+
+fn serialize_match(m: &LcsMatch, with_match_len: bool) -> String {
+    let range = |start: usize| {
+        resp_serializer::to_resp_nested_array(vec![
+            resp_serializer::to_resp_integer(start as i64),
+            resp_serializer::to_resp_integer((start + m.len - 1) as i64),
+        ])
+    };
+
+    let mut items = vec![range(m.a_start), range(m.b_start)];
+    if with_match_len {
+        items.push(resp_serializer::to_resp_integer(m.len as i64));
+    }
+    resp_serializer::to_resp_nested_array(items)
+}
+
+fn group_matches(pairs: &[(usize, usize)]) -> Vec<LcsMatch> {
+    let mut matches: Vec<LcsMatch> = Vec::new();
+
+    for (i, j) in pairs {
+        match matches.last_mut() {
+            Some(last) if last.a_start + last.len == *i && last.b_start + last.len == *j => {
+                last.len += 1
+            }
+            _ => matches.push(LcsMatch {
+                a_start: *i,
+                b_start: *j,
+                len: 1,
+            }),
+        }
+    }
+
+    matches
+}
+
+// Last row of the LCS table, keeping only two rows in memory.
+fn lcs_last_row<'a>(
+    a: impl Iterator<Item = &'a u8>,
+    b: impl Iterator<Item = &'a u8> + Clone,
+    b_len: usize,
+) -> Vec<usize> {
+    let mut previous = vec![0; b_len + 1];
+    let mut current = vec![0; b_len + 1];
+
+    for a_byte in a {
+        for (j, b_byte) in b.clone().enumerate() {
+            current[j + 1] = if a_byte == b_byte {
+                previous[j] + 1
+            } else {
+                current[j].max(previous[j + 1])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous
+}
+
+fn lcs_length(a: &[u8], b: &[u8]) -> usize {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    lcs_last_row(a.iter(), b.iter(), b.len())[b.len()]
+}
+
+// Hirschberg's algorithm: recovers the matched index pairs in linear space.
+fn lcs_pairs(a: &[u8], b: &[u8], a_offset: usize, b_offset: usize, out: &mut Vec<(usize, usize)>) {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+
+    if a.len() == 1 {
+        if let Some(j) = b.iter().position(|byte| *byte == a[0]) {
+            out.push((a_offset, b_offset + j));
+        }
+        return;
+    }
+
+    let mid = a.len() / 2;
+    let forward = lcs_last_row(a[..mid].iter(), b.iter(), b.len());
+    let backward = lcs_last_row(a[mid..].iter().rev(), b.iter().rev(), b.len());
+
+    let split = (0..=b.len())
+        .max_by_key(|j| (forward[*j] + backward[b.len() - j], *j))
+        .unwrap_or(0);
+
+    lcs_pairs(&a[..mid], &b[..split], a_offset, b_offset, out);
+    lcs_pairs(
+        &a[mid..],
+        &b[split..],
+        a_offset + mid,
+        b_offset + split,
+        out,
+    );
+}
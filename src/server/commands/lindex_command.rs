@@ -0,0 +1,37 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_lindex(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(index)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(index) = command_utils::parse_integer(&index) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mem = app.memory.lock().await;
+    let list = match command_utils::get_value::<VecDeque<String>>(&mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    match command_utils::resolve_index(list.len(), index).and_then(|idx| list.get(idx)) {
+        Some(element) => resp_serializer::to_resp_bulk(element.to_owned()).into(),
+        None => resp_serializer::null_resp_string().into(),
+    }
+}
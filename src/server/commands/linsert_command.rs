@@ -0,0 +1,44 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_linsert(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(position), Some(pivot), Some(element), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let after = match position.to_uppercase().as_str() {
+        "BEFORE" => false,
+        "AFTER" => true,
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let list = match command_utils::get_value_mut::<VecDeque<String>>(&mut mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let Some(idx) = list.iter().position(|item| *item == pivot) else {
+        return resp_serializer::to_resp_integer(-1).into();
+    };
+
+    list.insert(if after { idx + 1 } else { idx }, element);
+    let len = list.len();
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
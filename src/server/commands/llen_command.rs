@@ -0,0 +1,22 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_llen(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let Some(key) = command_utils::get_next_arg_string(&mut token.get_command_args()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<VecDeque<String>>(&mem, &key) {
+        Ok(list) => {
+            resp_serializer::to_resp_integer(list.map_or(0, |list| list.len()) as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
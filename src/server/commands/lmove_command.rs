@@ -0,0 +1,96 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, keyspace::Keyspace},
+};
+
+use super::{
+    command_utils::{self, ListEnd},
+    pop_command, push_command,
+};
+
+pub async fn execute_lmove(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [source, destination, from, to] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (Some(from), Some(to)) = (ListEnd::parse(from), ListEnd::parse(to)) else {
+        return resp_serializer::to_err_string("ERR syntax error".into()).into();
+    };
+
+    lmove(app, token, source, destination, from, to).await
+}
+
+pub async fn execute_rpoplpush(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [source, destination] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    lmove(
+        app,
+        token,
+        source,
+        destination,
+        ListEnd::Right,
+        ListEnd::Left,
+    )
+    .await
+}
+
+async fn lmove(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    source: &str,
+    destination: &str,
+    from: ListEnd,
+    to: ListEnd,
+) -> ExecResponse {
+    let mut mem = app.memory.lock().await;
+    match move_element(&mut mem, source, destination, from, to) {
+        Ok(Some(element)) => {
+            app.buffer_command(token).await;
+            app.signal_key_ready(&mut mem, destination).await;
+            resp_serializer::to_resp_bulk(element).into()
+        }
+        Ok(None) => resp_serializer::null_resp_string().into(),
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub fn move_element(
+    mem: &mut Keyspace,
+    source: &str,
+    destination: &str,
+    from: ListEnd,
+    to: ListEnd,
+) -> Result<Option<String>, &'static str> {
+    if command_utils::get_value::<VecDeque<String>>(mem, source)?.is_none() {
+        return Ok(None);
+    }
+    command_utils::get_value::<VecDeque<String>>(mem, destination)?;
+
+    let Some(list) = command_utils::get_value_mut::<VecDeque<String>>(mem, source)? else {
+        return Ok(None);
+    };
+    let Some(element) = pop_command::pop_elements(list, from, 1).pop() else {
+        return Ok(None);
+    };
+    command_utils::remove_if_empty(mem, source);
+
+    let list = command_utils::get_or_create_value(mem, destination, VecDeque::new)?;
+    push_command::push_elements(list, to, vec![element.clone()]);
+    Ok(Some(element))
+}
@@ -0,0 +1,102 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+const RANK_ZERO_ERROR: &str = "ERR RANK can't be zero: \
+    use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list";
+
+struct LposOptions {
+    rank: i64,
+    count: Option<usize>,
+    max_len: usize,
+}
+
+pub async fn execute_lpos(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(element)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let opts = match get_optional_args(&mut args) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let list = match command_utils::get_value::<VecDeque<String>>(&mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) if opts.count.is_some() => return resp_serializer::to_resp_array(vec![]).into(),
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let len = list.len();
+    let scan_len = if opts.max_len == 0 {
+        len
+    } else {
+        opts.max_len.min(len)
+    };
+    let indexes: Box<dyn Iterator<Item = usize>> = if opts.rank > 0 {
+        Box::new(0..scan_len)
+    } else {
+        Box::new((len - scan_len..len).rev())
+    };
+
+    let limit = match opts.count {
+        Some(0) => usize::MAX,
+        Some(count) => count,
+        None => 1,
+    };
+    let mut matches: Vec<String> = indexes
+        .filter(|idx| list[*idx] == element)
+        .skip(opts.rank.unsigned_abs() as usize - 1)
+        .take(limit)
+        .map(|idx| resp_serializer::to_resp_integer(idx as i64))
+        .collect();
+
+    if opts.count.is_some() {
+        return resp_serializer::to_resp_nested_array(matches).into();
+    }
+
+    match matches.pop() {
+        Some(first) => first.into(),
+        None => resp_serializer::null_resp_string().into(),
+    }
+}
+
+fn get_optional_args<'a>(
+    args: &mut impl Iterator<Item = &'a RespTk>,
+) -> Result<LposOptions, &'static str> {
+    let mut opts = LposOptions {
+        rank: 1,
+        count: None,
+        max_len: 0,
+    };
+
+    while let Some(opt) = command_utils::get_next_arg_string(args) {
+        let value = command_utils::get_next_arg_string(args).ok_or("ERR syntax error")?;
+        let value = command_utils::parse_integer(&value)
+            .ok_or("ERR value is not an integer or out of range")?;
+
+        match opt.to_uppercase().as_str() {
+            "RANK" if value == 0 => return Err(RANK_ZERO_ERROR),
+            "RANK" if value == i64::MIN => return Err("ERR value is out of range"),
+            "RANK" => opts.rank = value,
+            "COUNT" if value < 0 => return Err("ERR COUNT can't be negative"),
+            "COUNT" => opts.count = Some(value as usize),
+            "MAXLEN" if value < 0 => return Err("ERR MAXLEN can't be negative"),
+            "MAXLEN" => opts.max_len = value as usize,
+            _ => return Err("ERR syntax error"),
+        }
+    }
+
+    Ok(opts)
+}
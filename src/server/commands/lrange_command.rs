@@ -0,0 +1,42 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_lrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(start), Some(end)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (Some(start), Some(end)) = (
+        command_utils::parse_integer(&start),
+        command_utils::parse_integer(&end),
+    ) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mem = app.memory.lock().await;
+    let list = match command_utils::get_value::<VecDeque<String>>(&mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) => return resp_serializer::to_resp_array(vec![]).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let elements = match command_utils::normalize_list_range(list.len(), start, end) {
+        Some((start, end)) => list.range(start..=end).cloned().collect(),
+        None => vec![],
+    };
+    resp_serializer::to_resp_array(elements).into()
+}
@@ -0,0 +1,63 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_lrem(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(count), Some(element), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(count) = command_utils::parse_integer(&count) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let list = match command_utils::get_value_mut::<VecDeque<String>>(&mut mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    // A negative count removes matches scanning from the tail.
+    let limit = if count == 0 {
+        usize::MAX
+    } else {
+        count.unsigned_abs() as usize
+    };
+    let mut positions: Vec<usize> = list
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| **item == element)
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if count < 0 {
+        positions.reverse();
+    }
+    positions.truncate(limit);
+    positions.sort_unstable();
+
+    for idx in positions.iter().rev() {
+        list.remove(*idx);
+    }
+
+    command_utils::remove_if_empty(&mut mem, &key);
+    if !positions.is_empty() {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(positions.len() as i64).into()
+}
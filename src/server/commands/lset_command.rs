@@ -0,0 +1,42 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_lset(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(index), Some(element), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(index) = command_utils::parse_integer(&index) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let list = match command_utils::get_value_mut::<VecDeque<String>>(&mut mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) => return resp_serializer::to_err_string("ERR no such key".into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let Some(index) = command_utils::resolve_index(list.len(), index) else {
+        return resp_serializer::to_err_string("ERR index out of range".into()).into();
+    };
+
+    list[index] = element;
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".to_owned()).into()
+}
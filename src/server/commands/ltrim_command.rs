@@ -0,0 +1,49 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_ltrim(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(start), Some(end), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (Some(start), Some(end)) = (
+        command_utils::parse_integer(&start),
+        command_utils::parse_integer(&end),
+    ) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let list = match command_utils::get_value_mut::<VecDeque<String>>(&mut mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) => return resp_serializer::to_resp_string("OK".to_owned()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    match command_utils::normalize_list_range(list.len(), start, end) {
+        Some((start, end)) => {
+            list.truncate(end + 1);
+            list.drain(..start);
+        }
+        None => list.clear(),
+    }
+
+    command_utils::remove_if_empty(&mut mem, &key);
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".to_owned()).into()
+}
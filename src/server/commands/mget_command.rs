@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_mget(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if keys.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mem = app.memory.lock().await;
+    let values = keys
+        .iter()
+        .map(|key| match command_utils::get_string_value(&mem, key) {
+            Ok(Some(value)) => resp_serializer::to_resp_bulk(value),
+            _ => resp_serializer::null_resp_string(),
+        })
+        .collect();
+
+    resp_serializer::to_resp_nested_array(values).into()
+}
@@ -1,16 +1,140 @@
-pub mod command_inc;
+pub mod append_command;
+pub mod bf_add_command;
+pub mod bf_exists_command;
+pub mod bf_info_command;
+pub mod bf_reserve_command;
+pub mod bitcount_command;
+pub mod bitfield_command;
+pub mod bitop_command;
+pub mod bitpos_command;
+pub mod blmove_command;
+pub mod blpop_command;
+pub mod bzpop_command;
+pub mod cf_add_command;
+pub mod cf_del_command;
+pub mod cf_exists_command;
 mod command_utils;
 pub mod config_command;
+pub mod del_command;
+pub mod dump_command;
 pub(crate) mod echo_command;
+pub mod ft_aggregate_command;
+pub mod ft_create_command;
+pub mod ft_dropindex_command;
+pub mod ft_search_command;
+pub mod geoadd_command;
+pub mod geodist_command;
+pub mod geohash_command;
+pub mod geopos_command;
+pub mod geosearch_command;
 pub mod get_command;
+pub mod getbit_command;
+pub mod getdel_command;
+pub mod getex_command;
+pub mod getrange_command;
+pub mod getset_command;
+pub mod hdel_command;
+pub mod hexists_command;
+pub mod hexpire_command;
+pub mod hget_command;
+pub mod hgetall_command;
+pub mod hincrby_command;
+pub mod hlen_command;
+pub mod hpersist_command;
+pub mod hrandfield_command;
+pub mod hscan_command;
+pub mod hset_command;
+pub mod hstrlen_command;
+pub mod httl_command;
+pub mod incr_command;
 pub mod info_command;
 pub(crate) mod invalid_command;
+pub mod json_arrappend_command;
+pub mod json_arrinsert_command;
+pub mod json_arrpop_command;
+pub mod json_del_command;
+pub mod json_get_command;
+pub mod json_merge_command;
+pub mod json_numincrby_command;
+pub mod json_objkeys_command;
+pub mod json_set_command;
+pub mod json_strappend_command;
+pub mod json_type_command;
 pub mod keys_command;
+pub mod lcs_command;
+pub mod lindex_command;
+pub mod linsert_command;
+pub mod llen_command;
+pub mod lmove_command;
+pub mod lpos_command;
+pub mod lrange_command;
+pub mod lrem_command;
+pub mod lset_command;
+pub mod ltrim_command;
+pub mod mget_command;
+pub mod mset_command;
+pub mod pfadd_command;
+pub mod pfcount_command;
+pub mod pfdebug_command;
+pub mod pfmerge_command;
 pub(crate) mod ping_command;
+pub mod pop_command;
 pub mod psync_command;
+pub mod push_command;
 pub mod replconf_command;
+pub mod restore_command;
+pub mod sadd_command;
+pub mod scard_command;
+pub mod sdiff_command;
 pub mod set_command;
+pub mod setbit_command;
+pub mod setex_command;
+pub mod setnx_command;
+pub mod setrange_command;
+pub mod sinter_command;
+pub mod sismember_command;
+pub mod smembers_command;
+pub mod smove_command;
+pub mod spop_command;
+pub mod srandmember_command;
+pub mod srem_command;
+pub mod sscan_command;
+pub mod strlen_command;
+pub mod sunion_command;
+pub mod ts_add_command;
+pub mod ts_create_command;
+pub mod ts_createrule_command;
+pub mod ts_range_command;
 pub mod type_command;
+pub mod vadd_command;
+pub mod vcard_command;
+pub mod vdim_command;
+pub mod vgetattr_command;
+pub mod vrem_command;
+pub mod vsetattr_command;
+pub mod vsim_command;
+pub mod xack_command;
 pub mod xadd_command;
+pub mod xautoclaim_command;
+pub mod xclaim_command;
+pub mod xdel_command;
+pub mod xgroup_command;
+pub mod xlen_command;
+pub mod xpending_command;
 pub mod xrange_command;
 pub mod xread_command;
+pub mod xreadgroup_command;
+pub mod xtrim_command;
+pub mod zadd_command;
+pub mod zcard_command;
+pub mod zcount_command;
+pub mod zincrby_command;
+pub mod zpop_command;
+pub mod zrandmember_command;
+pub mod zrange_command;
+pub mod zrank_command;
+pub mod zrem_command;
+pub mod zremrangeby_command;
+pub mod zscan_command;
+pub mod zscore_command;
+pub mod zsetop_command;
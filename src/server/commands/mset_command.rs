@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        entry_value::EntryValue, execution_response::ExecResponse, value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_mset(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let Some(pairs) = get_pairs(token) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    for (key, value) in pairs {
+        mem.insert(
+            key,
+            EntryValue {
+                value,
+                expires_at: None,
+            },
+        );
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".to_owned()).into()
+}
+
+pub async fn execute_msetnx(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let Some(pairs) = get_pairs(token) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let any_exists = pairs
+        .iter()
+        .any(|(key, _)| mem.get(key).is_some_and(|entry| !entry.is_expired()));
+
+    if any_exists {
+        return resp_serializer::to_resp_integer(0).into();
+    }
+
+    for (key, value) in pairs {
+        mem.insert(
+            key,
+            EntryValue {
+                value,
+                expires_at: None,
+            },
+        );
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(1).into()
+}
+
+fn get_pairs(token: &RespTk) -> Option<Vec<(String, ValueContainer)>> {
+    let args: Vec<&RespTk> = token.get_command_args().collect();
+
+    if args.is_empty() || !args.len().is_multiple_of(2) {
+        return None;
+    }
+
+    args.chunks(2)
+        .map(|pair| Some((pair[0].get_content_string()?, pair[1].get_value())))
+        .collect()
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hyperloglog::HyperLogLog},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_pfadd(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let Some(key) = command_utils::get_next_arg_string(&mut args) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let (mut hll, mut updated) = match command_utils::get_hyperloglog(&mem, &key) {
+        Ok(Some(hll)) => (hll, false),
+        Ok(None) => (HyperLogLog::new(), true),
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    for element in args.filter_map(|tk| tk.get_content_string()) {
+        match hll.add(&utils::string_to_bytes(&element)) {
+            Ok(changed) => updated |= changed,
+            Err(err) => return resp_serializer::to_err_string(err.to_string()).into(),
+        }
+    }
+
+    if !updated {
+        return resp_serializer::to_resp_integer(0).into();
+    }
+
+    hll.invalidate_cache();
+    command_utils::set_hyperloglog(&mut mem, &key, hll);
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(1).into()
+}
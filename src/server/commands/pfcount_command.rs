@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, hyperloglog},
+};
+
+use super::command_utils;
+
+pub async fn execute_pfcount(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if keys.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mut mem = app.memory.lock().await;
+
+    if let [key] = keys.as_slice() {
+        let mut hll = match command_utils::get_hyperloglog(&mem, key) {
+            Ok(Some(hll)) => hll,
+            Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+            Err(err) => return resp_serializer::to_err_string(err).into(),
+        };
+
+        // A stale cached cardinality is refreshed in place, like Redis does.
+        let cached = hll.has_valid_cache();
+        let card = match hll.count() {
+            Ok(card) => card,
+            Err(err) => return resp_serializer::to_err_string(err.to_string()).into(),
+        };
+
+        if !cached {
+            command_utils::set_hyperloglog(&mut mem, key, hll);
+            app.buffer_command(token).await;
+        }
+        return resp_serializer::to_resp_integer(card as i64).into();
+    }
+
+    let mut registers = hyperloglog::new_registers();
+    for key in &keys {
+        let merged = match command_utils::get_hyperloglog(&mem, key) {
+            Ok(Some(hll)) => hll
+                .merge_into(&mut registers)
+                .map_err(|err| err.to_string()),
+            Ok(None) => Ok(()),
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = merged {
+            return resp_serializer::to_err_string(err).into();
+        }
+    }
+
+    resp_serializer::to_resp_integer(hyperloglog::count_registers(&registers) as i64).into()
+}
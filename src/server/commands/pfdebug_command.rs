@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils;
+
+pub async fn execute_pfdebug(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(subcommand), Some(key), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let mut hll = match command_utils::get_hyperloglog(&mem, &key) {
+        Ok(Some(hll)) => hll,
+        Ok(None) => {
+            return resp_serializer::to_err_string("ERR The specified key does not exist".into())
+                .into()
+        }
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    match subcommand.to_uppercase().as_str() {
+        "GETREG" => {
+            let converted = match hll.to_dense() {
+                Ok(converted) => converted,
+                Err(err) => return resp_serializer::to_err_string(err.to_string()).into(),
+            };
+
+            let registers = match hll.registers() {
+                Ok(registers) => registers,
+                Err(err) => return resp_serializer::to_err_string(err.to_string()).into(),
+            };
+
+            if converted {
+                command_utils::set_hyperloglog(&mut mem, &key, hll);
+                app.buffer_command(token).await;
+            }
+
+            let registers: Vec<String> = registers
+                .into_iter()
+                .map(|register| resp_serializer::to_resp_integer(register as i64))
+                .collect();
+            resp_serializer::to_resp_nested_array(registers).into()
+        }
+        "DECODE" => match hll.decode_sparse() {
+            Some(decoded) => resp_serializer::to_resp_bulk(decoded).into(),
+            None => resp_serializer::to_err_string("ERR HLL encoding is not sparse".into()).into(),
+        },
+        "ENCODING" => {
+            let encoding = if hll.is_sparse() { "sparse" } else { "dense" };
+            resp_serializer::to_resp_string(encoding.to_owned()).into()
+        }
+        "TODENSE" => match hll.to_dense() {
+            Ok(true) => {
+                command_utils::set_hyperloglog(&mut mem, &key, hll);
+                app.buffer_command(token).await;
+                resp_serializer::to_resp_integer(1).into()
+            }
+            Ok(false) => resp_serializer::to_resp_integer(0).into(),
+            Err(err) => resp_serializer::to_err_string(err.to_string()).into(),
+        },
+        _ => resp_serializer::to_err_string(format!(
+            "ERR Unknown PFDEBUG subcommand '{}'",
+            subcommand
+        ))
+        .into(),
+    }
+}
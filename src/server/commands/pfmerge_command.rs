@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        hyperloglog::{self, HyperLogLog},
+        redis_error::RedisError,
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_pfmerge(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some(dest) = keys.first() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let mut registers = hyperloglog::new_registers();
+    let mut use_dense = false;
+
+    // The destination takes part in the union as well.
+    for key in &keys {
+        let merged = match command_utils::get_hyperloglog(&mem, key) {
+            Ok(Some(hll)) => {
+                use_dense |= !hll.is_sparse();
+                hll.merge_into(&mut registers)
+                    .map_err(|err| err.to_string())
+            }
+            Ok(None) => Ok(()),
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = merged {
+            return resp_serializer::to_err_string(err).into();
+        }
+    }
+
+    let mut hll = match command_utils::get_hyperloglog(&mem, dest) {
+        Ok(Some(hll)) => hll,
+        _ => HyperLogLog::new(),
+    };
+
+    if let Err(err) = write_registers(&mut hll, registers, use_dense) {
+        return resp_serializer::to_err_string(err.to_string()).into();
+    }
+
+    hll.invalidate_cache();
+    command_utils::set_hyperloglog(&mut mem, dest, hll);
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".to_owned()).into()
+}
+
+fn write_registers(
+    hll: &mut HyperLogLog,
+    registers: Vec<u8>,
+    use_dense: bool,
+) -> Result<(), RedisError> {
+    if use_dense {
+        hll.to_dense()?;
+    }
+
+    for (index, count) in registers.into_iter().enumerate() {
+        if count > 0 {
+            hll.set_register(index, count)?;
+        }
+    }
+    Ok(())
+}
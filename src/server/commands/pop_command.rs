@@ -0,0 +1,66 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils::{self, ListEnd};
+
+pub async fn execute_lpop(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    pop(app, token, ListEnd::Left).await
+}
+
+pub async fn execute_rpop(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    pop(app, token, ListEnd::Right).await
+}
+
+async fn pop(app: Arc<RedisApp>, token: &RespTk, end: ListEnd) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), count, None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let count = match count.map(|count| command_utils::parse_integer(&count)) {
+        None => None,
+        Some(Some(count)) if count >= 0 => Some(count as usize),
+        Some(_) => {
+            return resp_serializer::to_err_string(
+                "ERR value is out of range, must be positive".into(),
+            )
+            .into()
+        }
+    };
+
+    let mut mem = app.memory.lock().await;
+    let list = match command_utils::get_value_mut::<VecDeque<String>>(&mut mem, &key) {
+        Ok(Some(list)) => list,
+        Ok(None) if count.is_some() => return resp_serializer::null_resp_array().into(),
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let popped = pop_elements(list, end, count.unwrap_or(1));
+    command_utils::remove_if_empty(&mut mem, &key);
+
+    if !popped.is_empty() {
+        app.buffer_command(token).await;
+    }
+
+    match count {
+        Some(_) => resp_serializer::to_resp_array(popped).into(),
+        None => resp_serializer::to_resp_bulk(popped.into_iter().next().unwrap_or_default()).into(),
+    }
+}
+
+pub fn pop_elements(list: &mut VecDeque<String>, end: ListEnd, count: usize) -> Vec<String> {
+    let count = count.min(list.len());
+    match end {
+        ListEnd::Left => list.drain(..count).collect(),
+        ListEnd::Right => list.drain(list.len() - count..).rev().collect(),
+    }
+}
@@ -0,0 +1,69 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse,
+};
+
+use super::command_utils::{self, ListEnd};
+
+pub async fn execute_lpush(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    push(app, token, ListEnd::Left, false).await
+}
+
+pub async fn execute_rpush(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    push(app, token, ListEnd::Right, false).await
+}
+
+pub async fn execute_lpushx(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    push(app, token, ListEnd::Left, true).await
+}
+
+pub async fn execute_rpushx(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    push(app, token, ListEnd::Right, true).await
+}
+
+async fn push(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    end: ListEnd,
+    only_existing: bool,
+) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let key = command_utils::get_next_arg_string(&mut args);
+    let elements: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+
+    let Some(key) = key.filter(|_| !elements.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    if only_existing {
+        match command_utils::get_value::<VecDeque<String>>(&mem, &key) {
+            Ok(Some(_)) => {}
+            Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+            Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+        }
+    }
+
+    let list = match command_utils::get_or_create_value(&mut mem, &key, VecDeque::new) {
+        Ok(list) => list,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    push_elements(list, end, elements);
+    let len = list.len();
+
+    app.buffer_command(token).await;
+    app.signal_key_ready(&mut mem, &key).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
+
+pub fn push_elements(list: &mut VecDeque<String>, end: ListEnd, elements: Vec<String>) {
+    for element in elements {
+        match end {
+            ListEnd::Left => list.push_front(element),
+            ListEnd::Right => list.push_back(element),
+        }
+    }
+}
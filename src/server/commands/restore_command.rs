@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use crate::{
+    rdb::dump_payload,
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{entry_value::EntryValue, execution_response::ExecResponse, redis_error::RedisError},
+    utils,
+};
+
+use super::command_utils;
+
+struct RestoreOptions {
+    replace: bool,
+    absolute_ttl: bool,
+}
+
+pub async fn execute_restore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+
+    let (Some(key), Some(ttl), Some(payload)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(
+            "ERR wrong number of arguments for 'restore' command".into(),
+        )
+        .into();
+    };
+
+    let Ok(ttl) = ttl.parse::<i64>() else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    if ttl < 0 {
+        return resp_serializer::to_err_string("ERR Invalid TTL value, must be >= 0".into()).into();
+    }
+
+    let options = match get_options(&mut args) {
+        Ok(options) => options,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let value = match dump_payload::deserialize(&utils::string_to_bytes(&payload)) {
+        Ok(value) => value,
+        Err(RedisError::InvalidDumpPayload) => {
+            return resp_serializer::to_err_string(
+                "ERR DUMP payload version or checksum are wrong".into(),
+            )
+            .into()
+        }
+        Err(_) => {
+            return resp_serializer::to_err_string("ERR Bad data format".into()).into();
+        }
+    };
+
+    let mut mem = app.memory.lock().await;
+
+    if !options.replace && mem.get(&key).is_some_and(|entry| !entry.is_expired()) {
+        return resp_serializer::to_err_string("BUSYKEY Target key name already exists.".into())
+            .into();
+    }
+
+    let expires_at = match (ttl, options.absolute_ttl) {
+        (0, _) => None,
+        (ttl, true) => Some(ttl as u128),
+        (ttl, false) => Some(utils::get_current_time_ms() + ttl as u128),
+    };
+
+    if expires_at.is_some_and(|exp| exp <= utils::get_current_time_ms()) {
+        mem.remove(&key);
+    } else {
+        mem.insert(key.clone(), EntryValue { value, expires_at });
+    }
+
+    app.buffer_command(token).await;
+    app.signal_key_ready(&mut mem, &key).await;
+    resp_serializer::to_resp_string("OK".to_owned()).into()
+}
+
+fn get_options<'a>(args: &mut impl Iterator<Item = &'a RespTk>) -> Result<RestoreOptions, String> {
+    let mut options = RestoreOptions {
+        replace: false,
+        absolute_ttl: false,
+    };
+    let mut idle_time = None;
+    let mut frequency = None;
+
+    while let Some(option) = command_utils::get_next_arg_string(args) {
+        match option.to_uppercase().as_str() {
+            "REPLACE" => options.replace = true,
+            "ABSTTL" => options.absolute_ttl = true,
+            "IDLETIME" if frequency.is_none() => {
+                let value = command_utils::get_next_arg_string(args)
+                    .ok_or("ERR syntax error".to_owned())?
+                    .parse::<i64>()
+                    .map_err(|_| "ERR value is not an integer or out of range".to_owned())?;
+                if value < 0 {
+                    return Err("ERR Invalid IDLETIME value, must be >= 0".into());
+                }
+                idle_time = Some(value);
+            }
+            "FREQ" if idle_time.is_none() => {
+                let value = command_utils::get_next_arg_string(args)
+                    .ok_or("ERR syntax error".to_owned())?
+                    .parse::<i64>()
+                    .map_err(|_| "ERR value is not an integer or out of range".to_owned())?;
+                if !(0..=255).contains(&value) {
+                    return Err("ERR Invalid FREQ value, must be >= 0 and <= 255".into());
+                }
+                frequency = Some(value);
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+
+    Ok(options)
+}
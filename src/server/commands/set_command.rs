@@ -1,52 +1,143 @@
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
 use crate::{
     resp::resp_serializer,
     resp_desserializer::RespTk,
     server::redis_app::RedisApp,
-    types::{execution_response::ExecResponse, value_container::ValueContainer},
+    types::{
+        entry_value::EntryValue, execution_response::ExecResponse, value_container::ValueContainer,
+    },
 };
 
+use super::command_utils;
+
+#[derive(PartialEq, Eq)]
+enum SetCondition {
+    Always,
+    IfNotExists,
+    IfExists,
+}
+
+enum SetExpiration {
+    Discard,
+    Keep,
+    At(u128),
+}
+
+struct SetOptions {
+    condition: SetCondition,
+    get: bool,
+    expiration: SetExpiration,
+}
+
 pub async fn execute_set(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
     let mut args = token.get_command_args();
 
-    if let (Some(key), Some(value)) = (
-        args.next().and_then(|tk| tk.get_content_string()),
-        args.next().and_then(|tk| Some(tk.get_value())),
-    ) {
-        let opts = get_optional_args(&mut args);
-        let exp = get_expiration_time(opts);
+    let (Some(key), Some(value_tk)) = (command_utils::get_next_arg_string(&mut args), args.next())
+    else {
+        return resp_serializer::to_err_string(
+            "ERR wrong number of arguments for 'set' command".into(),
+        )
+        .into();
+    };
+
+    let value = value_tk.get_value();
+
+    let opts = match get_optional_args(&mut args) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let current = mem.get(&key).filter(|entry| !entry.is_expired());
+
+    let old_value = match current.map(|entry| &entry.value) {
+        Some(ValueContainer::String(_)) | Some(ValueContainer::Integer(_)) | None => {
+            current.map(|entry| entry.value.clone())
+        }
+        Some(_) if opts.get => {
+            return resp_serializer::to_err_string(command_utils::WRONGTYPE_ERROR.into()).into()
+        }
+        Some(_) => None,
+    };
+
+    let should_set = match opts.condition {
+        SetCondition::Always => true,
+        SetCondition::IfNotExists => current.is_none(),
+        SetCondition::IfExists => current.is_some(),
+    };
 
-        app.put_entry(key, value, exp).await;
-        app.buffer_command(token).await;
-        return resp_serializer::to_resp_string("OK".to_owned()).into();
+    if should_set {
+        let expires_at = match opts.expiration {
+            SetExpiration::Discard => None,
+            SetExpiration::Keep => current.and_then(|entry| entry.expires_at),
+            SetExpiration::At(at) => Some(at),
+        };
+
+        mem.insert(key.to_owned(), EntryValue { value, expires_at });
+
+        // Relative TTLs are sent as absolute ones, so a replica applying the
+        // command late expires the key at the same time as this instance.
+        match opts.expiration {
+            SetExpiration::At(at) => {
+                let replicated = vec![
+                    "SET".into(),
+                    key,
+                    value_tk.get_content_string().unwrap_or_default(),
+                    "PXAT".into(),
+                    at.to_string(),
+                ];
+                app.buffer_command(&command_utils::to_command_token(replicated))
+                    .await;
+            }
+            _ => app.buffer_command(token).await,
+        }
+    }
+
+    match (opts.get, old_value) {
+        (true, Some(old)) => {
+            let old: String = old.into();
+            resp_serializer::to_resp_bulk(old).into()
+        }
+        (true, None) => resp_serializer::null_resp_string().into(),
+        (false, _) if should_set => resp_serializer::to_resp_string("OK".to_owned()).into(),
+        (false, _) => resp_serializer::null_resp_string().into(),
     }
-    resp_serializer::null_resp_string().into()
 }
 
 fn get_optional_args<'a>(
     args: &mut impl Iterator<Item = &'a RespTk>,
-) -> HashMap<String, ValueContainer> {
-    let mut result = HashMap::new();
-    while let (Some(key), val) = (
-        args.next().and_then(|tk| tk.get_content_string()),
-        args.next().and_then(|tk| Some(tk.get_value())),
-    ) {
-        _ = result.insert(key, val.unwrap_or(ValueContainer::Null));
-    }
-    result
-}
+) -> Result<SetOptions, String> {
+    let mut opts = SetOptions {
+        condition: SetCondition::Always,
+        get: false,
+        expiration: SetExpiration::Discard,
+    };
+    let mut has_expiration = false;
 
-fn get_expiration_time(map: HashMap<String, ValueContainer>) -> Option<u128> {
-    for (name, val) in map {
-        match (name.as_str(), val) {
-            ("PX", ValueContainer::String(exp)) => return exp.parse::<u128>().ok(),
-            ("EX", ValueContainer::String(exp)) => {
-                return exp.parse::<u128>().map(|x| x * 1000).ok()
+    while let Some(opt) = command_utils::get_next_arg_string(args) {
+        match opt.to_uppercase().as_str() {
+            "NX" if opts.condition != SetCondition::IfExists => {
+                opts.condition = SetCondition::IfNotExists
             }
-            _ => {}
-        };
+            "XX" if opts.condition != SetCondition::IfNotExists => {
+                opts.condition = SetCondition::IfExists
+            }
+            "GET" => opts.get = true,
+            "KEEPTTL" if !has_expiration => {
+                has_expiration = true;
+                opts.expiration = SetExpiration::Keep;
+            }
+            unit @ ("EX" | "PX" | "EXAT" | "PXAT") if !has_expiration => {
+                let time = command_utils::get_next_arg_string(args)
+                    .ok_or("ERR syntax error".to_owned())?;
+                has_expiration = true;
+                opts.expiration =
+                    SetExpiration::At(command_utils::get_expiration_time("set", unit, &time)?);
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
     }
 
-    None
+    Ok(opts)
 }
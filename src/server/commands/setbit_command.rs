@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{bitmap, execution_response::ExecResponse, value_container::ValueContainer},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_setbit(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(offset), Some(bit)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(offset) = command_utils::parse_bit_offset(&offset) else {
+        return resp_serializer::to_err_string(
+            "ERR bit offset is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    let bit = match bit.as_str() {
+        "0" => 0,
+        "1" => 1,
+        _ => {
+            return resp_serializer::to_err_string(
+                "ERR bit is not an integer or out of range".into(),
+            )
+            .into()
+        }
+    };
+
+    let mut mem = app.memory.lock().await;
+    let mut bytes = match command_utils::get_string_value(&mem, &key) {
+        Ok(value) => utils::string_to_bytes(&value.unwrap_or_default()),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let old = bitmap::set_bit(&mut bytes, offset, bit);
+    command_utils::set_string_value(
+        &mut mem,
+        &key,
+        ValueContainer::String(utils::bytes_to_string(&bytes)),
+    );
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(old as i64).into()
+}
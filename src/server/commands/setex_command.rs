@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{entry_value::EntryValue, execution_response::ExecResponse},
+};
+
+use super::command_utils;
+
+pub async fn execute_setex(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    set_with_expiration(app, token, "EX").await
+}
+
+pub async fn execute_psetex(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    set_with_expiration(app, token, "PX").await
+}
+
+async fn set_with_expiration(app: Arc<RedisApp>, token: &RespTk, unit: &str) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(time), Some(value_tk)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let command = token.get_command_name().to_lowercase();
+    let expires_at = match command_utils::get_expiration_time(&command, unit, &time) {
+        Ok(expires_at) => expires_at,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    mem.insert(
+        key.to_owned(),
+        EntryValue {
+            value: value_tk.get_value(),
+            expires_at: Some(expires_at),
+        },
+    );
+
+    // Replicated with an absolute expiration, like SET with EX or PX.
+    let replicated = vec![
+        "SET".into(),
+        key,
+        value_tk.get_content_string().unwrap_or_default(),
+        "PXAT".into(),
+        expires_at.to_string(),
+    ];
+    app.buffer_command(&command_utils::to_command_token(replicated))
+        .await;
+    resp_serializer::to_resp_string("OK".to_owned()).into()
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{entry_value::EntryValue, execution_response::ExecResponse},
+};
+
+use super::command_utils;
+
+pub async fn execute_setnx(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(value)) = (
+        command_utils::get_next_arg_string(&mut args),
+        args.next().map(|tk| tk.get_value()),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    if mem.get(&key).is_some_and(|entry| !entry.is_expired()) {
+        return resp_serializer::to_resp_integer(0).into();
+    }
+
+    mem.insert(
+        key,
+        EntryValue {
+            value,
+            expires_at: None,
+        },
+    );
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(1).into()
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, value_container::ValueContainer},
+    utils,
+};
+
+use super::command_utils;
+
+const MAX_STRING_SIZE: usize = 512 * 1024 * 1024;
+
+pub async fn execute_setrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(offset), Some(patch)) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(offset) = command_utils::parse_integer(&offset) else {
+        return resp_serializer::to_err_string(
+            "ERR value is not an integer or out of range".into(),
+        )
+        .into();
+    };
+
+    if offset < 0 {
+        return resp_serializer::to_err_string("ERR offset is out of range".into()).into();
+    }
+
+    let offset = offset as usize;
+    let patch = utils::string_to_bytes(&patch);
+
+    if offset.saturating_add(patch.len()) > MAX_STRING_SIZE {
+        return resp_serializer::to_err_string(
+            "ERR string exceeds maximum allowed size (proto-max-bulk-len)".into(),
+        )
+        .into();
+    }
+
+    let mut mem = app.memory.lock().await;
+    let current = match command_utils::get_string_value(&mem, &key) {
+        Ok(current) => current,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut value = utils::string_to_bytes(&current.unwrap_or_default());
+
+    if patch.is_empty() {
+        return resp_serializer::to_resp_integer(value.len() as i64).into();
+    }
+
+    if value.len() < offset + patch.len() {
+        value.resize(offset + patch.len(), 0);
+    }
+    value[offset..offset + patch.len()].copy_from_slice(&patch);
+
+    let len = value.len();
+    command_utils::set_string_value(
+        &mut mem,
+        &key,
+        ValueContainer::String(utils::bytes_to_string(&value)),
+    );
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
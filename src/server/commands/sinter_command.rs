@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, keyspace::Keyspace, set::RedisSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_sinter(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if keys.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mem = app.memory.lock().await;
+    match intersect(&mem, &keys, 0) {
+        Ok(members) => resp_serializer::to_resp_array(members).into(),
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub async fn execute_sinterstore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((destination, keys)) = args.split_first().filter(|(_, keys)| !keys.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let members = match intersect(&mem, keys, 0) {
+        Ok(members) => members,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let len = command_utils::store_set(&mut mem, destination, members.into_iter().collect());
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
+
+pub async fn execute_sintercard(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((numkeys, rest)) = args.split_first().filter(|(_, rest)| !rest.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let numkeys = match command_utils::parse_integer(numkeys) {
+        Some(numkeys) if numkeys > 0 => numkeys as usize,
+        _ => {
+            return resp_serializer::to_err_string("ERR numkeys should be greater than 0".into())
+                .into()
+        }
+    };
+
+    if numkeys > rest.len() {
+        return resp_serializer::to_err_string(
+            "ERR Number of keys can't be greater than number of args".into(),
+        )
+        .into();
+    }
+
+    let (keys, options) = rest.split_at(numkeys);
+    let limit = match options {
+        [] => 0,
+        [option, limit] if option.eq_ignore_ascii_case("LIMIT") => {
+            match command_utils::parse_integer(limit) {
+                Some(limit) if limit >= 0 => limit as usize,
+                _ => {
+                    return resp_serializer::to_err_string("ERR LIMIT can't be negative".into())
+                        .into()
+                }
+            }
+        }
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    match intersect(&mem, keys, limit) {
+        Ok(members) => resp_serializer::to_resp_integer(members.len() as i64).into(),
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+// Walks the smallest set and keeps the members found in every other one,
+// stopping early once `limit` members are found (0 means no limit).
+fn intersect(mem: &Keyspace, keys: &[String], limit: usize) -> Result<Vec<String>, &'static str> {
+    let sets = command_utils::get_sets(mem, keys)?;
+    let Some(mut sets) = sets.into_iter().collect::<Option<Vec<&RedisSet>>>() else {
+        return Ok(vec![]);
+    };
+
+    sets.sort_by_key(|set| set.len());
+    let (smallest, others) = sets.split_first().ok_or(command_utils::WRONGTYPE_ERROR)?;
+
+    let members = smallest
+        .iter()
+        .filter(|member| others.iter().all(|set| set.contains(member)));
+
+    Ok(match limit {
+        0 => members.collect(),
+        limit => members.take(limit).collect(),
+    })
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, set::RedisSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_sismember(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(member), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<RedisSet>(&mem, &key) {
+        Ok(set) => {
+            let exists = set.is_some_and(|set| set.contains(&member));
+            resp_serializer::to_resp_integer(exists as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub async fn execute_smismember(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let key = command_utils::get_next_arg_string(&mut args);
+    let members: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+
+    let Some(key) = key.filter(|_| !members.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    let set = match command_utils::get_value::<RedisSet>(&mem, &key) {
+        Ok(set) => set,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let replies = members
+        .iter()
+        .map(|member| {
+            let exists = set.is_some_and(|set| set.contains(member));
+            resp_serializer::to_resp_integer(exists as i64)
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(replies).into()
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, set::RedisSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_smove(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(source), Some(destination), Some(member), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let exists = match (
+        command_utils::get_value::<RedisSet>(&mem, &source),
+        command_utils::get_value::<RedisSet>(&mem, &destination),
+    ) {
+        (Err(err), _) | (_, Err(err)) => return resp_serializer::to_err_string(err.into()).into(),
+        (Ok(set), _) => set.is_some_and(|set| set.contains(&member)),
+    };
+
+    if !exists {
+        return resp_serializer::to_resp_integer(0).into();
+    }
+
+    if source != destination {
+        if let Ok(Some(set)) = command_utils::get_value_mut::<RedisSet>(&mut mem, &source) {
+            set.remove(&member);
+        }
+        command_utils::remove_if_empty(&mut mem, &source);
+
+        if let Ok(set) = command_utils::get_or_create_value(&mut mem, &destination, RedisSet::new) {
+            set.insert(member);
+        }
+        app.buffer_command(token).await;
+    }
+
+    resp_serializer::to_resp_integer(1).into()
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, set::RedisSet},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_spop(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, count) = match args.as_slice() {
+        [key] => (key, None),
+        [key, count] => match command_utils::parse_integer(count) {
+            Some(count) if count >= 0 => (key, Some(count as usize)),
+            _ => {
+                return resp_serializer::to_err_string(
+                    "ERR value is out of range, must be positive".into(),
+                )
+                .into()
+            }
+        },
+        [_, _, _] => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let set = match command_utils::get_value_mut::<RedisSet>(&mut mem, key) {
+        Ok(Some(set)) => set,
+        Ok(None) if count.is_some() => return resp_serializer::to_resp_array(vec![]).into(),
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let members = set.members();
+    let popped: Vec<String> = utils::sample_distinct(members.len(), count.unwrap_or(1))
+        .into_iter()
+        .map(|idx| members[idx].to_owned())
+        .collect();
+
+    for member in &popped {
+        set.remove(member);
+    }
+    command_utils::remove_if_empty(&mut mem, key);
+
+    // Replicas remove the same members instead of picking their own.
+    if !popped.is_empty() {
+        let mut command = vec!["SREM".to_owned(), key.to_owned()];
+        command.extend(popped.iter().cloned());
+        app.buffer_command(&command_utils::to_command_token(command))
+            .await;
+    }
+
+    match count {
+        Some(_) => resp_serializer::to_resp_array(popped).into(),
+        None => match popped.into_iter().next() {
+            Some(member) => resp_serializer::to_resp_bulk(member).into(),
+            None => resp_serializer::null_resp_string().into(),
+        },
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, set::RedisSet},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_srandmember(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, count) = match args.as_slice() {
+        [key] => (key, None),
+        [key, count] => (key, Some(count)),
+        [_, _, _] => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let count = match count.map(|count| command_utils::parse_integer(count)) {
+        None => None,
+        Some(Some(count)) if count.unsigned_abs() <= i64::MAX as u64 / 2 => Some(count),
+        Some(Some(_)) => {
+            return resp_serializer::to_err_string("ERR value is out of range".into()).into()
+        }
+        Some(None) => {
+            return resp_serializer::to_err_string(
+                "ERR value is not an integer or out of range".into(),
+            )
+            .into()
+        }
+    };
+
+    let mem = app.memory.lock().await;
+    let set = match command_utils::get_value::<RedisSet>(&mem, key) {
+        Ok(Some(set)) => set,
+        Ok(None) if count.is_some() => return resp_serializer::to_resp_array(vec![]).into(),
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let members = set.members();
+    let Some(count) = count else {
+        let member = members[utils::random_index(members.len())].to_owned();
+        return resp_serializer::to_resp_bulk(member).into();
+    };
+
+    // A negative count allows the same member to be returned more than once.
+    let picked: Vec<usize> = if count < 0 {
+        (0..count.unsigned_abs())
+            .map(|_| utils::random_index(members.len()))
+            .collect()
+    } else {
+        utils::sample_distinct(members.len(), count as usize)
+    };
+
+    let reply = picked
+        .into_iter()
+        .map(|idx| members[idx].to_owned())
+        .collect();
+    resp_serializer::to_resp_array(reply).into()
+}
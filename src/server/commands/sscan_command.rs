@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, set::RedisSet},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_sscan(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, args)) = args.split_first().filter(|(_, args)| !args.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let opts = match command_utils::parse_scan_args(args, false) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let set = match command_utils::get_value::<RedisSet>(&mem, key) {
+        Ok(set) => set,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+    let members = set.map(|set| set.members()).unwrap_or_default();
+
+    // Intsets are small enough to be returned in a single call.
+    let (cursor, page) = match set {
+        Some(set) if set.is_intset() => (0, members.iter().map(|member| (member, ())).collect()),
+        _ => utils::scan_page(
+            members.iter().map(|member| (member, ())),
+            opts.cursor,
+            opts.count,
+        ),
+    };
+
+    let items = page
+        .into_iter()
+        .filter(|(member, _)| opts.matches(member))
+        .map(|(member, _)| member.to_owned())
+        .collect();
+
+    resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_bulk(cursor.to_string()),
+        resp_serializer::to_resp_array(items),
+    ])
+    .into()
+}
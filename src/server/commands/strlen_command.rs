@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer, resp_desserializer::RespTk, server::redis_app::RedisApp,
+    types::execution_response::ExecResponse, utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_strlen(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let Some(key) = command_utils::get_next_arg_string(&mut token.get_command_args()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_string_value(&mem, &key) {
+        Ok(value) => {
+            let len = value.map_or(0, |value| utils::string_to_bytes(&value).len());
+            resp_serializer::to_resp_integer(len as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, keyspace::Keyspace, set::RedisSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_sunion(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let keys: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if keys.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mem = app.memory.lock().await;
+    match union(&mem, &keys) {
+        Ok(set) => resp_serializer::to_resp_array(set.members()).into(),
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub async fn execute_sunionstore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((destination, keys)) = args.split_first().filter(|(_, keys)| !keys.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let set = match union(&mem, keys) {
+        Ok(set) => set,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let len = command_utils::store_set(&mut mem, destination, set);
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
+
+fn union(mem: &Keyspace, keys: &[String]) -> Result<RedisSet, &'static str> {
+    let sets = command_utils::get_sets(mem, keys)?;
+    Ok(sets
+        .into_iter()
+        .flatten()
+        .flat_map(|set| set.iter())
+        .collect())
+}
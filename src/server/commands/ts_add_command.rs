@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        entry_value::EntryValue, execution_response::ExecResponse, keyspace::Keyspace,
+        time_series::DuplicatePolicy, time_series::TimeSeries, value_container::ValueContainer,
+    },
+    utils,
+};
+
+use super::{command_utils, ts_create_command::SeriesOptions};
+
+pub async fn execute_ts_add(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, timestamp, value, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (timestamp, value) = match parse_sample(timestamp, value) {
+        Ok(sample) => sample,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+    let options = match SeriesOptions::parse(options, true) {
+        Ok(options) => options,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    match command_utils::get_value::<TimeSeries>(&mem, key) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            mem.insert(
+                key.to_owned(),
+                EntryValue {
+                    value: ValueContainer::TimeSeries(options.to_series()),
+                    expires_at: None,
+                },
+            );
+        }
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    }
+
+    if let Err(err) = add_sample(&mut mem, key, timestamp, value, options.on_duplicate) {
+        return resp_serializer::to_err_string(err).into();
+    }
+
+    // `*` resolves to the primary's clock, so replicas get the timestamp.
+    let mut command = vec!["TS.ADD".to_owned(), key.to_owned(), timestamp.to_string()];
+    command.extend(args.into_iter().skip(2));
+    app.buffer_command(&command_utils::to_command_token(command))
+        .await;
+    resp_serializer::to_resp_integer(timestamp as i64).into()
+}
+
+// Replies the timestamp of every sample added, or the error it hit.
+pub async fn execute_ts_madd(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if args.is_empty() || !args.len().is_multiple_of(3) {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let mut mem = app.memory.lock().await;
+    let mut command = vec!["TS.MADD".to_owned()];
+    let replies = args
+        .chunks(3)
+        .map(|sample| {
+            let (timestamp, value) = parse_sample(&sample[1], &sample[2])?;
+            add_sample(&mut mem, &sample[0], timestamp, value, None)?;
+            command.extend([
+                sample[0].to_owned(),
+                timestamp.to_string(),
+                sample[2].to_owned(),
+            ]);
+            Ok(timestamp)
+        })
+        .map(|result: Result<u64, String>| match result {
+            Ok(timestamp) => resp_serializer::to_resp_integer(timestamp as i64),
+            Err(err) => resp_serializer::to_err_string(err),
+        })
+        .collect();
+
+    if command.len() > 1 {
+        app.buffer_command(&command_utils::to_command_token(command))
+            .await;
+    }
+    resp_serializer::to_resp_nested_array(replies).into()
+}
+
+fn parse_sample(timestamp: &str, value: &str) -> Result<(u64, f64), &'static str> {
+    let timestamp = match timestamp {
+        "*" => utils::get_current_time_ms() as u64,
+        timestamp => command_utils::parse_integer(timestamp)
+            .filter(|timestamp| *timestamp >= 0)
+            .ok_or("ERR TSDB: invalid timestamp")? as u64,
+    };
+    let value = command_utils::parse_float(value).ok_or("ERR TSDB: invalid value")?;
+    Ok((timestamp, value))
+}
+
+// Adds a sample to an existing series and feeds it through the series'
+// compaction rules into their destinations.
+pub fn add_sample(
+    mem: &mut Keyspace,
+    key: &str,
+    timestamp: u64,
+    value: f64,
+    policy: Option<DuplicatePolicy>,
+) -> Result<(), String> {
+    let series = match command_utils::get_value_mut::<TimeSeries>(mem, key) {
+        Ok(Some(series)) => series,
+        Ok(None) => return Err("ERR TSDB: the key does not exist".into()),
+        Err(err) => return Err(err.into()),
+    };
+
+    series.add(timestamp, value, policy)?;
+    for (dest, start, value) in series.compactions(timestamp) {
+        // Destinations deleted since the rule was created are skipped.
+        let _ = add_sample(mem, &dest, start, value, Some(DuplicatePolicy::Last));
+    }
+    Ok(())
+}
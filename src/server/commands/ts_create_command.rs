@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        entry_value::EntryValue,
+        execution_response::ExecResponse,
+        time_series::{DuplicatePolicy, TimeSeries},
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+// Options accepted by TS.CREATE, and by TS.ADD for the series it creates.
+#[derive(Default)]
+pub struct SeriesOptions {
+    pub retention: u64,
+    pub labels: Vec<(String, String)>,
+    pub duplicate_policy: DuplicatePolicy,
+    pub on_duplicate: Option<DuplicatePolicy>,
+}
+
+impl SeriesOptions {
+    pub fn parse(mut options: &[String], allow_on_duplicate: bool) -> Result<Self, String> {
+        let mut parsed = SeriesOptions::default();
+
+        while let [option, rest @ ..] = options {
+            let [value, rest @ ..] = rest else {
+                return Err("ERR syntax error".into());
+            };
+            match option.to_uppercase().as_str() {
+                "RETENTION" => {
+                    parsed.retention = command_utils::parse_integer(value)
+                        .filter(|retention| *retention >= 0)
+                        .ok_or("ERR TSDB: Couldn't parse RETENTION")?
+                        as u64;
+                }
+                "DUPLICATE_POLICY" => {
+                    parsed.duplicate_policy = DuplicatePolicy::parse(value)
+                        .ok_or("ERR TSDB: Unknown DUPLICATE_POLICY")?;
+                }
+                "ON_DUPLICATE" if allow_on_duplicate => {
+                    parsed.on_duplicate = Some(
+                        DuplicatePolicy::parse(value).ok_or("ERR TSDB: Unknown ON_DUPLICATE")?,
+                    );
+                }
+                // Labels run to the end of the command.
+                "LABELS" => {
+                    let labels = &options[1..];
+                    if !labels.len().is_multiple_of(2) {
+                        return Err("ERR TSDB: Invalid LABELS".into());
+                    }
+                    parsed.labels = labels
+                        .chunks(2)
+                        .map(|pair| (pair[0].to_owned(), pair[1].to_owned()))
+                        .collect();
+                    break;
+                }
+                _ => return Err("ERR syntax error".into()),
+            }
+            options = rest;
+        }
+        Ok(parsed)
+    }
+
+    pub fn to_series(&self) -> TimeSeries {
+        let mut series = TimeSeries::new();
+        series.retention = self.retention;
+        series.labels = self.labels.clone();
+        series.duplicate_policy = self.duplicate_policy;
+        series
+    }
+}
+
+pub async fn execute_ts_create(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, options)) = args.split_first() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let options = match SeriesOptions::parse(options, false) {
+        Ok(options) => options,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    if mem.get(key).is_some_and(|entry| !entry.is_expired()) {
+        return resp_serializer::to_err_string("ERR TSDB: key already exists".into()).into();
+    }
+
+    mem.insert(
+        key.to_owned(),
+        EntryValue {
+            value: ValueContainer::TimeSeries(options.to_series()),
+            expires_at: None,
+        },
+    );
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".into()).into()
+}
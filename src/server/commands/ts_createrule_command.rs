@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, time_series::TimeSeries},
+};
+
+use super::{command_utils, ts_range_command};
+
+// Destinations cannot feed rules of their own, which keeps rules acyclic.
+pub async fn execute_ts_createrule(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (source, dest, aggregation, duration, align) = match args.as_slice() {
+        [source, dest, option, aggregation, duration, align @ ..]
+            if option.eq_ignore_ascii_case("AGGREGATION") && align.len() <= 1 =>
+        {
+            (source, dest, aggregation, duration, align.first())
+        }
+        [_, _, _, _, _, ..] => {
+            return resp_serializer::to_err_string("ERR syntax error".into()).into()
+        }
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let align = match align.map(|align| command_utils::parse_integer(align)) {
+        None => 0,
+        Some(Some(align)) if align >= 0 => align as u64,
+        Some(_) => {
+            return resp_serializer::to_err_string("ERR TSDB: invalid alignTimestamp".into()).into()
+        }
+    };
+    let bucketing = match ts_range_command::parse_bucketing(aggregation, duration, align) {
+        Ok(bucketing) => bucketing,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    if source == dest {
+        return resp_serializer::to_err_string(
+            "ERR TSDB: the source key and destination key should be different".into(),
+        )
+        .into();
+    }
+
+    let mut mem = app.memory.lock().await;
+    let (source_series, dest_series) = match (
+        command_utils::get_value::<TimeSeries>(&mem, source),
+        command_utils::get_value::<TimeSeries>(&mem, dest),
+    ) {
+        (Ok(Some(source)), Ok(Some(dest))) => (source, dest),
+        (Err(err), _) | (_, Err(err)) => return resp_serializer::to_err_string(err.into()).into(),
+        _ => {
+            return resp_serializer::to_err_string("ERR TSDB: the key does not exist".into()).into()
+        }
+    };
+
+    if dest_series.source.is_some() {
+        return resp_serializer::to_err_string(
+            "ERR TSDB: the destination key already has a src rule".into(),
+        )
+        .into();
+    }
+    if !dest_series.rules.is_empty() || source_series.source.is_some() {
+        return resp_serializer::to_err_string(
+            "ERR TSDB: the destination key cannot have rules".into(),
+        )
+        .into();
+    }
+
+    if let Ok(Some(series)) = command_utils::get_value_mut::<TimeSeries>(&mut mem, source) {
+        series.add_rule(dest.to_owned(), bucketing);
+    }
+    if let Ok(Some(series)) = command_utils::get_value_mut::<TimeSeries>(&mut mem, dest) {
+        series.source = Some(source.to_owned());
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_string("OK".into()).into()
+}
@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        time_series::{Aggregation, Bucketing, TimeSeries},
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+struct RangeQuery {
+    from: u64,
+    to: u64,
+    count: Option<usize>,
+    bucketing: Option<Bucketing>,
+    reverse: bool,
+}
+
+impl RangeQuery {
+    fn samples(&self, series: &TimeSeries) -> String {
+        let mut samples = series.range(self.from, self.to);
+        if let Some(bucketing) = &self.bucketing {
+            samples = bucketing.aggregate(&samples);
+        }
+        if self.reverse {
+            samples.reverse();
+        }
+        if let Some(count) = self.count {
+            samples.truncate(count);
+        }
+
+        let samples = samples
+            .into_iter()
+            .map(|(timestamp, value)| {
+                resp_serializer::to_resp_nested_array(vec![
+                    resp_serializer::to_resp_integer(timestamp as i64),
+                    resp_serializer::to_resp_bulk(command_utils::format_float(value)),
+                ])
+            })
+            .collect();
+        resp_serializer::to_resp_nested_array(samples)
+    }
+}
+
+// A `label=value` or `label!=value` matcher, where the value may be a
+// `(a,b)` list and an empty value stands for a missing label.
+struct LabelFilter {
+    label: String,
+    values: Vec<String>,
+    negated: bool,
+}
+
+impl LabelFilter {
+    fn parse(filter: &str) -> Option<LabelFilter> {
+        let (label, value, negated) = match filter.split_once("!=") {
+            Some((label, value)) => (label, value, true),
+            None => filter
+                .split_once('=')
+                .map(|(label, value)| (label, value, false))?,
+        };
+        if label.is_empty() {
+            return None;
+        }
+
+        let values = match value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+            Some(list) => list.split(',').map(|value| value.to_owned()).collect(),
+            None => vec![value.to_owned()],
+        };
+        Some(LabelFilter {
+            label: label.to_owned(),
+            values,
+            negated,
+        })
+    }
+
+    fn matches(&self, series: &TimeSeries) -> bool {
+        let value = series.label(&self.label).unwrap_or_default();
+        self.values.iter().any(|expected| expected == value) != self.negated
+    }
+
+    // Only filters requiring a label value narrow the keyspace.
+    fn is_positive(&self) -> bool {
+        !self.negated && self.values.iter().all(|value| !value.is_empty())
+    }
+}
+
+pub async fn execute_ts_range(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    range(app, token, false).await
+}
+
+pub async fn execute_ts_revrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    range(app, token, true).await
+}
+
+async fn range(app: Arc<RedisApp>, token: &RespTk, reverse: bool) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, from, to, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let query = match parse_query(from, to, options, reverse) {
+        Ok((query, [])) => query,
+        Ok(_) => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<TimeSeries>(&mem, key) {
+        Ok(Some(series)) => query.samples(series).into(),
+        Ok(None) => {
+            resp_serializer::to_err_string("ERR TSDB: the key does not exist".into()).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub async fn execute_ts_mrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [from, to, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (query, mut options) = match parse_query(from, to, options, false) {
+        Ok(parsed) => parsed,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let with_labels = match options {
+        [option, rest @ ..] if option.eq_ignore_ascii_case("WITHLABELS") => {
+            options = rest;
+            true
+        }
+        _ => false,
+    };
+    let filters = match options {
+        [option, filters @ ..] if option.eq_ignore_ascii_case("FILTER") => filters,
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let Some(filters) = filters
+        .iter()
+        .map(|filter| LabelFilter::parse(filter))
+        .collect::<Option<Vec<LabelFilter>>>()
+    else {
+        return resp_serializer::to_err_string("ERR TSDB: failed parsing labels".into()).into();
+    };
+    if !filters.iter().any(|filter| filter.is_positive()) {
+        return resp_serializer::to_err_string(
+            "ERR TSDB: please provide at least one matcher".into(),
+        )
+        .into();
+    }
+
+    let mem = app.memory.lock().await;
+    let mut matches: Vec<(&String, &TimeSeries)> = mem
+        .iter()
+        .filter(|(_, entry)| !entry.is_expired())
+        .filter_map(|(key, entry)| match &entry.value {
+            ValueContainer::TimeSeries(series) => Some((key, series)),
+            _ => None,
+        })
+        .filter(|(_, series)| filters.iter().all(|filter| filter.matches(series)))
+        .collect();
+    matches.sort_by_key(|(key, _)| *key);
+
+    let replies = matches
+        .into_iter()
+        .map(|(key, series)| {
+            let labels = match with_labels {
+                true => series
+                    .labels
+                    .iter()
+                    .map(|(label, value)| {
+                        resp_serializer::to_resp_array(vec![label.to_owned(), value.to_owned()])
+                    })
+                    .collect(),
+                false => vec![],
+            };
+            resp_serializer::to_resp_nested_array(vec![
+                resp_serializer::to_resp_bulk(key.to_owned()),
+                resp_serializer::to_resp_nested_array(labels),
+                query.samples(series),
+            ])
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(replies).into()
+}
+
+// Parses the range bounds and the COUNT and AGGREGATION options, returning
+// whatever options follow them.
+fn parse_query<'a>(
+    from: &str,
+    to: &str,
+    mut options: &'a [String],
+    reverse: bool,
+) -> Result<(RangeQuery, &'a [String]), String> {
+    let from = match from {
+        "-" => 0,
+        from => parse_timestamp(from).ok_or("ERR TSDB: wrong fromTimestamp")?,
+    };
+    let to = match to {
+        "+" => u64::MAX,
+        to => parse_timestamp(to).ok_or("ERR TSDB: wrong toTimestamp")?,
+    };
+
+    let mut query = RangeQuery {
+        from,
+        to,
+        count: None,
+        bucketing: None,
+        reverse,
+    };
+    loop {
+        match options {
+            [option, count, rest @ ..] if option.eq_ignore_ascii_case("COUNT") => {
+                let count = command_utils::parse_integer(count)
+                    .filter(|count| *count >= 0)
+                    .ok_or("ERR TSDB: Couldn't parse COUNT")?;
+                query.count = Some(count as usize);
+                options = rest;
+            }
+            [option, aggregation, duration, rest @ ..]
+                if option.eq_ignore_ascii_case("AGGREGATION") =>
+            {
+                query.bucketing = Some(parse_bucketing(aggregation, duration, 0)?);
+                options = rest;
+            }
+            _ => return Ok((query, options)),
+        }
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<u64> {
+    command_utils::parse_integer(value)
+        .filter(|timestamp| *timestamp >= 0)
+        .map(|timestamp| timestamp as u64)
+}
+
+// Parses the `AGGREGATION aggregator bucketDuration` arguments shared by
+// TS.RANGE and TS.CREATERULE.
+pub fn parse_bucketing(aggregation: &str, duration: &str, align: u64) -> Result<Bucketing, String> {
+    let aggregation =
+        Aggregation::parse(aggregation).ok_or("ERR TSDB: Unknown aggregation type")?;
+    let duration = command_utils::parse_integer(duration)
+        .filter(|duration| *duration > 0)
+        .ok_or("ERR TSDB: bucketDuration must be greater than zero")?;
+
+    Ok(Bucketing {
+        aggregation,
+        duration: duration as u64,
+        align,
+    })
+}
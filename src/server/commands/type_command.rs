@@ -20,7 +20,23 @@ pub async fn execute_type(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
         let resp = match value {
             ValueContainer::Stream(..) => resp_serializer::to_resp_string("stream".to_owned()),
             ValueContainer::String(_) => resp_serializer::to_resp_string("string".to_owned()),
-            ValueContainer::Array(..) => resp_serializer::to_resp_string("list".to_owned()),
+            ValueContainer::Array(..) | ValueContainer::List(..) => {
+                resp_serializer::to_resp_string("list".to_owned())
+            }
+            ValueContainer::Hash(_) => resp_serializer::to_resp_string("hash".to_owned()),
+            ValueContainer::Set(_) => resp_serializer::to_resp_string("set".to_owned()),
+            ValueContainer::SortedSet(_) => resp_serializer::to_resp_string("zset".to_owned()),
+            ValueContainer::Json(_) => resp_serializer::to_resp_string("ReJSON-RL".to_owned()),
+            ValueContainer::BloomFilter(_) => {
+                resp_serializer::to_resp_string("MBbloom--".to_owned())
+            }
+            ValueContainer::CuckooFilter(_) => {
+                resp_serializer::to_resp_string("MBbloomCF".to_owned())
+            }
+            ValueContainer::TimeSeries(_) => {
+                resp_serializer::to_resp_string("TSDB-TYPE".to_owned())
+            }
+            ValueContainer::VectorSet(_) => resp_serializer::to_resp_string("vectorset".to_owned()),
             ValueContainer::Integer(_) => resp_serializer::to_resp_string("integer".to_owned()),
             ValueContainer::Boolean(_) => resp_serializer::to_resp_string("boolean".to_owned()),
             ValueContainer::Null => resp_serializer::to_resp_string("none".to_owned()),
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        json::JsonValue,
+        vector_set::{self, Metric, Quantization, VectorSet},
+    },
+    utils,
+};
+
+use super::command_utils;
+
+struct AddOptions {
+    metric: Metric,
+    quantization: Quantization,
+    m: usize,
+    ef_construction: usize,
+    attributes: Option<String>,
+    // Whether the options set the metric or quantization explicitly.
+    explicit_metric: bool,
+    explicit_quantization: bool,
+}
+
+impl AddOptions {
+    fn parse(mut options: &[String]) -> Result<Self, String> {
+        let mut parsed = AddOptions {
+            metric: Metric::Cosine,
+            quantization: Quantization::NoQuant,
+            m: vector_set::DEFAULT_M,
+            ef_construction: vector_set::DEFAULT_EF_CONSTRUCTION,
+            attributes: None,
+            explicit_metric: false,
+            explicit_quantization: false,
+        };
+
+        while let [option, rest @ ..] = options {
+            options = rest;
+            match (option.to_uppercase().as_str(), options) {
+                // Insertions are atomic already, so CAS has nothing to change.
+                ("CAS", _) => {}
+                ("NOQUANT", _) => {
+                    parsed.quantization = Quantization::NoQuant;
+                    parsed.explicit_quantization = true;
+                }
+                ("Q8", _) => {
+                    parsed.quantization = Quantization::Int8;
+                    parsed.explicit_quantization = true;
+                }
+                ("METRIC", [metric, rest @ ..]) => {
+                    parsed.metric = Metric::parse(metric).ok_or("ERR unknown METRIC")?;
+                    parsed.explicit_metric = true;
+                    options = rest;
+                }
+                ("EF", [value, rest @ ..]) => {
+                    parsed.ef_construction = parse_positive(value, "ERR invalid EF")?;
+                    options = rest;
+                }
+                ("M", [value, rest @ ..]) => {
+                    parsed.m = parse_positive(value, "ERR invalid M")?;
+                    options = rest;
+                }
+                ("SETATTR", [attributes, rest @ ..]) => {
+                    if JsonValue::parse(attributes).is_err() {
+                        return Err("ERR invalid JSON attributes".into());
+                    }
+                    parsed.attributes = Some(attributes.to_owned());
+                    options = rest;
+                }
+                _ => return Err("ERR syntax error".into()),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn parse_positive(value: &str, error: &str) -> Result<usize, String> {
+    command_utils::parse_integer(value)
+        .filter(|value| *value > 0)
+        .map(|value| value as usize)
+        .ok_or_else(|| error.to_owned())
+}
+
+pub async fn execute_vadd(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, vector @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let (vector, [element, options @ ..]) = (match parse_vector(vector) {
+        Ok(parsed) => parsed,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    }) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let options = match AddOptions::parse(options) {
+        Ok(options) => options,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let vset = match command_utils::get_or_create_value(&mut mem, key, || {
+        VectorSet::new(
+            options.metric,
+            options.quantization,
+            vector.len(),
+            options.m,
+            options.ef_construction,
+        )
+    }) {
+        Ok(vset) => vset,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    if vector.len() != vset.dim() {
+        return resp_serializer::to_err_string(vector_dimension_error(vector.len(), vset.dim()))
+            .into();
+    }
+    if options.explicit_quantization && options.quantization != vset.quantization() {
+        return resp_serializer::to_err_string(
+            "ERR asked quantization mismatch with existing vector set".into(),
+        )
+        .into();
+    }
+    if options.explicit_metric && options.metric != vset.metric() {
+        return resp_serializer::to_err_string(
+            "ERR asked metric mismatch with existing vector set".into(),
+        )
+        .into();
+    }
+
+    let added = vset.add(element, vector, options.attributes);
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_integer(added as i64).into()
+}
+
+// Reads a vector given as `VALUES count v1 .. vN`, or as `FP32 blob` with
+// little endian floats, returning it with the arguments that follow.
+pub fn parse_vector(args: &[String]) -> Result<(Vec<f32>, &[String]), &'static str> {
+    const INVALID_VECTOR: &str = "ERR invalid vector specification";
+
+    match args {
+        [kind, blob, rest @ ..] if kind.eq_ignore_ascii_case("FP32") => {
+            let bytes = utils::string_to_bytes(blob);
+            if bytes.is_empty() || !bytes.len().is_multiple_of(4) {
+                return Err(INVALID_VECTOR);
+            }
+            let vector = bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect();
+            Ok((vector, rest))
+        }
+        [kind, count, rest @ ..] if kind.eq_ignore_ascii_case("VALUES") => {
+            let count = command_utils::parse_integer(count)
+                .filter(|count| *count > 0 && *count as usize <= rest.len())
+                .ok_or(INVALID_VECTOR)? as usize;
+            let vector = rest[..count]
+                .iter()
+                .map(|value| command_utils::parse_float(value).filter(|value| value.is_finite()))
+                .map(|value| value.map(|value| value as f32))
+                .collect::<Option<Vec<f32>>>()
+                .ok_or(INVALID_VECTOR)?;
+            Ok((vector, &rest[count..]))
+        }
+        _ => Err(INVALID_VECTOR),
+    }
+}
+
+pub fn vector_dimension_error(got: usize, expected: usize) -> String {
+    format!(
+        "ERR Vector dimension mismatch - got {} but set has {}",
+        got, expected
+    )
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, vector_set::VectorSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_vdim(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<VectorSet>(&mem, key) {
+        Ok(Some(vset)) => resp_serializer::to_resp_integer(vset.dim() as i64),
+        Ok(None) => resp_serializer::to_err_string("ERR key does not exist".into()),
+        Err(err) => resp_serializer::to_err_string(err.into()),
+    }
+    .into()
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, vector_set::VectorSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_vgetattr(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, element] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<VectorSet>(&mem, key) {
+        Ok(vset) => match vset.and_then(|vset| vset.attributes(element)) {
+            Some(attributes) => resp_serializer::to_resp_bulk(attributes.to_owned()),
+            None => resp_serializer::null_resp_string(),
+        },
+        Err(err) => resp_serializer::to_err_string(err.into()),
+    }
+    .into()
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, vector_set::VectorSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_vrem(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, element] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let removed = match command_utils::get_value_mut::<VectorSet>(&mut mem, key) {
+        Ok(Some(vset)) => vset.remove(element),
+        Ok(None) => false,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    if removed {
+        command_utils::remove_if_empty(&mut mem, key);
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(removed as i64).into()
+}
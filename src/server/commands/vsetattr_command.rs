@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, json::JsonValue, vector_set::VectorSet},
+};
+
+use super::command_utils;
+
+// An empty string removes the attributes of the element.
+pub async fn execute_vsetattr(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, element, attributes] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let attributes = match attributes.is_empty() {
+        true => None,
+        false if JsonValue::parse(attributes).is_err() => {
+            return resp_serializer::to_err_string("ERR invalid JSON attributes".into()).into()
+        }
+        false => Some(attributes.to_owned()),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let updated = match command_utils::get_value_mut::<VectorSet>(&mut mem, key) {
+        Ok(Some(vset)) => vset.set_attributes(element, attributes),
+        Ok(None) => false,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    if updated {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(updated as i64).into()
+}
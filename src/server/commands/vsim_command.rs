@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, vector_set, vector_set::VectorSet},
+};
+
+use super::{command_utils, vadd_command};
+
+const DEFAULT_COUNT: usize = 10;
+
+// The query is either another element of the set or a vector.
+enum Query {
+    Element(String),
+    Vector(Vec<f32>),
+}
+
+pub async fn execute_vsim(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, query, options) = match args.as_slice() {
+        [key, kind, element, options @ ..] if kind.eq_ignore_ascii_case("ELE") => {
+            (key, Query::Element(element.to_owned()), options)
+        }
+        [key, vector @ ..] if !vector.is_empty() => match vadd_command::parse_vector(vector) {
+            Ok((vector, options)) => (key, Query::Vector(vector), options),
+            Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+        },
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let mut with_scores = false;
+    let mut count = DEFAULT_COUNT;
+    let mut ef = vector_set::DEFAULT_EF_SEARCH;
+    let mut options = options;
+    while let [option, rest @ ..] = options {
+        options = rest;
+        match (option.to_uppercase().as_str(), options) {
+            ("WITHSCORES", _) => with_scores = true,
+            ("COUNT", [value, rest @ ..]) | ("EF", [value, rest @ ..]) => {
+                let Some(value) = command_utils::parse_integer(value).filter(|value| *value > 0)
+                else {
+                    return resp_serializer::to_err_string(format!(
+                        "ERR invalid {}",
+                        option.to_uppercase()
+                    ))
+                    .into();
+                };
+                match option.eq_ignore_ascii_case("COUNT") {
+                    true => count = value as usize,
+                    false => ef = value as usize,
+                }
+                options = rest;
+            }
+            _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        }
+    }
+
+    let mem = app.memory.lock().await;
+    let vset = match command_utils::get_value::<VectorSet>(&mem, key) {
+        Ok(Some(vset)) => vset,
+        Ok(None) => return resp_serializer::to_resp_array(vec![]).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let query = match query {
+        Query::Element(element) => match vset.vector(&element) {
+            Some(vector) => vector,
+            None => {
+                return resp_serializer::to_err_string("ERR element not found in set".into()).into()
+            }
+        },
+        Query::Vector(vector) if vector.len() != vset.dim() => {
+            return resp_serializer::to_err_string(vadd_command::vector_dimension_error(
+                vector.len(),
+                vset.dim(),
+            ))
+            .into();
+        }
+        Query::Vector(vector) => vector,
+    };
+
+    let mut items = vec![];
+    for (element, distance) in vset.search(&query, count, ef) {
+        items.push(element.to_owned());
+        if with_scores {
+            items.push(command_utils::format_float(vset.metric().score(distance)));
+        }
+    }
+    resp_serializer::to_resp_array(items).into()
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, stream::RedisStream, stream_key::StreamKey},
+};
+
+use super::command_utils;
+
+pub async fn execute_xack(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, group, ids @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if ids.is_empty() {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let ids = match ids
+        .iter()
+        .map(|id| StreamKey::parse(id, 0))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(ids) => ids,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let group = match command_utils::get_value_mut::<RedisStream>(&mut mem, key) {
+        Ok(stream) => stream.and_then(|stream| stream.groups.get_mut(group)),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let acked = match group {
+        Some(group) => ids
+            .iter()
+            .filter(|id| group.pending.remove(id).is_some())
+            .count(),
+        None => 0,
+    };
+    if acked > 0 {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(acked as i64).into()
+}
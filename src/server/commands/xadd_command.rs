@@ -5,29 +5,70 @@ use crate::{
     resp_desserializer::RespTk,
     server::redis_app::RedisApp,
     types::{
-        entry_value::EntryValue, execution_response::ExecResponse, stream_entry::StreamEntry,
-        stream_key::StreamKey, value_container::ValueContainer,
+        execution_response::ExecResponse,
+        stream::RedisStream,
+        stream_entry::StreamEntry,
+        stream_key::{self, StreamKey},
     },
 };
 
+use super::{
+    command_utils,
+    xtrim_command::{self, TrimOptions},
+};
+
 pub async fn execute_xadd(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
-    let mut args = token.get_command_args();
-    if let (Some(stream_id), Some(entry_id)) = (
-        args.next().and_then(|t| t.get_content_string()),
-        args.next().and_then(|t| t.get_content_string()),
-    ) {
-        let mut fields = Vec::new();
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [stream_id, rest @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let mut rest = rest;
 
-        while let (Some(key), Some(value)) = (
-            args.next().and_then(|t| t.get_content_string()),
-            args.next().and_then(|t| t.get_content_string()),
-        ) {
-            fields.push((key, value));
+    // NOMKSTREAM and the trimming options come before the entry id.
+    let mut nomkstream = false;
+    let mut trim = None;
+    loop {
+        match rest {
+            [option, tail @ ..] if option.eq_ignore_ascii_case("NOMKSTREAM") => {
+                nomkstream = true;
+                rest = tail;
+            }
+            _ => match xtrim_command::parse_trim(rest) {
+                Ok(Some((options, tail))) => {
+                    trim = Some(options);
+                    rest = tail;
+                }
+                Ok(None) => break,
+                Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+            },
         }
+    }
 
-        return execute(token, app, stream_id, entry_id, fields).await;
+    let [entry_id, fields @ ..] = rest else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if fields.is_empty() || !fields.len().is_multiple_of(2) {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
     }
-    resp_serializer::null_resp_string().into()
+    let fields = fields
+        .chunks(2)
+        .map(|pair| (pair[0].to_owned(), pair[1].to_owned()))
+        .collect();
+
+    execute(
+        token,
+        app,
+        stream_id.to_owned(),
+        entry_id.to_owned(),
+        fields,
+        nomkstream,
+        trim,
+    )
+    .await
 }
 
 async fn execute(
@@ -36,24 +77,32 @@ async fn execute(
     stream_id: String,
     entry_id: String,
     fields: Vec<(String, String)>,
+    nomkstream: bool,
+    trim: Option<TrimOptions>,
 ) -> ExecResponse {
-    if entry_id == "0-0" {
+    let mut mem = app.memory.lock().await;
+    match command_utils::get_value::<RedisStream>(&mem, &stream_id) {
+        Ok(None) if nomkstream => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+        _ => {}
+    }
+
+    let last_key = app.get_last_stream_key(&stream_id, &mem);
+    // `$`, `-` and `+` only name ids of an existing stream in reads.
+    let stream_key = match entry_id.as_str() {
+        "$" | "-" | "+" => None,
+        id => StreamKey::from_string(&id.to_owned(), &last_key, Some(0)).ok(),
+    };
+    let Some(stream_key) = stream_key else {
+        return resp_serializer::to_err_string(stream_key::INVALID_ID_ERROR.into()).into();
+    };
+    if stream_key.miliseconds_time == 0 && stream_key.sequence_number == 0 {
         return resp_serializer::to_err_string(
             "ERR The ID specified in XADD must be greater than 0-0".to_owned(),
         )
         .into();
     }
 
-    let mut mem = app.memory.lock().await;
-    let last_key = app.get_last_stream_key(&stream_id, &mem);
-    let stream_key_result = StreamKey::from_string(&entry_id.to_owned(), &last_key, None);
-
-    if stream_key_result.is_err() {
-        return resp_serializer::to_err_string("INVALID_COMMAND".into()).into();
-    }
-
-    let stream_key = stream_key_result.unwrap();
-
     if let Some(last) = last_key {
         if stream_key <= last {
             return resp_serializer::to_err_string(String::from(
@@ -68,21 +117,16 @@ async fn execute(
         fields: fields.to_owned(),
     };
 
-    if let Some(entry) = mem.get_mut(&stream_id) {
-        if let ValueContainer::Stream(ref mut stream) = entry.value {
-            stream.push(new_entry);
-            app.buffer_command(token).await;
-            return resp_serializer::to_resp_bulk(stream_key.into()).into();
-        }
+    let stream = match command_utils::get_or_create_value(&mut mem, &stream_id, RedisStream::new) {
+        Ok(stream) => stream,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+    stream.push(new_entry);
+    if let Some(trim) = trim {
+        stream.trim(&trim.strategy, trim.limit);
     }
 
-    mem.insert(
-        stream_id.to_owned(),
-        EntryValue {
-            expires_at: None,
-            value: ValueContainer::Stream(vec![new_entry]),
-        },
-    );
     app.buffer_command(token).await;
+    app.signal_key_ready(&mut mem, &stream_id).await;
     resp_serializer::to_resp_bulk(stream_key.into()).into()
 }
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        stream::{self, RedisStream},
+        stream_key::StreamKey,
+    },
+    utils,
+};
+
+use super::{
+    command_utils,
+    xclaim_command::{self, Claim, ClaimOptions},
+};
+
+const DEFAULT_COUNT: usize = 100;
+// Pending entries looked at per requested entry before giving up for this call.
+const ATTEMPTS_FACTOR: usize = 10;
+
+pub async fn execute_xautoclaim(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, group, consumer, min_idle, start, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let parsed = xclaim_command::parse_min_idle(min_idle)
+        .and_then(|min_idle| Ok((min_idle, StreamKey::parse(start, 0)?)));
+    let (min_idle, start) = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut count = DEFAULT_COUNT;
+    let mut justid = false;
+    let mut options = options;
+    while let [option, rest @ ..] = options {
+        options = rest;
+        match (option.to_uppercase().as_str(), options) {
+            ("JUSTID", _) => justid = true,
+            ("COUNT", [value, rest @ ..]) => {
+                match command_utils::parse_integer(value).filter(|count| *count > 0) {
+                    Some(value) => count = value as usize,
+                    None => {
+                        return resp_serializer::to_err_string("ERR COUNT must be > 0".into())
+                            .into()
+                    }
+                }
+                options = rest;
+            }
+            _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        }
+    }
+
+    let mut mem = app.memory.lock().await;
+    let stream = match command_utils::get_value_mut::<RedisStream>(&mut mem, key) {
+        Ok(stream) => stream.filter(|stream| stream.groups.contains_key(group.as_str())),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+    let Some(stream) = stream else {
+        return resp_serializer::to_err_string(stream::no_group_error(key, group)).into();
+    };
+
+    let now = utils::get_current_time_ms();
+    let claim_options = ClaimOptions {
+        group,
+        consumer,
+        min_idle,
+        delivered_at: now,
+        retry_count: None,
+        force: false,
+        justid,
+    };
+    let mut pending: Vec<StreamKey> = vec![];
+    if let Some(group) = stream.groups.get_mut(group.as_str()) {
+        group.touch_consumer(consumer, now);
+        pending = group
+            .pending
+            .range(start..)
+            .take(count * ATTEMPTS_FACTOR + 1)
+            .map(|(id, _)| *id)
+            .collect();
+    }
+
+    let mut claimed = vec![];
+    let mut deleted = vec![];
+    let mut next = StreamKey::new(0, 0);
+    for (scanned, id) in pending.iter().enumerate() {
+        if claimed.len() == count || scanned == count * ATTEMPTS_FACTOR {
+            next = *id;
+            break;
+        }
+        match xclaim_command::claim(stream, *id, &claim_options, now) {
+            Claim::Claimed(reply) => claimed.push(reply),
+            Claim::Deleted => deleted.push((*id).into()),
+            Claim::Skipped => {}
+        }
+    }
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_bulk(next.into()),
+        resp_serializer::to_resp_nested_array(claimed),
+        resp_serializer::to_resp_array(deleted),
+    ])
+    .into()
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer::{self, RespSerializer},
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        stream::{self, RedisStream},
+        stream_key::{self, StreamKey},
+    },
+    utils,
+};
+
+use super::command_utils;
+
+pub enum Claim {
+    // The reply for the claimed entry, its id alone with JUSTID.
+    Claimed(String),
+    // The entry left the stream, so it was dropped from the pending list.
+    Deleted,
+    Skipped,
+}
+
+pub struct ClaimOptions<'a> {
+    pub group: &'a str,
+    pub consumer: &'a str,
+    pub min_idle: u128,
+    pub delivered_at: u128,
+    pub retry_count: Option<u64>,
+    pub force: bool,
+    pub justid: bool,
+}
+
+// Moves a pending entry idle for at least `min_idle` to the consumer. JUSTID
+// leaves the delivery count alone, RETRYCOUNT sets it.
+pub fn claim(stream: &mut RedisStream, id: StreamKey, options: &ClaimOptions, now: u128) -> Claim {
+    let entry = stream.get(&id).map(|entry| entry.to_resp());
+    let Some(group) = stream.groups.get_mut(options.group) else {
+        return Claim::Skipped;
+    };
+
+    if options.force && entry.is_some() && !group.pending.contains_key(&id) {
+        group.deliver(options.consumer, id, options.delivered_at);
+        if let Some(pending) = group.pending.get_mut(&id) {
+            pending.delivery_count = 0;
+        }
+    }
+    let Some(pending) = group.pending.get_mut(&id) else {
+        return Claim::Skipped;
+    };
+    let Some(entry) = entry else {
+        group.pending.remove(&id);
+        return Claim::Deleted;
+    };
+    if now.saturating_sub(pending.delivered_at) < options.min_idle {
+        return Claim::Skipped;
+    }
+
+    pending.consumer = options.consumer.to_owned();
+    pending.delivered_at = options.delivered_at;
+    match options.retry_count {
+        Some(retry_count) => pending.delivery_count = retry_count,
+        None if !options.justid => pending.delivery_count += 1,
+        None => {}
+    }
+
+    match options.justid {
+        true => Claim::Claimed(resp_serializer::to_resp_bulk(id.into())),
+        false => Claim::Claimed(entry),
+    }
+}
+
+pub fn parse_min_idle(value: &str) -> Result<u128, &'static str> {
+    command_utils::parse_integer(value)
+        .map(|idle| idle.max(0) as u128)
+        .ok_or("ERR Invalid min-idle-time argument for XCLAIM")
+}
+
+pub async fn execute_xclaim(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, group, consumer, min_idle, rest @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let min_idle = match parse_min_idle(min_idle) {
+        Ok(min_idle) => min_idle,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    // Ids run until the first argument that is not one.
+    let id_count = rest
+        .iter()
+        .take_while(|id| StreamKey::parse(id, 0).is_ok())
+        .count();
+    if id_count == 0 {
+        return resp_serializer::to_err_string(stream_key::INVALID_ID_ERROR.into()).into();
+    }
+    let (ids, mut options) = rest.split_at(id_count);
+    let ids: Vec<StreamKey> = ids
+        .iter()
+        .filter_map(|id| StreamKey::parse(id, 0).ok())
+        .collect();
+
+    let now = utils::get_current_time_ms();
+    let mut claim_options = ClaimOptions {
+        group,
+        consumer,
+        min_idle,
+        delivered_at: now,
+        retry_count: None,
+        force: false,
+        justid: false,
+    };
+    let mut last_id = None;
+    while let [option, rest @ ..] = options {
+        options = rest;
+        let parsed = match (option.to_uppercase().as_str(), options) {
+            ("FORCE", _) => {
+                claim_options.force = true;
+                Ok(())
+            }
+            ("JUSTID", _) => {
+                claim_options.justid = true;
+                Ok(())
+            }
+            ("IDLE", [value, rest @ ..]) => {
+                options = rest;
+                command_utils::parse_integer(value)
+                    .map(|idle| {
+                        claim_options.delivered_at = now.saturating_sub(idle.max(0) as u128)
+                    })
+                    .ok_or("ERR Invalid IDLE option argument for XCLAIM")
+            }
+            ("TIME", [value, rest @ ..]) => {
+                options = rest;
+                command_utils::parse_integer(value)
+                    .map(|time| claim_options.delivered_at = time.max(0) as u128)
+                    .ok_or("ERR Invalid TIME option argument for XCLAIM")
+            }
+            ("RETRYCOUNT", [value, rest @ ..]) => {
+                options = rest;
+                command_utils::parse_integer(value)
+                    .filter(|count| *count >= 0)
+                    .map(|count| claim_options.retry_count = Some(count as u64))
+                    .ok_or("ERR Invalid RETRYCOUNT option argument for XCLAIM")
+            }
+            ("LASTID", [value, rest @ ..]) => {
+                options = rest;
+                StreamKey::parse(value, 0).map(|id| last_id = Some(id))
+            }
+            _ => Err("ERR syntax error"),
+        };
+        if let Err(err) = parsed {
+            return resp_serializer::to_err_string(err.into()).into();
+        }
+    }
+
+    let mut mem = app.memory.lock().await;
+    let stream = match command_utils::get_value_mut::<RedisStream>(&mut mem, key) {
+        Ok(stream) => stream.filter(|stream| stream.groups.contains_key(group.as_str())),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+    let Some(stream) = stream else {
+        return resp_serializer::to_err_string(stream::no_group_error(key, group)).into();
+    };
+
+    if let Some(group) = stream.groups.get_mut(group.as_str()) {
+        group.touch_consumer(consumer, now);
+        if let Some(last_id) = last_id.filter(|id| *id > group.last_delivered) {
+            group.last_delivered = last_id;
+        }
+    }
+
+    let claimed = ids
+        .into_iter()
+        .filter_map(|id| match claim(stream, id, &claim_options, now) {
+            Claim::Claimed(reply) => Some(reply),
+            Claim::Deleted | Claim::Skipped => None,
+        })
+        .collect();
+
+    app.buffer_command(token).await;
+    resp_serializer::to_resp_nested_array(claimed).into()
+}
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        entry_value::EntryValue,
+        execution_response::ExecResponse,
+        stream::{ConsumerGroup, RedisStream},
+        stream_key::StreamKey,
+        value_container::ValueContainer,
+    },
+    utils,
+};
+
+use super::command_utils;
+
+const MISSING_KEY_ERROR: &str = "ERR The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically.";
+
+pub async fn execute_xgroup(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [subcommand, key, group, rest @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let subcommand = subcommand.to_uppercase();
+
+    let mut mem = app.memory.lock().await;
+    let mkstream = subcommand == "CREATE"
+        && rest
+            .iter()
+            .skip(1)
+            .any(|option| option.eq_ignore_ascii_case("MKSTREAM"));
+    let created = mkstream && mem.get(key).is_none_or(|entry| entry.is_expired());
+    if created {
+        mem.insert(
+            key.to_owned(),
+            EntryValue {
+                value: ValueContainer::Stream(RedisStream::new()),
+                expires_at: None,
+            },
+        );
+    }
+
+    let stream = match command_utils::get_value_mut::<RedisStream>(&mut mem, key) {
+        Ok(Some(stream)) => stream,
+        Ok(None) => return resp_serializer::to_err_string(MISSING_KEY_ERROR.into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let result = match (subcommand.as_str(), rest) {
+        ("CREATE", [id, options @ ..]) => create(stream, group, id, options),
+        ("SETID", [id, options @ ..]) => set_id(stream, key, group, id, options),
+        ("DESTROY", []) => Ok(resp_serializer::to_resp_integer(
+            stream.groups.remove(group).is_some() as i64,
+        )),
+        ("CREATECONSUMER", [consumer]) => match stream.groups.get_mut(group) {
+            Some(group) => {
+                let created = group.touch_consumer(consumer, utils::get_current_time_ms());
+                Ok(resp_serializer::to_resp_integer(created as i64))
+            }
+            None => Err(missing_group_error(key, group)),
+        },
+        ("DELCONSUMER", [consumer]) => match stream.groups.get_mut(group) {
+            Some(group) => Ok(resp_serializer::to_resp_integer(
+                group.remove_consumer(consumer).unwrap_or(0) as i64,
+            )),
+            None => Err(missing_group_error(key, group)),
+        },
+        ("CREATE" | "SETID" | "DESTROY" | "CREATECONSUMER" | "DELCONSUMER", _) => {
+            Err(command_utils::arity_error(token))
+        }
+        _ => Err(format!(
+            "ERR unknown subcommand '{}'. Try XGROUP HELP.",
+            subcommand
+        )),
+    };
+
+    match result {
+        Ok(response) => {
+            app.buffer_command(token).await;
+            response.into()
+        }
+        Err(err) => {
+            if created {
+                mem.remove(key);
+            }
+            resp_serializer::to_err_string(err).into()
+        }
+    }
+}
+
+fn create(
+    stream: &mut RedisStream,
+    group: &str,
+    id: &str,
+    options: &[String],
+) -> Result<String, String> {
+    let last_delivered = parse_group_id(stream, id, options, true)?;
+    if stream.groups.contains_key(group) {
+        return Err("BUSYGROUP Consumer Group name already exists".into());
+    }
+    stream
+        .groups
+        .insert(group.to_owned(), ConsumerGroup::new(last_delivered));
+    Ok(resp_serializer::to_resp_string("OK".into()))
+}
+
+fn set_id(
+    stream: &mut RedisStream,
+    key: &str,
+    group: &str,
+    id: &str,
+    options: &[String],
+) -> Result<String, String> {
+    let last_delivered = parse_group_id(stream, id, options, false)?;
+    match stream.groups.get_mut(group) {
+        Some(group) => {
+            group.last_delivered = last_delivered;
+            Ok(resp_serializer::to_resp_string("OK".into()))
+        }
+        None => Err(missing_group_error(key, group)),
+    }
+}
+
+// `$` stands for the last id of the stream. ENTRIESREAD is accepted for
+// compatibility, lag is not tracked.
+fn parse_group_id(
+    stream: &RedisStream,
+    id: &str,
+    mut options: &[String],
+    allow_mkstream: bool,
+) -> Result<StreamKey, String> {
+    while let [option, rest @ ..] = options {
+        options = rest;
+        match (option.to_uppercase().as_str(), options) {
+            ("MKSTREAM", _) if allow_mkstream => {}
+            ("ENTRIESREAD", [value, rest @ ..]) => {
+                command_utils::parse_integer(value)
+                    .filter(|value| *value >= -1)
+                    .ok_or("ERR value for ENTRIESREAD must be positive or -1")?;
+                options = rest;
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+
+    match id {
+        "$" => Ok(stream.last_id().unwrap_or(StreamKey::new(0, 0))),
+        id => Ok(StreamKey::parse(id, 0)?),
+    }
+}
+
+fn missing_group_error(key: &str, group: &str) -> String {
+    format!(
+        "NOGROUP No such consumer group '{}' for key name '{}'",
+        group, key
+    )
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, stream::RedisStream},
+};
+
+use super::command_utils;
+
+pub async fn execute_xlen(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<RedisStream>(&mem, key) {
+        Ok(stream) => {
+            resp_serializer::to_resp_integer(stream.map_or(0, |stream| stream.len()) as i64)
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()),
+    }
+    .into()
+}
@@ -0,0 +1,141 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        stream::{self, ConsumerGroup, RedisStream},
+        stream_key::StreamKey,
+    },
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_xpending(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, group_name, range @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let (min_idle, range) = match range {
+        [option, idle, range @ ..] if option.eq_ignore_ascii_case("IDLE") => {
+            match command_utils::parse_integer(idle) {
+                Some(idle) => (Some(idle.max(0) as u128), range),
+                None => {
+                    return resp_serializer::to_err_string(
+                        "ERR value is not an integer or out of range".into(),
+                    )
+                    .into()
+                }
+            }
+        }
+        range => (None, range),
+    };
+
+    let extended = match range {
+        [] if min_idle.is_none() => None,
+        [start, end, count, consumer @ ..] if consumer.len() <= 1 => {
+            let parsed = StreamKey::parse_range_bound(start, true).and_then(|start| {
+                let end = StreamKey::parse_range_bound(end, false)?;
+                let count = command_utils::parse_integer(count)
+                    .ok_or("ERR value is not an integer or out of range")?;
+                Ok((start, end, count.max(0) as usize))
+            });
+            match parsed {
+                Ok((start, end, count)) => Some((start, end, count, consumer.first())),
+                Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+            }
+        }
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let group = match command_utils::get_value::<RedisStream>(&mem, key) {
+        Ok(stream) => stream.and_then(|stream| stream.groups.get(group_name)),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+    let Some(group) = group else {
+        return resp_serializer::to_err_string(stream::no_group_error(key, group_name)).into();
+    };
+
+    match extended {
+        Some((start, end, count, consumer)) => {
+            pending_entries(group, start, end, count, consumer, min_idle)
+        }
+        None => pending_summary(group),
+    }
+    .into()
+}
+
+// Count, smallest and greatest pending id, and entries pending per consumer.
+fn pending_summary(group: &ConsumerGroup) -> String {
+    let (Some((first, _)), Some((last, _))) = (
+        group.pending.first_key_value(),
+        group.pending.last_key_value(),
+    ) else {
+        return resp_serializer::to_resp_nested_array(vec![
+            resp_serializer::to_resp_integer(0),
+            resp_serializer::null_resp_string(),
+            resp_serializer::null_resp_string(),
+            resp_serializer::null_resp_array(),
+        ]);
+    };
+
+    let mut consumers: BTreeMap<&str, usize> = BTreeMap::new();
+    for pending in group.pending.values() {
+        *consumers.entry(&pending.consumer).or_default() += 1;
+    }
+
+    resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_integer(group.pending.len() as i64),
+        resp_serializer::to_resp_bulk((*first).into()),
+        resp_serializer::to_resp_bulk((*last).into()),
+        resp_serializer::to_resp_nested_array(
+            consumers
+                .into_iter()
+                .map(|(consumer, count)| {
+                    resp_serializer::to_resp_array(vec![consumer.to_owned(), count.to_string()])
+                })
+                .collect(),
+        ),
+    ])
+}
+
+fn pending_entries(
+    group: &ConsumerGroup,
+    start: StreamKey,
+    end: StreamKey,
+    count: usize,
+    consumer: Option<&String>,
+    min_idle: Option<u128>,
+) -> String {
+    if start > end {
+        return resp_serializer::to_resp_array(vec![]);
+    }
+
+    let now = utils::get_current_time_ms();
+    let items = group
+        .pending
+        .range(start..=end)
+        .filter(|(_, pending)| consumer.is_none_or(|consumer| pending.consumer == *consumer))
+        .map(|(id, pending)| (id, pending, now.saturating_sub(pending.delivered_at)))
+        .filter(|(_, _, idle)| min_idle.is_none_or(|min_idle| *idle >= min_idle))
+        .take(count)
+        .map(|(id, pending, idle)| {
+            resp_serializer::to_resp_nested_array(vec![
+                resp_serializer::to_resp_bulk((*id).into()),
+                resp_serializer::to_resp_bulk(pending.consumer.to_owned()),
+                resp_serializer::to_resp_integer(idle as i64),
+                resp_serializer::to_resp_integer(pending.delivery_count as i64),
+            ])
+        })
+        .collect();
+    resp_serializer::to_resp_nested_array(items)
+}
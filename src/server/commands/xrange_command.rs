@@ -1,54 +1,84 @@
 use std::sync::Arc;
 
 use crate::{
-    resp::resp_serializer,
+    resp::resp_serializer::{self, RespSerializer},
     resp_desserializer::RespTk,
     server::redis_app::RedisApp,
-    types::{
-        execution_response::ExecResponse, redis_error::RedisError, stream_key::StreamKey,
-        value_container::ValueContainer,
-    },
+    types::{execution_response::ExecResponse, stream::RedisStream, stream_key::StreamKey},
 };
 
+use super::command_utils;
+
 pub async fn execute_xrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
-    let mut args = token.get_command_args();
-    if let (Some(stream_id), Some(start), Some(end)) = (
-        args.next().and_then(|t| t.get_content_string()),
-        args.next().and_then(|t| t.get_content_string()),
-        args.next().and_then(|t| t.get_content_string()),
-    ) {
-        let mem = app.memory.lock().await;
-        let start_id = StreamKey::from_string(&start, &None, Some(0))
-            .map_err(|_| RedisError::InvalidStreamEntryId(start))
-            .unwrap();
-        let end_id = StreamKey::from_string(&end, &None, Some(u64::MAX))
-            .map_err(|_| RedisError::InvalidStreamEntryId(end))
-            .unwrap();
-
-        if end_id < start_id {
-            return resp_serializer::to_err_string(String::from("ERR Invalid range")).into();
-        }
+    range(app, token, false).await
+}
+
+// Same as XRANGE with the bounds swapped, newest entries first.
+pub async fn execute_xrevrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    range(app, token, true).await
+}
 
-        if let Some(entry_value) = mem.get(&stream_id) {
-            if let ValueContainer::Stream(stream) = &entry_value.value {
-                let idx_start = match stream.binary_search_by(|val| val.id.cmp(&start_id)) {
-                    Ok(idx) => idx,
-                    Err(idx) => idx,
-                };
-
-                let idx_end = match stream.binary_search_by(|val| val.id.cmp(&end_id)) {
-                    Ok(idx) => idx + 1,
-                    Err(idx) => idx,
-                };
-
-                let slice = &stream[idx_start..idx_end];
-                let serialized = resp_serializer::slc_objects_to_resp(slice);
-                return serialized.into();
+async fn range(app: Arc<RedisApp>, token: &RespTk, rev: bool) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, first, second, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let (start, end) = match rev {
+        true => (second, first),
+        false => (first, second),
+    };
+
+    let count = match options {
+        [] => None,
+        [option, count] if option.eq_ignore_ascii_case("COUNT") => {
+            match command_utils::parse_integer(count) {
+                Some(count) => Some(count.max(0) as usize),
+                None => {
+                    return resp_serializer::to_err_string(
+                        "ERR value is not an integer or out of range".into(),
+                    )
+                    .into()
+                }
             }
         }
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let bounds = StreamKey::parse_range_bound(start, true)
+        .and_then(|start| Ok((start, StreamKey::parse_range_bound(end, false)?)));
+    let (start, end) = match bounds {
+        Ok(bounds) => bounds,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let stream = match command_utils::get_value::<RedisStream>(&mem, key) {
+        Ok(Some(stream)) if start <= end => stream.entries(),
+        Ok(_) => return resp_serializer::to_resp_array(vec![]).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let idx_start = stream.partition_point(|entry| entry.id < start);
+    let idx_end = stream.partition_point(|entry| entry.id <= end);
+    let slice = &stream[idx_start..idx_end];
+    let count = count.unwrap_or(usize::MAX);
 
-        return resp_serializer::to_err_string(String::from("ERR The ID specified not exists"))
-            .into();
-    }
-    resp_serializer::null_resp_string().into()
+    let entries = match rev {
+        true => slice
+            .iter()
+            .rev()
+            .take(count)
+            .map(|entry| entry.to_resp())
+            .collect(),
+        false => slice
+            .iter()
+            .take(count)
+            .map(|entry| entry.to_resp())
+            .collect(),
+    };
+    resp_serializer::to_resp_nested_array(entries).into()
 }
@@ -1,79 +1,137 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
-
-use tokio::sync::MutexGuard;
+use std::{sync::Arc, time::Duration};
 
 use crate::{
     resp::resp_serializer,
     resp_desserializer::RespTk,
     server::redis_app::RedisApp,
     types::{
-        entry_value::EntryValue, execution_response::ExecResponse, redis_error::RedisError,
-        stream_key::StreamKey, value_container::ValueContainer,
+        connection_context::ConnectionContext,
+        execution_response::ExecResponse,
+        keyspace::Keyspace,
+        stream::RedisStream,
+        stream_key::StreamKey,
+        waiter_registry::{WaiterHandler, WaiterOutcome},
     },
 };
 
-use super::command_utils::get_next_arg_string;
+use super::command_utils::{self, get_next_arg_string};
 
-pub async fn execute_xread(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
-    let (block_time, stream_keys, stream_ids) = get_parameters(token);
+struct ReadParameters {
+    block_time: Option<u64>,
+    count: Option<usize>,
+    stream_keys: Vec<String>,
+    stream_ids: Vec<String>,
+}
 
-    let ids = calculate_stream_start_ids(&stream_keys, &stream_ids, app.clone())
+pub async fn execute_xread(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    let params = match get_parameters(token) {
+        Ok(params) => params,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let ids = match calculate_stream_start_ids(&params.stream_keys, &params.stream_ids, app.clone())
         .await
-        .unwrap();
-
-    match block_time {
-        Some(block_time) => {
-            if block_time > 0 {
-                tokio::time::sleep(Duration::from_millis(block_time)).await;
-                let mem = app.memory.lock().await;
-                xread_reader(&stream_keys, &ids, &mem).into()
-            } else {
-                loop {
-                    tokio::time::sleep(Duration::from_millis(1000)).await;
-                    let mem = app.memory.lock().await;
-                    let resp = xread_reader(&stream_keys, &ids, &mem);
-                    if resp != resp_serializer::null_resp_string() {
-                        return resp.into();
-                    }
-                }
-            }
-        }
-        None => {
-            let mem = app.memory.lock().await;
-            xread_reader(&stream_keys, &ids, &mem).into()
-        }
+    {
+        Ok(ids) => ids,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    // Every stream with entries already is served at once, blocking or not.
+    let mem = app.memory.lock().await;
+    let replies = match xread_reader(&params.stream_keys, &ids, params.count, &mem) {
+        Ok(replies) => replies,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+    if !replies.is_empty() {
+        return resp_serializer::to_resp_nested_array(replies).into();
+    }
+    let Some(block_time) = params.block_time else {
+        return resp_serializer::null_resp_array().into();
+    };
+    drop(mem);
+
+    // XADD wakes the reader through the waiter registry as soon as one of
+    // the streams has entries past its id.
+    let handler: WaiterHandler = {
+        let stream_keys = params.stream_keys.clone();
+        let count = params.count;
+        Box::new(move |mem: &mut Keyspace, key: &str| {
+            let idx = stream_keys
+                .iter()
+                .position(|stream_key| stream_key == key)?;
+            let reply = read_key(mem, key, &ids[idx], count).ok()??;
+            Some(WaiterOutcome {
+                response: resp_serializer::to_resp_nested_array(vec![reply]),
+                commands: vec![],
+                ready_keys: vec![],
+            })
+        })
+    };
+
+    // BLOCK 0 waits for as long as it takes.
+    let timeout = (block_time > 0).then(|| Duration::from_millis(block_time));
+    match app
+        .serve_or_block(params.stream_keys, timeout, handler, context)
+        .await
+    {
+        Some(response) => response.into(),
+        None => resp_serializer::null_resp_array().into(),
     }
 }
 
-fn get_parameters(token: &RespTk) -> (Option<u64>, Vec<String>, Vec<String>) {
+fn get_parameters(token: &RespTk) -> Result<ReadParameters, String> {
     let mut args = token.get_command_args();
     let mut block_time: Option<u64> = None;
-    let mut stream_names = Vec::new();
+    let mut count = None;
     while let Some(tk_content) = get_next_arg_string(&mut args) {
         match tk_content.to_uppercase().as_str() {
             "BLOCK" => {
-                if let Some(time) = get_next_arg_string(&mut args) {
-                    block_time = u64::from_str_radix(&time, 10).ok();
+                let time = get_next_arg_string(&mut args).ok_or("ERR syntax error")?;
+                let time = command_utils::parse_integer(&time)
+                    .ok_or("ERR timeout is not an integer or out of range")?;
+                if time < 0 {
+                    return Err("ERR timeout is negative".into());
                 }
+                block_time = Some(time as u64);
+            }
+            "COUNT" => {
+                let value = get_next_arg_string(&mut args).ok_or("ERR syntax error")?;
+                let value = command_utils::parse_integer(&value)
+                    .ok_or("ERR value is not an integer or out of range")?;
+                // A count of 0 or less means no limit.
+                count = (value > 0).then_some(value as usize);
             }
             "STREAMS" => {
+                let mut stream_keys = Vec::new();
                 while let Some(stream_name) = get_next_arg_string(&mut args) {
-                    stream_names.push(stream_name);
+                    stream_keys.push(stream_name);
                 }
+                if stream_keys.is_empty() || !stream_keys.len().is_multiple_of(2) {
+                    return Err("ERR Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified.".into());
+                }
+                let stream_ids = stream_keys.split_off(stream_keys.len() / 2);
+                return Ok(ReadParameters {
+                    block_time,
+                    count,
+                    stream_keys,
+                    stream_ids,
+                });
             }
-            _ => continue,
+            _ => return Err("ERR syntax error".into()),
         }
     }
-    let stream_ids = stream_names.split_off(stream_names.len() / 2);
-
-    (block_time, stream_names, stream_ids)
+    Err("ERR syntax error".into())
 }
 
 async fn calculate_stream_start_ids(
     stream_keys: &[String],
     stream_ids: &[String],
     app: Arc<RedisApp>,
-) -> Result<Vec<StreamKey>, RedisError> {
+) -> Result<Vec<StreamKey>, &'static str> {
     let mut ids = Vec::new();
 
     let mem = app.memory.lock().await;
@@ -81,13 +139,9 @@ async fn calculate_stream_start_ids(
     for (key, id) in key_id {
         if id == "$" {
             let last_id = app.get_last_stream_key(key, &mem);
-            let start_id = StreamKey::from_string(&id, &last_id, Some(0))
-                .map_err(|_| RedisError::InvalidStreamEntryId(id.to_owned()))?;
-            ids.push(start_id);
+            ids.push(last_id.unwrap_or(StreamKey::new(0, 0)));
         } else {
-            let start_id = StreamKey::from_string(&id, &None, Some(0))
-                .map_err(|_| RedisError::InvalidStreamEntryId(id.to_owned()))?;
-            ids.push(start_id);
+            ids.push(StreamKey::parse(id, 0)?);
         }
     }
 
@@ -96,42 +150,38 @@ async fn calculate_stream_start_ids(
 
 fn xread_reader(
     stream_keys: &[String],
-    ids: &Vec<StreamKey>,
-    mem: &MutexGuard<HashMap<String, EntryValue>>,
-) -> String {
-    let stream_with_time = stream_keys.iter().zip(ids.iter());
+    ids: &[StreamKey],
+    count: Option<usize>,
+    mem: &Keyspace,
+) -> Result<Vec<String>, &'static str> {
     let mut entry_parsed = Vec::new();
-
-    for (key, id) in stream_with_time {
-        if let Some(entry) = mem.get(key) {
-            if let ValueContainer::Stream(stream) = &entry.value {
-                let idx_start = match stream.binary_search_by(|val| val.id.cmp(id)) {
-                    Ok(idx) => idx + 1,
-                    Err(idx) => idx,
-                };
-
-                if idx_start >= stream.len() {
-                    continue;
-                }
-
-                let slice = &stream[idx_start..];
-                let serialized = resp_serializer::slc_objects_to_resp(slice);
-                let name_serialized = resp_serializer::to_resp_bulk(key.to_owned());
-                let blob_serialized = format!("*{}\r\n{}{}", 2, name_serialized, serialized);
-
-                entry_parsed.push(blob_serialized);
-            }
+    for (key, id) in stream_keys.iter().zip(ids.iter()) {
+        if let Some(reply) = read_key(mem, key, id, count)? {
+            entry_parsed.push(reply);
         }
     }
+    Ok(entry_parsed)
+}
 
-    if entry_parsed.is_empty() {
-        return resp_serializer::null_resp_string();
-    }
-
-    let mut result = format!("*{}\r\n", entry_parsed.len());
-    for entry in entry_parsed {
-        result.push_str(&entry)
+// The key with its entries after `id`, or None when there are none.
+fn read_key(
+    mem: &Keyspace,
+    key: &str,
+    id: &StreamKey,
+    count: Option<usize>,
+) -> Result<Option<String>, &'static str> {
+    let Some(stream) = command_utils::get_value::<RedisStream>(mem, key)? else {
+        return Ok(None);
+    };
+
+    let entries = stream.after(id);
+    let entries = &entries[..entries.len().min(count.unwrap_or(usize::MAX))];
+    if entries.is_empty() {
+        return Ok(None);
     }
 
-    result
+    Ok(Some(resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_bulk(key.to_owned()),
+        resp_serializer::slc_objects_to_resp(entries),
+    ])))
 }
@@ -0,0 +1,290 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    resp::resp_serializer::{self, RespSerializer},
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        connection_context::ConnectionContext,
+        execution_response::ExecResponse,
+        keyspace::Keyspace,
+        stream::{self, RedisStream},
+        stream_entry::StreamEntry,
+        stream_key::StreamKey,
+        waiter_registry::{WaiterHandler, WaiterOutcome},
+    },
+    utils,
+};
+
+use super::command_utils;
+
+// `>` asks for entries never delivered to the group, an explicit id for
+// the consumer's own pending entries after it.
+#[derive(Clone, Copy)]
+enum ReadId {
+    New,
+    Pending(StreamKey),
+}
+
+#[derive(Clone)]
+struct ReadOptions {
+    group: String,
+    consumer: String,
+    count: Option<usize>,
+    noack: bool,
+}
+
+impl ReadOptions {
+    // The read as a non-blocking command, which is what replicas apply.
+    fn to_command(&self, keys: &[String], ids: &[String]) -> RespTk {
+        let mut command = vec![
+            "XREADGROUP".to_owned(),
+            "GROUP".to_owned(),
+            self.group.to_owned(),
+            self.consumer.to_owned(),
+        ];
+        if let Some(count) = self.count {
+            command.extend(["COUNT".to_owned(), count.to_string()]);
+        }
+        if self.noack {
+            command.push("NOACK".to_owned());
+        }
+        command.push("STREAMS".to_owned());
+        command.extend(keys.iter().cloned());
+        command.extend(ids.iter().cloned());
+        command_utils::to_command_token(command)
+    }
+}
+
+pub async fn execute_xreadgroup(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    context: &ConnectionContext,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (options, timeout, keys, ids) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+    let read_ids = match ids
+        .iter()
+        .map(|id| match id.as_str() {
+            ">" => Ok(ReadId::New),
+            id => StreamKey::parse(id, 0).map(ReadId::Pending),
+        })
+        .collect::<Result<Vec<ReadId>, &str>>()
+    {
+        Ok(read_ids) => read_ids,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    // A missing group on any key fails the read before the others deliver.
+    for key in keys {
+        if let Err(err) = check_group(&mem, key, &options.group) {
+            return resp_serializer::to_err_string(err).into();
+        }
+    }
+
+    let mut replies = vec![];
+    for (key, id) in keys.iter().zip(&read_ids) {
+        match read_key(&mut mem, key, *id, &options) {
+            Ok(Some(reply)) => replies.push(reply),
+            Ok(None) => {}
+            Err(err) => return resp_serializer::to_err_string(err).into(),
+        }
+    }
+
+    if !replies.is_empty() || timeout.is_none() {
+        app.buffer_command(&options.to_command(keys, ids)).await;
+        return match replies.is_empty() {
+            true => resp_serializer::null_resp_array(),
+            false => resp_serializer::to_resp_nested_array(replies),
+        }
+        .into();
+    }
+    drop(mem);
+
+    // Only reads of new entries come here: pending reads always reply.
+    let handler: WaiterHandler = {
+        let options = options.clone();
+        Box::new(move |mem: &mut Keyspace, key: &str| {
+            match read_key(mem, key, ReadId::New, &options) {
+                Ok(Some(reply)) => Some(WaiterOutcome {
+                    response: resp_serializer::to_resp_nested_array(vec![reply]),
+                    commands: vec![options.to_command(&[key.to_owned()], &[">".to_owned()])],
+                    ready_keys: vec![],
+                }),
+                Ok(None) => None,
+                Err(err) => Some(WaiterOutcome {
+                    response: resp_serializer::to_err_string(err),
+                    commands: vec![],
+                    ready_keys: vec![],
+                }),
+            }
+        })
+    };
+
+    match app
+        .serve_or_block(keys.to_vec(), timeout.flatten(), handler, context)
+        .await
+    {
+        Some(response) => response.into(),
+        None => resp_serializer::null_resp_array().into(),
+    }
+}
+
+type ParsedArgs<'a> = (
+    ReadOptions,
+    Option<Option<Duration>>,
+    &'a [String],
+    &'a [String],
+);
+
+// The timeout is None without BLOCK, and Some(None) for BLOCK 0.
+fn parse_args(args: &[String]) -> Result<ParsedArgs<'_>, String> {
+    let mut group = None;
+    let mut count = None;
+    let mut timeout = None;
+    let mut noack = false;
+
+    let mut args = args;
+    while let [option, rest @ ..] = args {
+        args = rest;
+        match (option.to_uppercase().as_str(), args) {
+            ("GROUP", [name, consumer, rest @ ..]) => {
+                group = Some((name, consumer));
+                args = rest;
+            }
+            ("COUNT", [value, rest @ ..]) => {
+                let value = command_utils::parse_integer(value)
+                    .ok_or("ERR value is not an integer or out of range")?;
+                count = (value > 0).then_some(value as usize);
+                args = rest;
+            }
+            ("BLOCK", [value, rest @ ..]) => {
+                let millis = command_utils::parse_integer(value)
+                    .ok_or("ERR timeout is not an integer or out of range")?;
+                if millis < 0 {
+                    return Err("ERR timeout is negative".into());
+                }
+                timeout = Some((millis > 0).then(|| Duration::from_millis(millis as u64)));
+                args = rest;
+            }
+            ("NOACK", _) => noack = true,
+            ("STREAMS", streams) => {
+                let Some((name, consumer)) = group else {
+                    return Err("ERR Missing GROUP option for XREADGROUP".into());
+                };
+                if streams.is_empty() || !streams.len().is_multiple_of(2) {
+                    return Err("ERR Unbalanced 'xreadgroup' list of streams: for each stream key an ID or '>' must be specified.".into());
+                }
+                let (keys, ids) = streams.split_at(streams.len() / 2);
+                let options = ReadOptions {
+                    group: name.to_owned(),
+                    consumer: consumer.to_owned(),
+                    count,
+                    noack,
+                };
+                return Ok((options, timeout, keys, ids));
+            }
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+    Err("ERR syntax error".into())
+}
+
+fn check_group(mem: &Keyspace, key: &str, group: &str) -> Result<(), String> {
+    match command_utils::get_value::<RedisStream>(mem, key)? {
+        Some(stream) if stream.groups.contains_key(group) => Ok(()),
+        _ => Err(stream::no_group_error(key, group)),
+    }
+}
+
+// Serves the consumer from one stream, replying `[key, entries]`. Reads of
+// new entries reply None when there are none.
+fn read_key(
+    mem: &mut Keyspace,
+    key: &str,
+    id: ReadId,
+    options: &ReadOptions,
+) -> Result<Option<String>, String> {
+    let now = utils::get_current_time_ms();
+    let Some(stream) = command_utils::get_value_mut::<RedisStream>(mem, key)? else {
+        return Err(stream::no_group_error(key, &options.group));
+    };
+    let Some(last_delivered) = stream
+        .groups
+        .get(&options.group)
+        .map(|group| group.last_delivered)
+    else {
+        return Err(stream::no_group_error(key, &options.group));
+    };
+    let count = options.count.unwrap_or(usize::MAX);
+
+    let items = match id {
+        ReadId::New => {
+            let entries: Vec<StreamEntry> = stream
+                .after(&last_delivered)
+                .iter()
+                .take(count)
+                .cloned()
+                .collect();
+            let Some(group) = stream.groups.get_mut(&options.group) else {
+                return Err(stream::no_group_error(key, &options.group));
+            };
+            group.touch_consumer(&options.consumer, now);
+            if entries.is_empty() {
+                return Ok(None);
+            }
+
+            for entry in &entries {
+                group.last_delivered = entry.id;
+                if !options.noack {
+                    group.deliver(&options.consumer, entry.id, now);
+                }
+            }
+            entries.iter().map(|entry| entry.to_resp()).collect()
+        }
+        ReadId::Pending(after) => {
+            let Some(group) = stream.groups.get(&options.group) else {
+                return Err(stream::no_group_error(key, &options.group));
+            };
+            let ids: Vec<StreamKey> = group
+                .pending
+                .range(after..)
+                .filter(|(id, pending)| **id > after && pending.consumer == options.consumer)
+                .take(count)
+                .map(|(id, _)| *id)
+                .collect();
+            let items: Vec<String> = ids
+                .iter()
+                .map(|id| match stream.get(id) {
+                    Some(entry) => entry.to_resp(),
+                    // Entries deleted since delivery are reported without fields.
+                    None => resp_serializer::to_resp_nested_array(vec![
+                        resp_serializer::to_resp_bulk((*id).into()),
+                        resp_serializer::null_resp_array(),
+                    ]),
+                })
+                .collect();
+
+            if let Some(group) = stream.groups.get_mut(&options.group) {
+                group.touch_consumer(&options.consumer, now);
+                for id in ids {
+                    group.deliver(&options.consumer, id, now);
+                }
+            }
+            items
+        }
+    };
+
+    Ok(Some(resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_bulk(key.to_owned()),
+        resp_serializer::to_resp_nested_array(items),
+    ])))
+}
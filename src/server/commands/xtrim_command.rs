@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        stream::{RedisStream, TrimStrategy},
+        stream_key::StreamKey,
+    },
+};
+
+use super::command_utils;
+
+pub struct TrimOptions {
+    pub strategy: TrimStrategy,
+    pub limit: Option<usize>,
+}
+
+// Parses `MAXLEN|MINID [=|~] threshold [LIMIT count]` off the front of
+// `args`, returning the arguments that follow. `~` trims as much as it can
+// within LIMIT, so it may keep more entries than asked.
+pub fn parse_trim(args: &[String]) -> Result<Option<(TrimOptions, &[String])>, &'static str> {
+    let [kind, rest @ ..] = args else {
+        return Ok(None);
+    };
+    let max_len = match kind.to_uppercase().as_str() {
+        "MAXLEN" => true,
+        "MINID" => false,
+        _ => return Ok(None),
+    };
+
+    let (approximate, rest) = match rest {
+        [operator, rest @ ..] if operator == "~" => (true, rest),
+        [operator, rest @ ..] if operator == "=" => (false, rest),
+        rest => (false, rest),
+    };
+    let [threshold, rest @ ..] = rest else {
+        return Err("ERR syntax error");
+    };
+
+    let strategy = match max_len {
+        true => TrimStrategy::MaxLen(match command_utils::parse_integer(threshold) {
+            Some(max_len) if max_len >= 0 => max_len as usize,
+            Some(_) => return Err("ERR The MAXLEN argument must be >= 0."),
+            None => return Err("ERR value is not an integer or out of range"),
+        }),
+        false => TrimStrategy::MinId(StreamKey::parse(threshold, 0)?),
+    };
+
+    let (limit, rest) = match rest {
+        [option, count, rest @ ..] if option.eq_ignore_ascii_case("LIMIT") => {
+            if !approximate {
+                return Err("ERR syntax error, LIMIT cannot be used without the special ~ option");
+            }
+            let count = command_utils::parse_integer(count)
+                .filter(|count| *count >= 0)
+                .ok_or("ERR The LIMIT argument must be >= 0.")?;
+            // LIMIT 0 means no limit.
+            ((count > 0).then_some(count as usize), rest)
+        }
+        rest => (None, rest),
+    };
+
+    Ok(Some((TrimOptions { strategy, limit }, rest)))
+}
+
+pub async fn execute_xtrim(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [key, options @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    let trim = match parse_trim(options) {
+        Ok(Some((trim, []))) => trim,
+        Ok(_) => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let evicted = match command_utils::get_value_mut::<RedisStream>(&mut mem, key) {
+        Ok(Some(stream)) => stream.trim(&trim.strategy, trim.limit),
+        Ok(None) => 0,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    if evicted > 0 {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(evicted as i64).into()
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+const NOT_A_FLOAT_ERROR: &str = "ERR value is not a valid float";
+const NAN_SCORE_ERROR: &str = "ERR resulting score is not a number (NaN)";
+
+#[derive(Default)]
+struct ZAddOptions {
+    only_new: bool,
+    only_existing: bool,
+    only_greater: bool,
+    only_less: bool,
+    changed: bool,
+    increment: bool,
+}
+
+pub async fn execute_zadd(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, args)) = args.split_first().filter(|(_, args)| args.len() >= 2) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut opts = ZAddOptions::default();
+    let mut args = args;
+    while let Some((option, rest)) = args.split_first() {
+        match option.to_uppercase().as_str() {
+            "NX" => opts.only_new = true,
+            "XX" => opts.only_existing = true,
+            "GT" => opts.only_greater = true,
+            "LT" => opts.only_less = true,
+            "CH" => opts.changed = true,
+            "INCR" => opts.increment = true,
+            _ => break,
+        }
+        args = rest;
+    }
+
+    if args.is_empty() || !args.len().is_multiple_of(2) {
+        return resp_serializer::to_err_string("ERR syntax error".into()).into();
+    }
+    if opts.only_new && opts.only_existing {
+        return resp_serializer::to_err_string(
+            "ERR XX and NX options at the same time are not compatible".into(),
+        )
+        .into();
+    }
+    if (opts.only_greater && opts.only_less)
+        || (opts.only_new && (opts.only_greater || opts.only_less))
+    {
+        return resp_serializer::to_err_string(
+            "ERR GT, LT, and/or NX options at the same time are not compatible".into(),
+        )
+        .into();
+    }
+    if opts.increment && args.len() > 2 {
+        return resp_serializer::to_err_string(
+            "ERR INCR option supports a single increment-element pair".into(),
+        )
+        .into();
+    }
+
+    let mut elements = Vec::with_capacity(args.len() / 2);
+    for pair in args.chunks_exact(2) {
+        let Some(score) = command_utils::parse_float(&pair[0]) else {
+            return resp_serializer::to_err_string(NOT_A_FLOAT_ERROR.into()).into();
+        };
+        elements.push((score, pair[1].to_owned()));
+    }
+
+    let mut mem = app.memory.lock().await;
+    let zset = match command_utils::get_or_create_value(&mut mem, key, SortedSet::new) {
+        Ok(zset) => zset,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut added = 0;
+    let mut updated = 0;
+    let mut last_score = None;
+
+    for (score, member) in elements {
+        last_score = None;
+        match zset.score(&member) {
+            Some(_) if opts.only_new => {}
+            Some(current) => {
+                let score = if opts.increment {
+                    current + score
+                } else {
+                    score
+                };
+                if score.is_nan() {
+                    command_utils::remove_if_empty(&mut mem, key);
+                    return resp_serializer::to_err_string(NAN_SCORE_ERROR.into()).into();
+                }
+                if (opts.only_greater && score <= current) || (opts.only_less && score >= current) {
+                    continue;
+                }
+
+                last_score = Some(score);
+                if score != current {
+                    zset.insert(member, score);
+                    updated += 1;
+                }
+            }
+            None if opts.only_existing => {}
+            None => {
+                last_score = Some(score);
+                zset.insert(member, score);
+                added += 1;
+            }
+        }
+    }
+    command_utils::remove_if_empty(&mut mem, key);
+
+    if added + updated > 0 {
+        app.buffer_command(token).await;
+        app.signal_key_ready(&mut mem, key).await;
+    }
+
+    if opts.increment {
+        return match last_score {
+            Some(score) => resp_serializer::to_resp_bulk(command_utils::format_float(score)).into(),
+            None => resp_serializer::null_resp_string().into(),
+        };
+    }
+
+    let reply = if opts.changed { added + updated } else { added };
+    resp_serializer::to_resp_integer(reply).into()
+}
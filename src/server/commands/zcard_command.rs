@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_zcard(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), None) = (command_utils::get_next_arg_string(&mut args), args.next()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<SortedSet>(&mem, &key) {
+        Ok(zset) => {
+            let len = zset.map_or(0, |zset| zset.len());
+            resp_serializer::to_resp_integer(len as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        sorted_set::SortedSet,
+        sorted_set::{LexRange, ScoreRange},
+    },
+};
+
+use super::command_utils;
+
+pub async fn execute_zcount(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(min), Some(max), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(range) = ScoreRange::parse(&min, &max) else {
+        return resp_serializer::to_err_string(command_utils::SCORE_RANGE_ERROR.into()).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<SortedSet>(&mem, &key) {
+        Ok(zset) => {
+            let count = zset.map_or(0, |zset| zset.count_by_score(&range));
+            resp_serializer::to_resp_integer(count as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
+
+pub async fn execute_zlexcount(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(min), Some(max), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(range) = LexRange::parse(&min, &max) else {
+        return resp_serializer::to_err_string(command_utils::LEX_RANGE_ERROR.into()).into();
+    };
+
+    let mem = app.memory.lock().await;
+    match command_utils::get_value::<SortedSet>(&mem, &key) {
+        Ok(zset) => {
+            let count = zset.map_or(0, |zset| zset.count_by_lex(&range));
+            resp_serializer::to_resp_integer(count as i64).into()
+        }
+        Err(err) => resp_serializer::to_err_string(err.into()).into(),
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_zincrby(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(increment), Some(member), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let Some(increment) = command_utils::parse_float(&increment) else {
+        return resp_serializer::to_err_string("ERR value is not a valid float".into()).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let zset = match command_utils::get_or_create_value(&mut mem, &key, SortedSet::new) {
+        Ok(zset) => zset,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let score = zset.score(&member).unwrap_or_default() + increment;
+    if score.is_nan() {
+        command_utils::remove_if_empty(&mut mem, &key);
+        return resp_serializer::to_err_string("ERR resulting score is not a number (NaN)".into())
+            .into();
+    }
+
+    zset.insert(member, score);
+    app.buffer_command(token).await;
+    app.signal_key_ready(&mut mem, &key).await;
+    resp_serializer::to_resp_bulk(command_utils::format_float(score)).into()
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_zpopmin(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    pop(app, token, false).await
+}
+
+pub async fn execute_zpopmax(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    pop(app, token, true).await
+}
+
+async fn pop(app: Arc<RedisApp>, token: &RespTk, max: bool) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, count) = match args.as_slice() {
+        [key] => (key, 1),
+        [key, count] => match command_utils::parse_integer(count) {
+            Some(count) if count >= 0 => (key, count as usize),
+            _ => {
+                return resp_serializer::to_err_string(
+                    "ERR value is out of range, must be positive".into(),
+                )
+                .into()
+            }
+        },
+        [_, _, _] => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let zset = match command_utils::get_value_mut::<SortedSet>(&mut mem, key) {
+        Ok(Some(zset)) => zset,
+        Ok(None) => return resp_serializer::to_resp_array(vec![]).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let popped = pop_entries(zset, max, count);
+    command_utils::remove_if_empty(&mut mem, key);
+
+    if !popped.is_empty() {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_array(flatten_entries(popped)).into()
+}
+
+// Removes up to `count` elements from the lowest or highest end.
+pub fn pop_entries(zset: &mut SortedSet, max: bool, count: usize) -> Vec<(String, f64)> {
+    if count == 0 || zset.is_empty() {
+        return vec![];
+    }
+
+    let end = count.min(zset.len()) - 1;
+    let popped = zset.range_by_rank(0, end, max);
+    for (member, _) in &popped {
+        zset.remove(member);
+    }
+    popped
+}
+
+pub fn flatten_entries(entries: Vec<(String, f64)>) -> Vec<String> {
+    entries
+        .into_iter()
+        .flat_map(|(member, score)| [member, command_utils::format_float(score)])
+        .collect()
+}
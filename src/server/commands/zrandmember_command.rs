@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_zrandmember(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, count, with_scores) = match args.as_slice() {
+        [key] => (key, None, false),
+        [key, count] => (key, Some(count), false),
+        [key, count, option] if option.eq_ignore_ascii_case("WITHSCORES") => {
+            (key, Some(count), true)
+        }
+        [_, _, _] => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let count = match count.map(|count| command_utils::parse_integer(count)) {
+        None => None,
+        Some(Some(count)) if count.unsigned_abs() <= i64::MAX as u64 / 2 => Some(count),
+        Some(Some(_)) => {
+            return resp_serializer::to_err_string("ERR value is out of range".into()).into()
+        }
+        Some(None) => {
+            return resp_serializer::to_err_string(
+                "ERR value is not an integer or out of range".into(),
+            )
+            .into()
+        }
+    };
+
+    let mem = app.memory.lock().await;
+    let zset = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(Some(zset)) => zset,
+        Ok(None) if count.is_some() => return resp_serializer::to_resp_array(vec![]).into(),
+        Ok(None) => return resp_serializer::null_resp_string().into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let entries: Vec<(&String, f64)> = zset.iter().collect();
+    let Some(count) = count else {
+        let (member, _) = entries[utils::random_index(entries.len())];
+        return resp_serializer::to_resp_bulk(member.to_owned()).into();
+    };
+
+    // A negative count allows the same member to be returned more than once.
+    let picked: Vec<usize> = if count < 0 {
+        (0..count.unsigned_abs())
+            .map(|_| utils::random_index(entries.len()))
+            .collect()
+    } else {
+        utils::sample_distinct(entries.len(), count as usize)
+    };
+
+    let reply = picked
+        .into_iter()
+        .flat_map(|idx| {
+            let (member, score) = entries[idx];
+            let score = with_scores.then(|| command_utils::format_float(score));
+            std::iter::once(member.to_owned()).chain(score)
+        })
+        .collect();
+    resp_serializer::to_resp_array(reply).into()
+}
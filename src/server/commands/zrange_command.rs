@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        sorted_set::{LexRange, ScoreRange, SortedSet},
+    },
+};
+
+use super::command_utils;
+
+enum RangeBy {
+    Rank(i64, i64),
+    Score(ScoreRange),
+    Lex(LexRange),
+}
+
+struct RangeQuery {
+    by: RangeBy,
+    reverse: bool,
+    offset: i64,
+    limit: Option<usize>,
+    with_scores: bool,
+}
+
+pub async fn execute_zrange(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, args)) = args.split_first().filter(|(_, args)| args.len() >= 2) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let query = match parse_query(args, true) {
+        Ok(query) => query,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let entries = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(zset) => zset.map(|zset| run_query(zset, &query)).unwrap_or_default(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let items = entries
+        .into_iter()
+        .flat_map(|(member, score)| {
+            let score = query
+                .with_scores
+                .then(|| command_utils::format_float(score));
+            std::iter::once(member).chain(score)
+        })
+        .collect();
+    resp_serializer::to_resp_array(items).into()
+}
+
+pub async fn execute_zrangestore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let [destination, source, args @ ..] = args.as_slice() else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+    if args.len() < 2 {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let query = match parse_query(args, false) {
+        Ok(query) => query,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let entries = match command_utils::get_value::<SortedSet>(&mem, source) {
+        Ok(zset) => zset.map(|zset| run_query(zset, &query)).unwrap_or_default(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut result = SortedSet::new();
+    for (member, score) in entries {
+        result.insert(member, score);
+    }
+
+    let len = command_utils::store_sorted_set(&mut mem, destination, result);
+    app.buffer_command(token).await;
+    app.signal_key_ready(&mut mem, destination).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
+
+// Parses `start stop [BYSCORE | BYLEX] [REV] [LIMIT offset count] [WITHSCORES]`.
+fn parse_query(args: &[String], allow_with_scores: bool) -> Result<RangeQuery, &'static str> {
+    let (start, stop) = (&args[0], &args[1]);
+    let mut by_score = false;
+    let mut by_lex = false;
+    let mut reverse = false;
+    let mut limit = None;
+    let mut with_scores = false;
+
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.to_uppercase().as_str() {
+            "BYSCORE" => by_score = true,
+            "BYLEX" => by_lex = true,
+            "REV" => reverse = true,
+            "WITHSCORES" if allow_with_scores => with_scores = true,
+            "LIMIT" => {
+                let (Some(offset), Some(count)) = (options.next(), options.next()) else {
+                    return Err("ERR syntax error");
+                };
+                match (
+                    command_utils::parse_integer(offset),
+                    command_utils::parse_integer(count),
+                ) {
+                    (Some(offset), Some(count)) => limit = Some((offset, count)),
+                    _ => return Err("ERR value is not an integer or out of range"),
+                }
+            }
+            _ => return Err("ERR syntax error"),
+        }
+    }
+
+    if by_score && by_lex {
+        return Err("ERR syntax error");
+    }
+    if limit.is_some() && !by_score && !by_lex {
+        return Err(
+            "ERR syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX",
+        );
+    }
+    if with_scores && by_lex {
+        return Err("ERR syntax error, WITHSCORES not supported in combination with BYLEX");
+    }
+
+    // Score and lex ranges are given as `max min` when reversed.
+    let (min, max) = if reverse {
+        (stop, start)
+    } else {
+        (start, stop)
+    };
+    let by = if by_score {
+        RangeBy::Score(ScoreRange::parse(min, max).ok_or(command_utils::SCORE_RANGE_ERROR)?)
+    } else if by_lex {
+        RangeBy::Lex(LexRange::parse(min, max).ok_or(command_utils::LEX_RANGE_ERROR)?)
+    } else {
+        match (
+            command_utils::parse_integer(start),
+            command_utils::parse_integer(stop),
+        ) {
+            (Some(start), Some(stop)) => RangeBy::Rank(start, stop),
+            _ => return Err("ERR value is not an integer or out of range"),
+        }
+    };
+
+    let (offset, count) = limit.unwrap_or((0, -1));
+    Ok(RangeQuery {
+        by,
+        reverse,
+        offset,
+        limit: (count >= 0).then_some(count as usize),
+        with_scores,
+    })
+}
+
+fn run_query(zset: &SortedSet, query: &RangeQuery) -> Vec<(String, f64)> {
+    // A negative offset always yields an empty range.
+    if query.offset < 0 {
+        return vec![];
+    }
+    let offset = query.offset as usize;
+
+    match &query.by {
+        RangeBy::Rank(start, stop) => {
+            match command_utils::normalize_list_range(zset.len(), *start, *stop) {
+                Some((start, end)) => zset.range_by_rank(start, end, query.reverse),
+                None => vec![],
+            }
+        }
+        RangeBy::Score(range) => zset.range_by_score(range, query.reverse, offset, query.limit),
+        RangeBy::Lex(range) => zset.range_by_lex(range, query.reverse, offset, query.limit),
+    }
+}
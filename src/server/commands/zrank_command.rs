@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_zrank(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    rank(app, token, false).await
+}
+
+pub async fn execute_zrevrank(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    rank(app, token, true).await
+}
+
+async fn rank(app: Arc<RedisApp>, token: &RespTk, reverse: bool) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let (key, member, with_score) = match args.as_slice() {
+        [key, member] => (key, member, false),
+        [key, member, option] if option.eq_ignore_ascii_case("WITHSCORE") => (key, member, true),
+        [_, _, _] => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+        _ => return resp_serializer::to_err_string(command_utils::arity_error(token)).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let zset = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(zset) => zset,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let found = zset.and_then(|zset| Some((zset.rank(member, reverse)?, zset.score(member)?)));
+    match found {
+        Some((rank, score)) if with_score => resp_serializer::to_resp_nested_array(vec![
+            resp_serializer::to_resp_integer(rank as i64),
+            resp_serializer::to_resp_bulk(command_utils::format_float(score)),
+        ])
+        .into(),
+        Some((rank, _)) => resp_serializer::to_resp_integer(rank as i64).into(),
+        None => resp_serializer::null_resp_string().into(),
+    }
+}
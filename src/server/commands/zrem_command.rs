@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+};
+
+use super::command_utils;
+
+pub async fn execute_zrem(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let key = command_utils::get_next_arg_string(&mut args);
+    let members: Vec<String> = args.filter_map(|tk| tk.get_content_string()).collect();
+
+    let Some(key) = key.filter(|_| !members.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let mut mem = app.memory.lock().await;
+    let zset = match command_utils::get_value_mut::<SortedSet>(&mut mem, &key) {
+        Ok(Some(zset)) => zset,
+        Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let removed = members.iter().filter(|member| zset.remove(member)).count();
+    command_utils::remove_if_empty(&mut mem, &key);
+
+    if removed > 0 {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(removed as i64).into()
+}
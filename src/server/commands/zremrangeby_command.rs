@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse,
+        sorted_set::SortedSet,
+        sorted_set::{LexRange, ScoreRange},
+    },
+};
+
+use super::command_utils;
+
+enum RemoveRange {
+    Rank(i64, i64),
+    Score(ScoreRange),
+    Lex(LexRange),
+}
+
+pub async fn execute_zremrangebyrank(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    remove_range(app, token, |start, stop| {
+        match (
+            command_utils::parse_integer(start),
+            command_utils::parse_integer(stop),
+        ) {
+            (Some(start), Some(stop)) => Ok(RemoveRange::Rank(start, stop)),
+            _ => Err("ERR value is not an integer or out of range"),
+        }
+    })
+    .await
+}
+
+pub async fn execute_zremrangebyscore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    remove_range(app, token, |min, max| {
+        ScoreRange::parse(min, max)
+            .map(RemoveRange::Score)
+            .ok_or(command_utils::SCORE_RANGE_ERROR)
+    })
+    .await
+}
+
+pub async fn execute_zremrangebylex(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    remove_range(app, token, |min, max| {
+        LexRange::parse(min, max)
+            .map(RemoveRange::Lex)
+            .ok_or(command_utils::LEX_RANGE_ERROR)
+    })
+    .await
+}
+
+async fn remove_range(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    parse_range: impl Fn(&str, &str) -> Result<RemoveRange, &'static str>,
+) -> ExecResponse {
+    let mut args = token.get_command_args();
+    let (Some(key), Some(min), Some(max), None) = (
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        command_utils::get_next_arg_string(&mut args),
+        args.next(),
+    ) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let range = match parse_range(&min, &max) {
+        Ok(range) => range,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let zset = match command_utils::get_value_mut::<SortedSet>(&mut mem, &key) {
+        Ok(Some(zset)) => zset,
+        Ok(None) => return resp_serializer::to_resp_integer(0).into(),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let removed = match &range {
+        RemoveRange::Rank(start, stop) => {
+            match command_utils::normalize_list_range(zset.len(), *start, *stop) {
+                Some((start, end)) => zset.range_by_rank(start, end, false),
+                None => vec![],
+            }
+        }
+        RemoveRange::Score(range) => zset.range_by_score(range, false, 0, None),
+        RemoveRange::Lex(range) => zset.range_by_lex(range, false, 0, None),
+    };
+    for (member, _) in &removed {
+        zset.remove(member);
+    }
+    command_utils::remove_if_empty(&mut mem, &key);
+
+    if !removed.is_empty() {
+        app.buffer_command(token).await;
+    }
+    resp_serializer::to_resp_integer(removed.len() as i64).into()
+}
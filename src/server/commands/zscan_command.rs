@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{execution_response::ExecResponse, sorted_set::SortedSet},
+    utils,
+};
+
+use super::command_utils;
+
+pub async fn execute_zscan(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((key, args)) = args.split_first().filter(|(_, args)| !args.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let opts = match command_utils::parse_scan_args(args, false) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let (cursor, page) = match command_utils::get_value::<SortedSet>(&mem, key) {
+        Ok(Some(zset)) => utils::scan_page(zset.iter(), opts.cursor, opts.count),
+        Ok(None) => (0, vec![]),
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let items = page
+        .into_iter()
+        .filter(|(member, _)| opts.matches(member))
+        .flat_map(|(member, score)| [member.to_owned(), command_utils::format_float(score)])
+        .collect();
+
+    resp_serializer::to_resp_nested_array(vec![
+        resp_serializer::to_resp_bulk(cursor.to_string()),
+        resp_serializer::to_resp_array(items),
+    ])
+    .into()
+}
@@ -0,0 +1,380 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    resp::resp_serializer,
+    resp_desserializer::RespTk,
+    server::redis_app::RedisApp,
+    types::{
+        execution_response::ExecResponse, keyspace::Keyspace, set::RedisSet, sorted_set::SortedSet,
+        value_container::ValueContainer,
+    },
+};
+
+use super::command_utils;
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum SetOperation {
+    Union,
+    Inter,
+    Diff,
+}
+
+#[derive(Clone, Copy)]
+enum Aggregate {
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    fn apply(&self, current: f64, score: f64) -> f64 {
+        match self {
+            // inf + -inf is NaN, which Redis turns into 0.
+            Aggregate::Sum => Some(current + score)
+                .filter(|sum| !sum.is_nan())
+                .unwrap_or_default(),
+            Aggregate::Min => current.min(score),
+            Aggregate::Max => current.max(score),
+        }
+    }
+}
+
+struct SetOpArgs {
+    keys: Vec<String>,
+    weights: Vec<f64>,
+    aggregate: Aggregate,
+    with_scores: bool,
+}
+
+// Plain sets can be combined with sorted sets, every member scoring 1.
+enum Input<'a> {
+    Sorted(&'a SortedSet),
+    Plain(&'a RedisSet),
+    Missing,
+}
+
+impl Input<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Input::Sorted(zset) => zset.len(),
+            Input::Plain(set) => set.len(),
+            Input::Missing => 0,
+        }
+    }
+
+    fn score(&self, member: &str) -> Option<f64> {
+        match self {
+            Input::Sorted(zset) => zset.score(member),
+            Input::Plain(set) => set.contains(member).then_some(1.0),
+            Input::Missing => None,
+        }
+    }
+
+    fn entries(&self) -> Vec<(String, f64)> {
+        match self {
+            Input::Sorted(zset) => zset
+                .iter()
+                .map(|(member, score)| (member.to_owned(), score))
+                .collect(),
+            Input::Plain(set) => set.iter().map(|member| (member, 1.0)).collect(),
+            Input::Missing => vec![],
+        }
+    }
+}
+
+pub async fn execute_zunion(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    combine(app, token, SetOperation::Union).await
+}
+
+pub async fn execute_zinter(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    combine(app, token, SetOperation::Inter).await
+}
+
+pub async fn execute_zdiff(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    combine(app, token, SetOperation::Diff).await
+}
+
+pub async fn execute_zunionstore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    combine_and_store(app, token, SetOperation::Union).await
+}
+
+pub async fn execute_zinterstore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    combine_and_store(app, token, SetOperation::Inter).await
+}
+
+pub async fn execute_zdiffstore(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    combine_and_store(app, token, SetOperation::Diff).await
+}
+
+pub async fn execute_zintercard(app: Arc<RedisApp>, token: &RespTk) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((numkeys, rest)) = args.split_first().filter(|(_, rest)| !rest.is_empty()) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let numkeys = match command_utils::parse_integer(numkeys) {
+        Some(numkeys) if numkeys > 0 => numkeys as usize,
+        _ => {
+            return resp_serializer::to_err_string("ERR numkeys should be greater than 0".into())
+                .into()
+        }
+    };
+
+    if numkeys > rest.len() {
+        return resp_serializer::to_err_string(
+            "ERR Number of keys can't be greater than number of args".into(),
+        )
+        .into();
+    }
+
+    let (keys, options) = rest.split_at(numkeys);
+    let limit = match options {
+        [] => 0,
+        [option, limit] if option.eq_ignore_ascii_case("LIMIT") => {
+            match command_utils::parse_integer(limit) {
+                Some(limit) if limit >= 0 => limit as usize,
+                _ => {
+                    return resp_serializer::to_err_string("ERR LIMIT can't be negative".into())
+                        .into()
+                }
+            }
+        }
+        _ => return resp_serializer::to_err_string("ERR syntax error".into()).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let inputs = match get_inputs(&mem, keys) {
+        Ok(inputs) => inputs,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let Some((smallest, others)) = split_smallest(&inputs) else {
+        return resp_serializer::to_resp_integer(0).into();
+    };
+
+    let matches = smallest
+        .entries()
+        .into_iter()
+        .filter(|(member, _)| others.iter().all(|input| input.score(member).is_some()));
+    let count = match limit {
+        0 => matches.count(),
+        limit => matches.take(limit).count(),
+    };
+    resp_serializer::to_resp_integer(count as i64).into()
+}
+
+async fn combine(app: Arc<RedisApp>, token: &RespTk, operation: SetOperation) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    if args.len() < 2 {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    }
+
+    let command = token.get_command_name().to_lowercase();
+    let opts = match parse_args(&args, operation, true, &command) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mem = app.memory.lock().await;
+    let result = match compute(&mem, &opts, operation) {
+        Ok(result) => result,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let items = result
+        .iter()
+        .flat_map(|(member, score)| {
+            let score = opts.with_scores.then(|| command_utils::format_float(score));
+            std::iter::once(member.to_owned()).chain(score)
+        })
+        .collect();
+    resp_serializer::to_resp_array(items).into()
+}
+
+async fn combine_and_store(
+    app: Arc<RedisApp>,
+    token: &RespTk,
+    operation: SetOperation,
+) -> ExecResponse {
+    let args: Vec<String> = token
+        .get_command_args()
+        .filter_map(|tk| tk.get_content_string())
+        .collect();
+
+    let Some((destination, args)) = args.split_first().filter(|(_, args)| args.len() >= 2) else {
+        return resp_serializer::to_err_string(command_utils::arity_error(token)).into();
+    };
+
+    let command = token.get_command_name().to_lowercase();
+    let opts = match parse_args(args, operation, false, &command) {
+        Ok(opts) => opts,
+        Err(err) => return resp_serializer::to_err_string(err).into(),
+    };
+
+    let mut mem = app.memory.lock().await;
+    let result = match compute(&mem, &opts, operation) {
+        Ok(result) => result,
+        Err(err) => return resp_serializer::to_err_string(err.into()).into(),
+    };
+
+    let len = command_utils::store_sorted_set(&mut mem, destination, result);
+    app.buffer_command(token).await;
+    app.signal_key_ready(&mut mem, destination).await;
+    resp_serializer::to_resp_integer(len as i64).into()
+}
+
+// Parses `numkeys key [key ...] [WEIGHTS weight ...] [AGGREGATE SUM|MIN|MAX]
+// [WITHSCORES]`, where ZDIFF only takes WITHSCORES and the STORE variants
+// never do.
+fn parse_args(
+    args: &[String],
+    operation: SetOperation,
+    allow_with_scores: bool,
+    command: &str,
+) -> Result<SetOpArgs, String> {
+    let numkeys = command_utils::parse_integer(&args[0])
+        .ok_or("ERR value is not an integer or out of range")?;
+    if numkeys <= 0 {
+        return Err(format!(
+            "ERR at least 1 input key is needed for '{}' command",
+            command
+        ));
+    }
+
+    let numkeys = numkeys as usize;
+    let Some(keys) = args.get(1..=numkeys) else {
+        return Err("ERR syntax error".into());
+    };
+
+    let mut opts = SetOpArgs {
+        keys: keys.to_vec(),
+        weights: vec![1.0; numkeys],
+        aggregate: Aggregate::Sum,
+        with_scores: false,
+    };
+
+    let mut options = args[numkeys + 1..].iter();
+    while let Some(option) = options.next() {
+        match option.to_uppercase().as_str() {
+            "WEIGHTS" if operation != SetOperation::Diff => {
+                for weight in opts.weights.iter_mut() {
+                    let value = options.next().ok_or("ERR syntax error")?;
+                    *weight = command_utils::parse_float(value)
+                        .ok_or("ERR weight value is not a float")?;
+                }
+            }
+            "AGGREGATE" if operation != SetOperation::Diff => {
+                let aggregate = options.next().ok_or("ERR syntax error")?;
+                opts.aggregate = match aggregate.to_uppercase().as_str() {
+                    "SUM" => Aggregate::Sum,
+                    "MIN" => Aggregate::Min,
+                    "MAX" => Aggregate::Max,
+                    _ => return Err("ERR syntax error".into()),
+                };
+            }
+            "WITHSCORES" if allow_with_scores => opts.with_scores = true,
+            _ => return Err("ERR syntax error".into()),
+        }
+    }
+
+    Ok(opts)
+}
+
+fn compute(
+    mem: &Keyspace,
+    opts: &SetOpArgs,
+    operation: SetOperation,
+) -> Result<SortedSet, &'static str> {
+    let inputs = get_inputs(mem, &opts.keys)?;
+    let weighted = |score: f64, weight: f64| {
+        // 0 * inf is NaN, which Redis turns into 0.
+        Some(score * weight)
+            .filter(|score| !score.is_nan())
+            .unwrap_or_default()
+    };
+
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    match operation {
+        SetOperation::Union => {
+            for (input, weight) in inputs.iter().zip(&opts.weights) {
+                for (member, score) in input.entries() {
+                    let score = weighted(score, *weight);
+                    scores
+                        .entry(member)
+                        .and_modify(|current| *current = opts.aggregate.apply(*current, score))
+                        .or_insert(score);
+                }
+            }
+        }
+        SetOperation::Inter => {
+            if let Some((first, others)) = inputs.split_first() {
+                for (member, score) in first.entries() {
+                    let mut total = weighted(score, opts.weights[0]);
+                    let in_all = others
+                        .iter()
+                        .zip(&opts.weights[1..])
+                        .all(|(input, weight)| match input.score(&member) {
+                            Some(score) => {
+                                total = opts.aggregate.apply(total, weighted(score, *weight));
+                                true
+                            }
+                            None => false,
+                        });
+                    if in_all {
+                        scores.insert(member, total);
+                    }
+                }
+            }
+        }
+        SetOperation::Diff => {
+            if let Some((first, others)) = inputs.split_first() {
+                for (member, score) in first.entries() {
+                    if others.iter().all(|input| input.score(&member).is_none()) {
+                        scores.insert(member, score);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut result = SortedSet::new();
+    for (member, score) in scores {
+        result.insert(member, score);
+    }
+    Ok(result)
+}
+
+// Looks up every key up front so a WRONGTYPE anywhere fails the whole command.
+fn get_inputs<'a>(mem: &'a Keyspace, keys: &[String]) -> Result<Vec<Input<'a>>, &'static str> {
+    keys.iter()
+        .map(
+            |key| match mem.get(key).filter(|entry| !entry.is_expired()) {
+                None => Ok(Input::Missing),
+                Some(entry) => match &entry.value {
+                    ValueContainer::SortedSet(zset) => Ok(Input::Sorted(zset)),
+                    ValueContainer::Set(set) => Ok(Input::Plain(set)),
+                    _ => Err(command_utils::WRONGTYPE_ERROR),
+                },
+            },
+        )
+        .collect()
+}
+
+fn split_smallest<'a, 'b>(inputs: &'b [Input<'a>]) -> Option<(&'b Input<'a>, Vec<&'b Input<'a>>)> {
+    let smallest = (0..inputs.len()).min_by_key(|idx| inputs[*idx].len())?;
+    let others = inputs
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != smallest)
+        .map(|(_, input)| input)
+        .collect();
+    Some((&inputs[smallest], others))
+}
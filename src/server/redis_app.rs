@@ -1,6 +1,7 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::HashMap,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use tokio::{
@@ -13,22 +14,35 @@ use crate::{
     rdb::rdb_loader,
     resp_desserializer::RespTk,
     types::{
-        entry_value::EntryValue, instance_type::InstanceType, redis_replica::RedisReplica,
-        redis_settings::RedisSettings, stream_key::StreamKey, transactions::TransactionMap,
+        connection_context::ConnectionContext,
+        entry_value::EntryValue,
+        instance_type::InstanceType,
+        keyspace::Keyspace,
+        redis_replica::RedisReplica,
+        redis_settings::RedisSettings,
+        search_index::SearchRegistry,
+        stream_key::StreamKey,
+        transactions::TransactionMap,
         value_container::ValueContainer,
+        waiter_registry::{WaiterGuard, WaiterHandler, WaiterRegistry},
     },
     utils,
 };
 
+const ACTIVE_EXPIRE_KEYS_PER_LOOP: usize = 20;
+const ACTIVE_EXPIRE_TIME_LIMIT: Duration = Duration::from_millis(25);
+
 type ActionDefer = fn(app: Arc<RedisApp>) -> String;
 
 #[derive(Debug)]
 pub struct RedisApp {
-    pub memory: Mutex<HashMap<String, EntryValue>>,
+    pub memory: Mutex<Keyspace>,
     pub transactions: Mutex<TransactionMap>,
     pub settings: RedisSettings,
     pub replicas: Mutex<Vec<RedisReplica>>,
     pub replication_buffer: Mutex<Vec<RespTk>>,
+    pub waiters: std::sync::Mutex<WaiterRegistry>,
+    pub search_indexes: std::sync::Mutex<SearchRegistry>,
 }
 
 impl RedisApp {
@@ -45,6 +59,8 @@ impl RedisApp {
             settings: settings,
             replicas: Mutex::new(Vec::new()),
             replication_buffer: Mutex::new(Vec::new()),
+            waiters: std::sync::Mutex::new(WaiterRegistry::new()),
+            search_indexes: std::sync::Mutex::new(SearchRegistry::new()),
         }
     }
 
@@ -67,30 +83,130 @@ impl RedisApp {
         return mem.get(key).and_then(|container| container.get_value());
     }
 
-    pub async fn put_entry(&self, key: String, value: ValueContainer, exp: Option<u128>) {
+    pub async fn add_replica(&self, replica: RedisReplica) {
+        let mut replicas = self.replicas.lock().await;
+        replicas.push(replica);
+    }
+
+    pub async fn buffer_command(&self, cmd: &RespTk) {
+        let mut buffer = self.replication_buffer.lock().await;
+        buffer.push(cmd.clone());
+    }
+
+    // Hands data pushed to `key` to the clients blocked on it, while the
+    // caller still holds the keyspace lock.
+    pub async fn signal_key_ready(&self, mem: &mut Keyspace, key: &str) {
+        let commands = self.lock_waiters().serve(mem, key);
+        for command in &commands {
+            self.buffer_command(command).await;
+        }
+    }
+
+    // Serves a blocking command right away when one of its keys has data.
+    // Otherwise registers it as a waiter and releases the keyspace lock until
+    // a push serves it, the timeout expires or the client disconnects. `None`
+    // blocks forever.
+    pub async fn serve_or_block(
+        &self,
+        keys: Vec<String>,
+        timeout: Option<Duration>,
+        mut handler: WaiterHandler,
+        context: &ConnectionContext,
+    ) -> Option<String> {
         let mut mem = self.memory.lock().await;
+        for key in &keys {
+            if let Some(outcome) = handler(&mut mem, key) {
+                for command in &outcome.commands {
+                    self.buffer_command(command).await;
+                }
+                for ready_key in &outcome.ready_keys {
+                    self.signal_key_ready(&mut mem, ready_key).await;
+                }
+                return Some(outcome.response);
+            }
+        }
 
-        let expires: Option<u128> = match exp {
-            Some(ex) => Some(utils::get_current_time_ms() + ex),
-            None => None,
-        };
+        let (id, receiver) = self.lock_waiters().register(keys, handler);
+        let mut guard = WaiterGuard::new(&self.waiters, id, receiver);
+        drop(mem);
 
-        let entry = EntryValue {
-            value: value,
-            expires_at: expires,
+        let wait = async {
+            match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, guard.recv())
+                    .await
+                    .ok()
+                    .flatten(),
+                None => guard.recv().await,
+            }
+        };
+        let response = tokio::select! {
+            response = wait => response,
+            _ = context.closed() => None,
         };
 
-        _ = mem.insert(key, entry);
+        // The waiter may have been served right as the wait ended.
+        response.or_else(|| guard.finish())
     }
 
-    pub async fn add_replica(&self, replica: RedisReplica) {
-        let mut replicas = self.replicas.lock().await;
-        replicas.push(replica);
+    // Brings the search indexes in line with the keys written since the last
+    // call. Commands don't reindex on their own: this runs after every
+    // command, on each active expire round and before an index is queried.
+    pub fn apply_writes(&self, mem: &mut Keyspace) {
+        let written = mem.take_written();
+        if written.is_empty() {
+            return;
+        }
+
+        let mut indexes = self.lock_search_indexes();
+        for key in &written {
+            let hash = mem
+                .get(key)
+                .filter(|entry| !entry.is_expired())
+                .and_then(|entry| match &entry.value {
+                    ValueContainer::Hash(hash) => Some(hash),
+                    _ => None,
+                });
+            indexes.update_key(key, hash);
+        }
     }
 
-    pub async fn buffer_command(&self, cmd: &RespTk) {
-        let mut buffer = self.replication_buffer.lock().await;
-        buffer.push(cmd.clone());
+    pub fn lock_search_indexes(&self) -> std::sync::MutexGuard<'_, SearchRegistry> {
+        self.search_indexes
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    // Reclaims expired keys and hash fields that were never read again. Like
+    // Redis's active expire cycle, it samples the keys with TTLs in rounds and
+    // goes on while a good share of a round turns out to be expired, within
+    // a time budget so other clients are not stalled.
+    pub async fn remove_expired(&self) {
+        let started = Instant::now();
+        let mut mem = self.memory.lock().await;
+        self.apply_writes(&mut mem);
+
+        loop {
+            let sample = mem.sample_volatile(ACTIVE_EXPIRE_KEYS_PER_LOOP);
+            let mut expired = 0;
+            for key in &sample {
+                if let Some(command) = expire_key(&mut mem, key) {
+                    self.buffer_command(&command).await;
+                    expired += 1;
+                }
+            }
+            self.apply_writes(&mut mem);
+
+            if sample.len() < ACTIVE_EXPIRE_KEYS_PER_LOOP
+                || expired * 4 <= sample.len()
+                || started.elapsed() >= ACTIVE_EXPIRE_TIME_LIMIT
+            {
+                break;
+            }
+        }
+    }
+
+    fn lock_waiters(&self) -> std::sync::MutexGuard<'_, WaiterRegistry> {
+        self.waiters.lock().unwrap_or_else(|err| err.into_inner())
     }
 
     pub async fn broadcast_command(&self) {
@@ -103,7 +219,7 @@ impl RedisApp {
                 for cmd in buffer.iter() {
                     let cmd_resp: String = cmd.into();
                     println!("replicating> {}", cmd_resp.clone());
-                    let bytes = cmd_resp.into_bytes();
+                    let bytes = utils::string_to_bytes(&cmd_resp);
                     let _ = stream.write_all(&bytes).await;
                     let _ = stream.flush().await;
                 }
@@ -121,13 +237,13 @@ impl RedisApp {
         }
     }
 
-    fn init_database(settings: &RedisSettings) -> HashMap<String, EntryValue> {
+    fn init_database(settings: &RedisSettings) -> Keyspace {
         let db = if let (Some(dir), Some(file)) = (&settings.dir, &settings.db_file_name) {
             Self::restore_from_rdb(dir, file)
         } else {
             HashMap::new()
         };
-        db
+        Keyspace::from(db)
     }
 
     fn load_settings_from_args(
@@ -165,15 +281,51 @@ impl RedisApp {
     pub(crate) fn get_last_stream_key(
         &self,
         stream_key: &str,
-        mem: &MutexGuard<HashMap<String, EntryValue>>,
+        mem: &MutexGuard<Keyspace>,
     ) -> Option<StreamKey> {
         let entry = mem.get(stream_key)?;
 
         if let ValueContainer::Stream(stream) = &entry.value {
-            let last = stream.last()?;
-            Some(last.id.clone())
+            stream.last_id()
         } else {
             None
         }
     }
 }
+
+// Removes `key`, or the fields of the hash at `key`, once expired. Returns the
+// command replicating the removal.
+fn expire_key(mem: &mut Keyspace, key: &str) -> Option<RespTk> {
+    let entry = mem.get(key)?;
+    if entry.is_expired() {
+        mem.remove(key);
+        return Some(to_command_token(vec!["DEL".into(), key.to_owned()]));
+    }
+
+    let fields = match &entry.value {
+        ValueContainer::Hash(hash) => hash.expired_fields(),
+        _ => return None,
+    };
+    if fields.is_empty() {
+        return None;
+    }
+
+    let mut now_empty = false;
+    if let Some(ValueContainer::Hash(hash)) = mem.get_mut(key).map(|entry| &mut entry.value) {
+        for field in &fields {
+            hash.remove(field);
+        }
+        now_empty = hash.is_empty();
+    }
+    if now_empty {
+        mem.remove(key);
+    }
+
+    let mut command = vec!["HDEL".into(), key.to_owned()];
+    command.extend(fields);
+    Some(to_command_token(command))
+}
+
+fn to_command_token(args: Vec<String>) -> RespTk {
+    RespTk::Array(args.into_iter().map(RespTk::BulkString).collect())
+}
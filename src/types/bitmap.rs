@@ -0,0 +1,87 @@
+pub fn get_bit(bytes: &[u8], offset: usize) -> u8 {
+    match bytes.get(offset >> 3) {
+        Some(byte) => (byte >> (7 - (offset & 7))) & 1,
+        None => 0,
+    }
+}
+
+pub fn set_bit(bytes: &mut Vec<u8>, offset: usize, bit: u8) -> u8 {
+    let idx = offset >> 3;
+    if bytes.len() <= idx {
+        bytes.resize(idx + 1, 0);
+    }
+
+    let mask = 1 << (7 - (offset & 7));
+    let old = (bytes[idx] & mask != 0) as u8;
+    if bit == 1 {
+        bytes[idx] |= mask;
+    } else {
+        bytes[idx] &= !mask;
+    }
+    old
+}
+
+pub fn count_bits(bytes: &[u8], first_bit: usize, last_bit: usize) -> usize {
+    let first_byte = first_bit >> 3;
+    let last_byte = last_bit >> 3;
+    let head_mask = 0xFFu8 >> (first_bit & 7);
+    let tail_mask = 0xFFu8 << (7 - (last_bit & 7));
+
+    if first_byte == last_byte {
+        return (bytes[first_byte] & head_mask & tail_mask).count_ones() as usize;
+    }
+
+    (bytes[first_byte] & head_mask).count_ones() as usize
+        + popcount(&bytes[first_byte + 1..last_byte])
+        + (bytes[last_byte] & tail_mask).count_ones() as usize
+}
+
+pub fn find_bit(bytes: &[u8], bit: u8, first_bit: usize, last_bit: usize) -> Option<usize> {
+    let skip = if bit == 1 { 0x00 } else { 0xFF };
+    let mut offset = first_bit;
+
+    while offset <= last_bit {
+        let byte_aligned = offset & 7 == 0 && offset + 7 <= last_bit;
+        if byte_aligned && bytes[offset >> 3] == skip {
+            offset += 8;
+            continue;
+        }
+        if get_bit(bytes, offset) == bit {
+            return Some(offset);
+        }
+        offset += 1;
+    }
+
+    None
+}
+
+pub fn get_bits(bytes: &[u8], offset: usize, bits: usize) -> u64 {
+    (0..bits).fold(0u64, |acc, i| {
+        (acc << 1) | get_bit(bytes, offset + i) as u64
+    })
+}
+
+pub fn set_bits(bytes: &mut Vec<u8>, offset: usize, bits: usize, value: u64) {
+    for i in 0..bits {
+        let bit = ((value >> (bits - 1 - i)) & 1) as u8;
+        set_bit(bytes, offset + i, bit);
+    }
+}
+
+fn popcount(bytes: &[u8]) -> usize {
+    let mut chunks = bytes.chunks_exact(8);
+    let mut count: usize = chunks
+        .by_ref()
+        .map(|chunk| {
+            let word = u64::from_ne_bytes(chunk.try_into().unwrap_or_default());
+            word.count_ones() as usize
+        })
+        .sum();
+
+    count += chunks
+        .remainder()
+        .iter()
+        .map(|byte| byte.count_ones() as usize)
+        .sum::<usize>();
+    count
+}
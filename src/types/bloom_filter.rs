@@ -0,0 +1,202 @@
+use std::f64::consts::LN_2;
+
+use crate::utils;
+
+use super::redis_error::RedisError;
+
+pub const DEFAULT_ERROR_RATE: f64 = 0.01;
+pub const DEFAULT_CAPACITY: u64 = 100;
+pub const DEFAULT_EXPANSION: u32 = 2;
+// Each new sub-filter halves the error rate so the compound rate stays
+// bounded by the requested one.
+const TIGHTENING_RATIO: f64 = 0.5;
+const HASH_SEED: u64 = 0xc6a4_a793_5bd1_e995;
+
+#[derive(Debug, Clone)]
+struct SubFilter {
+    capacity: u64,
+    count: u64,
+    hashes: u32,
+    error_rate: f64,
+    num_bits: u64,
+    bits: Vec<u8>,
+}
+
+impl SubFilter {
+    fn new(capacity: u64, error_rate: f64) -> Self {
+        let bits_per_entry = -error_rate.ln() / (LN_2 * LN_2);
+        let num_bits = ((capacity as f64 * bits_per_entry).ceil() as u64).max(8);
+        Self {
+            capacity,
+            count: 0,
+            hashes: (LN_2 * bits_per_entry).ceil().max(1.0) as u32,
+            error_rate,
+            num_bits,
+            bits: vec![0; num_bits.div_ceil(8) as usize],
+        }
+    }
+
+    // Double hashing derives every probe position from two hashes.
+    fn positions(&self, (h1, h2): (u64, u64)) -> impl Iterator<Item = usize> + '_ {
+        (0..self.hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits) as usize)
+    }
+
+    fn contains(&self, hash: (u64, u64)) -> bool {
+        self.positions(hash)
+            .all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    fn insert(&mut self, hash: (u64, u64)) {
+        let positions: Vec<usize> = self.positions(hash).collect();
+        for bit in positions {
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+        self.count += 1;
+    }
+}
+
+// Scalable bloom filter: once a sub-filter reaches its capacity a bigger one
+// with a tighter error rate is stacked on top of it.
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    // None for filters that never grow.
+    expansion: Option<u32>,
+    filters: Vec<SubFilter>,
+}
+
+// BF.ADD and BF.MADD create missing filters with the default parameters.
+impl Default for BloomFilter {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_ERROR_RATE,
+            DEFAULT_CAPACITY,
+            Some(DEFAULT_EXPANSION),
+        )
+    }
+}
+
+impl BloomFilter {
+    pub fn new(error_rate: f64, capacity: u64, expansion: Option<u32>) -> Self {
+        Self {
+            expansion,
+            filters: vec![SubFilter::new(capacity, error_rate)],
+        }
+    }
+
+    pub fn contains(&self, item: &[u8]) -> bool {
+        let hash = hash(item);
+        self.filters.iter().any(|filter| filter.contains(hash))
+    }
+
+    // Returns false when the item may have been added before.
+    pub fn add(&mut self, item: &[u8]) -> Result<bool, &'static str> {
+        let hash = hash(item);
+        if self.filters.iter().any(|filter| filter.contains(hash)) {
+            return Ok(false);
+        }
+
+        let last = self.filters.last().ok_or("ERR not found")?;
+        if last.count >= last.capacity {
+            let expansion = self.expansion.ok_or("ERR non scaling filter is full")?;
+            let filter = SubFilter::new(
+                last.capacity.saturating_mul(expansion as u64),
+                last.error_rate * TIGHTENING_RATIO,
+            );
+            self.filters.push(filter);
+        }
+
+        if let Some(filter) = self.filters.last_mut() {
+            filter.insert(hash);
+        }
+        Ok(true)
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.filters.iter().map(|filter| filter.capacity).sum()
+    }
+
+    // Memory used by the bit arrays and their bookkeeping, in bytes.
+    pub fn size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self
+                .filters
+                .iter()
+                .map(|filter| std::mem::size_of::<SubFilter>() + filter.bits.len())
+                .sum::<usize>()
+    }
+
+    pub fn filter_count(&self) -> usize {
+        self.filters.len()
+    }
+
+    pub fn items(&self) -> u64 {
+        self.filters.iter().map(|filter| filter.count).sum()
+    }
+
+    pub fn expansion(&self) -> Option<u32> {
+        self.expansion
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&self.expansion.unwrap_or(0).to_le_bytes());
+        bytes.extend_from_slice(&(self.filters.len() as u32).to_le_bytes());
+        for filter in &self.filters {
+            bytes.extend_from_slice(&filter.capacity.to_le_bytes());
+            bytes.extend_from_slice(&filter.count.to_le_bytes());
+            bytes.extend_from_slice(&filter.hashes.to_le_bytes());
+            bytes.extend_from_slice(&filter.error_rate.to_le_bytes());
+            bytes.extend_from_slice(&filter.num_bits.to_le_bytes());
+            bytes.extend_from_slice(&filter.bits);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RedisError> {
+        Self::decode(bytes).ok_or(RedisError::RDBInvalidFilter)
+    }
+
+    fn decode(mut bytes: &[u8]) -> Option<Self> {
+        let bytes = &mut bytes;
+        let expansion = u32::from_le_bytes(utils::take_bytes(bytes)?);
+        let len = u32::from_le_bytes(utils::take_bytes(bytes)?);
+
+        let mut filters = vec![];
+        for _ in 0..len {
+            let capacity = u64::from_le_bytes(utils::take_bytes(bytes)?);
+            let count = u64::from_le_bytes(utils::take_bytes(bytes)?);
+            let hashes = u32::from_le_bytes(utils::take_bytes(bytes)?);
+            let error_rate = f64::from_le_bytes(utils::take_bytes(bytes)?);
+            let num_bits = u64::from_le_bytes(utils::take_bytes(bytes)?);
+            let byte_len = num_bits.div_ceil(8) as usize;
+            if num_bits == 0 || bytes.len() < byte_len {
+                return None;
+            }
+
+            let (bits, rest) = bytes.split_at(byte_len);
+            filters.push(SubFilter {
+                capacity,
+                count,
+                hashes,
+                error_rate,
+                num_bits,
+                bits: bits.to_vec(),
+            });
+            *bytes = rest;
+        }
+
+        if filters.is_empty() || !bytes.is_empty() {
+            return None;
+        }
+        Some(Self {
+            expansion: (expansion > 0).then_some(expansion),
+            filters,
+        })
+    }
+}
+
+fn hash(item: &[u8]) -> (u64, u64) {
+    let h1 = utils::murmur_hash64a(item, HASH_SEED);
+    (h1, utils::murmur_hash64a(item, h1))
+}
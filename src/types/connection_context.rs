@@ -1,14 +1,28 @@
+use tokio::sync::watch;
+
 #[derive(Clone)]
 pub struct ConnectionContext {
     pub connection_id: u64,
     pub client_address: String,
+    closed: watch::Receiver<bool>,
 }
 
 impl ConnectionContext {
-    pub fn new(connection_id: u64, client_address: String) -> Self {
+    pub fn new(connection_id: u64, client_address: String, closed: watch::Receiver<bool>) -> Self {
         Self {
             connection_id,
             client_address,
+            closed,
+        }
+    }
+
+    // Resolves once the client has gone away.
+    pub async fn closed(&self) {
+        let mut closed = self.closed.clone();
+        while !*closed.borrow() {
+            if closed.changed().await.is_err() {
+                return;
+            }
         }
     }
 }
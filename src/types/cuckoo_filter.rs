@@ -0,0 +1,240 @@
+use crate::utils;
+
+use super::redis_error::RedisError;
+
+pub const DEFAULT_CAPACITY: u64 = 1024;
+pub const DEFAULT_BUCKET_SIZE: usize = 2;
+pub const DEFAULT_MAX_ITERATIONS: usize = 20;
+pub const DEFAULT_EXPANSION: u64 = 1;
+const HASH_SEED: u64 = 0xc6a4_a793_5bd1_e995;
+// Multiplier mixing a fingerprint into the offset of its alternate bucket.
+const ALT_BUCKET_MIX: u64 = 0x5bd1_e995;
+// Fingerprints are never 0, which marks an empty slot.
+const EMPTY: u8 = 0;
+
+#[derive(Debug, Clone)]
+struct SubFilter {
+    // Always a power of two so bucket indexes can be masked.
+    num_buckets: u64,
+    slots: Vec<u8>,
+}
+
+impl SubFilter {
+    fn new(num_buckets: u64, bucket_size: usize) -> Self {
+        Self {
+            num_buckets,
+            slots: vec![EMPTY; num_buckets as usize * bucket_size],
+        }
+    }
+
+    fn index(&self, hash: u64) -> usize {
+        ((hash >> 32) & (self.num_buckets - 1)) as usize
+    }
+
+    fn alt_index(&self, index: usize, fingerprint: u8) -> usize {
+        ((index as u64 ^ (fingerprint as u64).wrapping_mul(ALT_BUCKET_MIX))
+            & (self.num_buckets - 1)) as usize
+    }
+
+    fn buckets(&self, hash: u64, fingerprint: u8) -> [usize; 2] {
+        let index = self.index(hash);
+        [index, self.alt_index(index, fingerprint)]
+    }
+}
+
+// Cuckoo filter storing one byte fingerprints in buckets of `bucket_size`
+// slots. Unlike a bloom filter items can be deleted again; when a table gets
+// too full to relocate fingerprints a bigger one is stacked on top of it.
+#[derive(Debug, Clone)]
+pub struct CuckooFilter {
+    bucket_size: usize,
+    max_iterations: usize,
+    expansion: u64,
+    items: u64,
+    filters: Vec<SubFilter>,
+}
+
+impl Default for CuckooFilter {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_CAPACITY,
+            DEFAULT_BUCKET_SIZE,
+            DEFAULT_MAX_ITERATIONS,
+            DEFAULT_EXPANSION,
+        )
+    }
+}
+
+impl CuckooFilter {
+    pub fn new(capacity: u64, bucket_size: usize, max_iterations: usize, expansion: u64) -> Self {
+        let num_buckets = capacity
+            .div_ceil(bucket_size as u64)
+            .max(1)
+            .next_power_of_two();
+        Self {
+            bucket_size,
+            max_iterations,
+            expansion,
+            items: 0,
+            filters: vec![SubFilter::new(num_buckets, bucket_size)],
+        }
+    }
+
+    pub fn add(&mut self, item: &[u8]) -> Result<(), &'static str> {
+        let (hash, fingerprint) = fingerprint(item);
+        if !self.insert(hash, fingerprint) {
+            if self.expansion == 0 {
+                return Err("ERR Filter is full");
+            }
+
+            let num_buckets = self.filters.last().map_or(1, |filter| filter.num_buckets);
+            let num_buckets = num_buckets
+                .saturating_mul(self.expansion)
+                .next_power_of_two();
+            self.filters
+                .push(SubFilter::new(num_buckets, self.bucket_size));
+            if !self.insert(hash, fingerprint) {
+                return Err("ERR Filter is full");
+            }
+        }
+        self.items += 1;
+        Ok(())
+    }
+
+    pub fn contains(&self, item: &[u8]) -> bool {
+        let (hash, fingerprint) = fingerprint(item);
+        self.filters.iter().any(|filter| {
+            filter
+                .buckets(hash, fingerprint)
+                .iter()
+                .any(|&bucket| self.bucket(filter, bucket).contains(&fingerprint))
+        })
+    }
+
+    // Removes one copy of the item, newest sub-filter first.
+    pub fn delete(&mut self, item: &[u8]) -> bool {
+        let (hash, fingerprint) = fingerprint(item);
+        let bucket_size = self.bucket_size;
+
+        for filter in self.filters.iter_mut().rev() {
+            for bucket in filter.buckets(hash, fingerprint) {
+                let slots = &mut filter.slots[bucket * bucket_size..(bucket + 1) * bucket_size];
+                if let Some(slot) = slots.iter_mut().find(|slot| **slot == fingerprint) {
+                    *slot = EMPTY;
+                    self.items -= 1;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    pub fn items(&self) -> u64 {
+        self.items
+    }
+
+    fn bucket<'a>(&self, filter: &'a SubFilter, bucket: usize) -> &'a [u8] {
+        &filter.slots[bucket * self.bucket_size..(bucket + 1) * self.bucket_size]
+    }
+
+    // Places the fingerprint in the newest sub-filter, kicking existing
+    // fingerprints to their alternate bucket when both buckets are full.
+    fn insert(&mut self, hash: u64, fingerprint: u8) -> bool {
+        let bucket_size = self.bucket_size;
+        let Some(filter) = self.filters.last_mut() else {
+            return false;
+        };
+
+        for bucket in filter.buckets(hash, fingerprint) {
+            let slots = &mut filter.slots[bucket * bucket_size..(bucket + 1) * bucket_size];
+            if let Some(slot) = slots.iter_mut().find(|slot| **slot == EMPTY) {
+                *slot = fingerprint;
+                return true;
+            }
+        }
+
+        let mut bucket = filter.buckets(hash, fingerprint)[utils::random_index(2)];
+        let mut fingerprint = fingerprint;
+        let mut swaps = vec![];
+        for _ in 0..self.max_iterations {
+            let slot = bucket * bucket_size + utils::random_index(bucket_size);
+            std::mem::swap(&mut fingerprint, &mut filter.slots[slot]);
+            swaps.push(slot);
+
+            bucket = filter.alt_index(bucket, fingerprint);
+            let slots = &mut filter.slots[bucket * bucket_size..(bucket + 1) * bucket_size];
+            if let Some(slot) = slots.iter_mut().find(|slot| **slot == EMPTY) {
+                *slot = fingerprint;
+                return true;
+            }
+        }
+
+        // Put every kicked fingerprint back so nothing already stored is lost.
+        for slot in swaps.into_iter().rev() {
+            std::mem::swap(&mut fingerprint, &mut filter.slots[slot]);
+        }
+        false
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&(self.bucket_size as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.max_iterations as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.expansion.to_le_bytes());
+        bytes.extend_from_slice(&self.items.to_le_bytes());
+        bytes.extend_from_slice(&(self.filters.len() as u32).to_le_bytes());
+        for filter in &self.filters {
+            bytes.extend_from_slice(&filter.num_buckets.to_le_bytes());
+            bytes.extend_from_slice(&filter.slots);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RedisError> {
+        Self::decode(bytes).ok_or(RedisError::RDBInvalidFilter)
+    }
+
+    fn decode(mut bytes: &[u8]) -> Option<Self> {
+        let bytes = &mut bytes;
+        let bucket_size = u64::from_le_bytes(utils::take_bytes(bytes)?) as usize;
+        let max_iterations = u64::from_le_bytes(utils::take_bytes(bytes)?) as usize;
+        let expansion = u64::from_le_bytes(utils::take_bytes(bytes)?);
+        let items = u64::from_le_bytes(utils::take_bytes(bytes)?);
+        let len = u32::from_le_bytes(utils::take_bytes(bytes)?);
+        if bucket_size == 0 {
+            return None;
+        }
+
+        let mut filters = vec![];
+        for _ in 0..len {
+            let num_buckets = u64::from_le_bytes(utils::take_bytes(bytes)?);
+            let slot_count = (num_buckets as usize).checked_mul(bucket_size)?;
+            if !num_buckets.is_power_of_two() || bytes.len() < slot_count {
+                return None;
+            }
+
+            let (slots, rest) = bytes.split_at(slot_count);
+            filters.push(SubFilter {
+                num_buckets,
+                slots: slots.to_vec(),
+            });
+            *bytes = rest;
+        }
+
+        if filters.is_empty() || !bytes.is_empty() {
+            return None;
+        }
+        Some(Self {
+            bucket_size,
+            max_iterations,
+            expansion,
+            items,
+            filters,
+        })
+    }
+}
+
+fn fingerprint(item: &[u8]) -> (u64, u8) {
+    let hash = utils::murmur_hash64a(item, HASH_SEED);
+    (hash, (hash % 255 + 1) as u8)
+}
@@ -19,4 +19,9 @@ impl EntryValue {
         }
         return Some(self.value.clone());
     }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|exp| utils::get_current_time_ms() >= exp)
+    }
 }
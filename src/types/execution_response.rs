@@ -1,3 +1,5 @@
+use crate::utils;
+
 pub enum ExecResponse {
     Simple(Vec<u8>),
     Multi(Vec<Vec<u8>>),
@@ -5,13 +7,13 @@ pub enum ExecResponse {
 
 impl From<String> for ExecResponse {
     fn from(value: String) -> Self {
-        Self::Simple(value.into_bytes())
+        Self::Simple(utils::string_to_bytes(&value))
     }
 }
 
 impl From<&String> for ExecResponse {
     fn from(value: &String) -> Self {
-        Self::Simple(value.clone().into_bytes())
+        Self::Simple(utils::string_to_bytes(value))
     }
 }
 
@@ -29,7 +31,7 @@ impl From<&Vec<u8>> for ExecResponse {
 
 impl From<Vec<String>> for ExecResponse {
     fn from(value: Vec<String>) -> Self {
-        let res: Vec<Vec<u8>> = value.iter().map(|i| i.clone().into_bytes()).collect();
+        let res: Vec<Vec<u8>> = value.iter().map(|i| utils::string_to_bytes(i)).collect();
         Self::Multi(res)
     }
 }
@@ -0,0 +1,389 @@
+const GEO_STEP_MAX: u8 = 26;
+const GEO_LAT_MIN: f64 = -85.05112878;
+const GEO_LAT_MAX: f64 = 85.05112878;
+const GEO_LONG_MIN: f64 = -180.0;
+const GEO_LONG_MAX: f64 = 180.0;
+const EARTH_RADIUS_IN_METERS: f64 = 6372797.560856;
+const MERCATOR_MAX: f64 = 20037726.37;
+const GEO_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+#[derive(Debug, Clone, Copy)]
+struct Range {
+    min: f64,
+    max: f64,
+}
+
+const LONG_RANGE: Range = Range {
+    min: GEO_LONG_MIN,
+    max: GEO_LONG_MAX,
+};
+const LAT_RANGE: Range = Range {
+    min: GEO_LAT_MIN,
+    max: GEO_LAT_MAX,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HashBits {
+    bits: u64,
+    step: u8,
+}
+
+struct Area {
+    longitude: Range,
+    latitude: Range,
+}
+
+// Area searched by GEOSEARCH, with every length already in meters.
+#[derive(Debug, Clone, Copy)]
+pub enum GeoShape {
+    Radius(f64),
+    Box { width: f64, height: f64 },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeoSearch {
+    pub longitude: f64,
+    pub latitude: f64,
+    pub shape: GeoShape,
+}
+
+pub fn is_valid_position(longitude: f64, latitude: f64) -> bool {
+    (GEO_LONG_MIN..=GEO_LONG_MAX).contains(&longitude)
+        && (GEO_LAT_MIN..=GEO_LAT_MAX).contains(&latitude)
+}
+
+// Encodes a position as the 52-bit interleaved geohash Redis stores as the
+// member's sorted set score.
+pub fn encode(longitude: f64, latitude: f64) -> Option<u64> {
+    encode_with(LONG_RANGE, LAT_RANGE, longitude, latitude, GEO_STEP_MAX).map(|hash| hash.bits)
+}
+
+// Returns the center of the cell a score points to as (longitude, latitude).
+pub fn decode(score: u64) -> (f64, f64) {
+    let area = decode_area(HashBits {
+        bits: score,
+        step: GEO_STEP_MAX,
+    });
+
+    let longitude =
+        ((area.longitude.min + area.longitude.max) / 2.0).clamp(GEO_LONG_MIN, GEO_LONG_MAX);
+    let latitude = ((area.latitude.min + area.latitude.max) / 2.0).clamp(GEO_LAT_MIN, GEO_LAT_MAX);
+    (longitude, latitude)
+}
+
+// Standard 11 character geohash. Scores use -85..85 as the latitude range, so
+// the position is re-encoded with the usual -90..90 before converting.
+pub fn to_geohash_string(longitude: f64, latitude: f64) -> String {
+    let standard_lat = Range {
+        min: -90.0,
+        max: 90.0,
+    };
+    let bits = encode_with(LONG_RANGE, standard_lat, longitude, latitude, GEO_STEP_MAX)
+        .map(|hash| hash.bits)
+        .unwrap_or_default();
+
+    (0..11)
+        .map(|idx| {
+            // Only 52 bits are available, the last character is always zero.
+            let value = match idx {
+                10 => 0,
+                _ => (bits >> (52 - (idx + 1) * 5)) & 0x1f,
+            };
+            GEO_ALPHABET[value as usize] as char
+        })
+        .collect()
+}
+
+// Haversine distance in meters.
+pub fn distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let v = ((lon2.to_radians() - lon1.to_radians()) / 2.0).sin();
+    if v == 0.0 {
+        return lat_distance(lat1, lat2);
+    }
+
+    let lat1 = lat1.to_radians();
+    let lat2 = lat2.to_radians();
+    let u = ((lat2 - lat1) / 2.0).sin();
+    let a = u * u + lat1.cos() * lat2.cos() * v * v;
+    2.0 * EARTH_RADIUS_IN_METERS * a.sqrt().asin()
+}
+
+fn lat_distance(lat1: f64, lat2: f64) -> f64 {
+    EARTH_RADIUS_IN_METERS * (lat2.to_radians() - lat1.to_radians()).abs()
+}
+
+impl GeoSearch {
+    // Distance from the center when the position falls inside the shape.
+    pub fn distance_if_within(&self, longitude: f64, latitude: f64) -> Option<f64> {
+        match self.shape {
+            GeoShape::Radius(radius) => {
+                let dist = distance(self.longitude, self.latitude, longitude, latitude);
+                (dist <= radius).then_some(dist)
+            }
+            GeoShape::Box { width, height } => {
+                if lat_distance(latitude, self.latitude) > height / 2.0 {
+                    return None;
+                }
+                if distance(longitude, latitude, self.longitude, latitude) > width / 2.0 {
+                    return None;
+                }
+                Some(distance(self.longitude, self.latitude, longitude, latitude))
+            }
+        }
+    }
+
+    // Score ranges, as [min, max), of the cell holding the center and of its
+    // neighbours that may overlap the shape. Every match lies in one of them.
+    pub fn score_ranges(&self) -> Vec<(u64, u64)> {
+        let (min_lon, min_lat, max_lon, max_lat) = self.bounding_box();
+        let radius = match self.shape {
+            GeoShape::Radius(radius) => radius,
+            GeoShape::Box { width, height } => {
+                ((width / 2.0).powi(2) + (height / 2.0).powi(2)).sqrt()
+            }
+        };
+
+        let mut step = estimate_steps_by_radius(radius, self.latitude);
+        let mut hash = self.hash_at(step);
+        let mut neighbors = Neighbors::of(hash);
+
+        // Near the edge of the center cell a neighbour may be too small to
+        // cover the shape, use cells twice as big then.
+        let too_small = decode_area(neighbors.north).latitude.max < max_lat
+            || decode_area(neighbors.south).latitude.min > min_lat
+            || decode_area(neighbors.east).longitude.max < max_lon
+            || decode_area(neighbors.west).longitude.min > min_lon;
+        if step > 1 && too_small {
+            step -= 1;
+            hash = self.hash_at(step);
+            neighbors = Neighbors::of(hash);
+        }
+
+        let area = decode_area(hash);
+        let mut cells = vec![Some(hash)];
+        let mut add = |cell: HashBits, useless: bool| {
+            cells.push((step < 2 || !useless).then_some(cell));
+        };
+
+        let south = area.latitude.min < min_lat;
+        let north = area.latitude.max > max_lat;
+        let west = area.longitude.min < min_lon;
+        let east = area.longitude.max > max_lon;
+        add(neighbors.north, north);
+        add(neighbors.south, south);
+        add(neighbors.east, east);
+        add(neighbors.west, west);
+        add(neighbors.north_east, north || east);
+        add(neighbors.south_east, south || east);
+        add(neighbors.north_west, north || west);
+        add(neighbors.south_west, south || west);
+
+        let mut ranges: Vec<(u64, u64)> = Vec::with_capacity(cells.len());
+        for cell in cells.into_iter().flatten() {
+            let shift = 52 - cell.step as u32 * 2;
+            let range = (cell.bits << shift, (cell.bits + 1) << shift);
+            // Cells repeat when the steps are big enough to wrap around.
+            if !ranges.contains(&range) {
+                ranges.push(range);
+            }
+        }
+        ranges
+    }
+
+    fn hash_at(&self, step: u8) -> HashBits {
+        encode_with(LONG_RANGE, LAT_RANGE, self.longitude, self.latitude, step)
+            .unwrap_or(HashBits { bits: 0, step })
+    }
+
+    fn bounding_box(&self) -> (f64, f64, f64, f64) {
+        let (width, height) = match self.shape {
+            GeoShape::Radius(radius) => (radius, radius),
+            GeoShape::Box { width, height } => (width / 2.0, height / 2.0),
+        };
+
+        let lat_delta = (height / EARTH_RADIUS_IN_METERS).to_degrees();
+        let long_delta_top =
+            (width / EARTH_RADIUS_IN_METERS / (self.latitude + lat_delta).to_radians().cos())
+                .to_degrees();
+        let long_delta_bottom =
+            (width / EARTH_RADIUS_IN_METERS / (self.latitude - lat_delta).to_radians().cos())
+                .to_degrees();
+
+        // The widest edge is the one closer to the equator.
+        let long_delta = match self.latitude < 0.0 {
+            true => long_delta_bottom,
+            false => long_delta_top,
+        };
+        (
+            self.longitude - long_delta,
+            self.latitude - lat_delta,
+            self.longitude + long_delta,
+            self.latitude + lat_delta,
+        )
+    }
+}
+
+struct Neighbors {
+    north: HashBits,
+    south: HashBits,
+    east: HashBits,
+    west: HashBits,
+    north_east: HashBits,
+    south_east: HashBits,
+    north_west: HashBits,
+    south_west: HashBits,
+}
+
+impl Neighbors {
+    fn of(hash: HashBits) -> Self {
+        let moved = |dx: i8, dy: i8| move_y(move_x(hash, dx), dy);
+        Self {
+            north: moved(0, 1),
+            south: moved(0, -1),
+            east: moved(1, 0),
+            west: moved(-1, 0),
+            north_east: moved(1, 1),
+            south_east: moved(1, -1),
+            north_west: moved(-1, 1),
+            south_west: moved(-1, -1),
+        }
+    }
+}
+
+fn estimate_steps_by_radius(mut range: f64, latitude: f64) -> u8 {
+    if range == 0.0 {
+        return GEO_STEP_MAX;
+    }
+
+    let mut step: i32 = 1;
+    while range < MERCATOR_MAX {
+        range *= 2.0;
+        step += 1;
+    }
+    // Make sure the range is included in most of the base cases.
+    step -= 2;
+
+    // Cells get narrower towards the poles.
+    if !(-66.0..=66.0).contains(&latitude) {
+        step -= 1;
+        if !(-80.0..=80.0).contains(&latitude) {
+            step -= 1;
+        }
+    }
+
+    step.clamp(1, GEO_STEP_MAX as i32) as u8
+}
+
+fn encode_with(
+    long_range: Range,
+    lat_range: Range,
+    longitude: f64,
+    latitude: f64,
+    step: u8,
+) -> Option<HashBits> {
+    if !is_valid_position(longitude, latitude)
+        || longitude < long_range.min
+        || longitude > long_range.max
+        || latitude < lat_range.min
+        || latitude > lat_range.max
+    {
+        return None;
+    }
+
+    let cells = (1u64 << step) as f64;
+    let lat_offset = (latitude - lat_range.min) / (lat_range.max - lat_range.min) * cells;
+    let long_offset = (longitude - long_range.min) / (long_range.max - long_range.min) * cells;
+    Some(HashBits {
+        bits: interleave(lat_offset as u32, long_offset as u32),
+        step,
+    })
+}
+
+fn decode_area(hash: HashBits) -> Area {
+    let (lat_cell, long_cell) = deinterleave(hash.bits);
+    let cells = (1u64 << hash.step) as f64;
+    let lat_scale = LAT_RANGE.max - LAT_RANGE.min;
+    let long_scale = LONG_RANGE.max - LONG_RANGE.min;
+
+    Area {
+        latitude: Range {
+            min: LAT_RANGE.min + (lat_cell as f64 / cells) * lat_scale,
+            max: LAT_RANGE.min + ((lat_cell as f64 + 1.0) / cells) * lat_scale,
+        },
+        longitude: Range {
+            min: LONG_RANGE.min + (long_cell as f64 / cells) * long_scale,
+            max: LONG_RANGE.min + ((long_cell as f64 + 1.0) / cells) * long_scale,
+        },
+    }
+}
+
+// Latitude bits go to the even positions and longitude bits to the odd ones.
+fn interleave(lat: u32, long: u32) -> u64 {
+    (spread(lat)) | (spread(long) << 1)
+}
+
+fn deinterleave(bits: u64) -> (u32, u32) {
+    (squash(bits), squash(bits >> 1))
+}
+
+fn spread(value: u32) -> u64 {
+    let mut value = value as u64;
+    value = (value | (value << 16)) & 0x0000_FFFF_0000_FFFF;
+    value = (value | (value << 8)) & 0x00FF_00FF_00FF_00FF;
+    value = (value | (value << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | (value << 2)) & 0x3333_3333_3333_3333;
+    (value | (value << 1)) & 0x5555_5555_5555_5555
+}
+
+fn squash(bits: u64) -> u32 {
+    let mut value = bits & 0x5555_5555_5555_5555;
+    value = (value | (value >> 1)) & 0x3333_3333_3333_3333;
+    value = (value | (value >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | (value >> 4)) & 0x00FF_00FF_00FF_00FF;
+    value = (value | (value >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((value | (value >> 16)) & 0x0000_0000_FFFF_FFFF) as u32
+}
+
+// Moves a cell one step east (positive) or west, wrapping around.
+fn move_x(hash: HashBits, direction: i8) -> HashBits {
+    if direction == 0 {
+        return hash;
+    }
+
+    let shift = 64 - hash.step as u32 * 2;
+    let mut x = hash.bits & 0xaaaa_aaaa_aaaa_aaaa;
+    let y = hash.bits & 0x5555_5555_5555_5555;
+    let zz = 0x5555_5555_5555_5555u64 >> shift;
+
+    x = match direction > 0 {
+        true => x.wrapping_add(zz + 1),
+        false => (x | zz).wrapping_sub(zz + 1),
+    };
+    x &= 0xaaaa_aaaa_aaaa_aaaau64 >> shift;
+    HashBits {
+        bits: x | y,
+        step: hash.step,
+    }
+}
+
+// Moves a cell one step north (positive) or south, wrapping around.
+fn move_y(hash: HashBits, direction: i8) -> HashBits {
+    if direction == 0 {
+        return hash;
+    }
+
+    let shift = 64 - hash.step as u32 * 2;
+    let x = hash.bits & 0xaaaa_aaaa_aaaa_aaaa;
+    let mut y = hash.bits & 0x5555_5555_5555_5555;
+    let zz = 0xaaaa_aaaa_aaaa_aaaau64 >> shift;
+
+    y = match direction > 0 {
+        true => y.wrapping_add(zz + 1),
+        false => (y | zz).wrapping_sub(zz + 1),
+    };
+    y &= 0x5555_5555_5555_5555u64 >> shift;
+    HashBits {
+        bits: x | y,
+        step: hash.step,
+    }
+}
@@ -0,0 +1,218 @@
+// Gorilla compression for time series chunks: timestamps are stored as
+// delta-of-deltas and values as the XOR against the previous value, so
+// regular intervals and slowly changing values take a few bits per sample.
+
+#[derive(Debug, Clone, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn push(&mut self, value: u64, bits: u32) {
+        for shift in (0..bits).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> shift) & 1 == 1 {
+                self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, bits: u32) -> Option<u64> {
+        let mut value = 0;
+        for _ in 0..bits {
+            let byte = self.bytes.get(self.pos / 8)?;
+            value = (value << 1) | ((byte >> (7 - self.pos % 8)) & 1) as u64;
+            self.pos += 1;
+        }
+        Some(value)
+    }
+
+    fn read_bit(&mut self) -> Option<bool> {
+        self.read(1).map(|bit| bit == 1)
+    }
+}
+
+// Control bit prefixes and payload widths for the delta-of-delta buckets.
+const DOD_BUCKETS: [(u64, u32, u32); 3] = [(0b10, 2, 7), (0b110, 3, 9), (0b1110, 4, 12)];
+
+#[derive(Debug, Clone, Default)]
+pub struct Chunk {
+    bits: BitWriter,
+    count: usize,
+    first_timestamp: u64,
+    last_timestamp: u64,
+    last_delta: i64,
+    last_value: u64,
+    leading: u32,
+    trailing: u32,
+}
+
+impl Chunk {
+    pub fn from_samples(samples: &[(u64, f64)]) -> Self {
+        let mut chunk = Chunk::default();
+        for &(timestamp, value) in samples {
+            chunk.push(timestamp, value);
+        }
+        chunk
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn first_timestamp(&self) -> u64 {
+        self.first_timestamp
+    }
+
+    pub fn last_timestamp(&self) -> u64 {
+        self.last_timestamp
+    }
+
+    // Appends a sample, whose timestamp must be past the last one.
+    pub fn push(&mut self, timestamp: u64, value: f64) {
+        let value = value.to_bits();
+        if self.count == 0 {
+            self.bits.push(timestamp, 64);
+            self.bits.push(value, 64);
+            self.first_timestamp = timestamp;
+        } else {
+            self.push_timestamp(timestamp);
+            self.push_value(value);
+        }
+        self.last_timestamp = timestamp;
+        self.last_value = value;
+        self.count += 1;
+    }
+
+    fn push_timestamp(&mut self, timestamp: u64) {
+        let delta = timestamp.wrapping_sub(self.last_timestamp) as i64;
+        let dod = delta.wrapping_sub(self.last_delta);
+        self.last_delta = delta;
+
+        if dod == 0 {
+            self.bits.push(0, 1);
+            return;
+        }
+        for (prefix, prefix_bits, bits) in DOD_BUCKETS {
+            let limit = 1i64 << (bits - 1);
+            if (-limit..limit).contains(&dod) {
+                self.bits.push(prefix, prefix_bits);
+                self.bits.push(dod as u64 & ((1 << bits) - 1), bits);
+                return;
+            }
+        }
+        self.bits.push(0b1111, 4);
+        self.bits.push(dod as u64, 64);
+    }
+
+    fn push_value(&mut self, value: u64) {
+        let xor = value ^ self.last_value;
+        if xor == 0 {
+            self.bits.push(0, 1);
+            return;
+        }
+
+        let leading = xor.leading_zeros().min(63);
+        let trailing = xor.trailing_zeros();
+        let has_window = self.leading + self.trailing > 0;
+        if has_window && leading >= self.leading && trailing >= self.trailing {
+            // The meaningful bits fit in the previous window.
+            self.bits.push(0b10, 2);
+            self.bits
+                .push(xor >> self.trailing, 64 - self.leading - self.trailing);
+            return;
+        }
+
+        let meaningful = 64 - leading - trailing;
+        self.bits.push(0b11, 2);
+        self.bits.push(leading as u64, 6);
+        self.bits.push((meaningful - 1) as u64, 6);
+        self.bits.push(xor >> trailing, meaningful);
+        self.leading = leading;
+        self.trailing = trailing;
+    }
+
+    pub fn samples(&self) -> Vec<(u64, f64)> {
+        decode(&self.bits.bytes, self.count).unwrap_or_default()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.count as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(self.bits.bytes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.bits.bytes);
+        bytes
+    }
+
+    // Reads a chunk written by `to_bytes` off the front of `bytes`.
+    pub fn from_bytes(bytes: &mut &[u8]) -> Option<Chunk> {
+        let count = u32::from_le_bytes(crate::utils::take_bytes(bytes)?) as usize;
+        let len = u32::from_le_bytes(crate::utils::take_bytes(bytes)?) as usize;
+        if bytes.len() < len {
+            return None;
+        }
+
+        let (data, rest) = bytes.split_at(len);
+        *bytes = rest;
+        Some(Chunk::from_samples(&decode(data, count)?))
+    }
+}
+
+fn decode(bytes: &[u8], count: usize) -> Option<Vec<(u64, f64)>> {
+    let mut reader = BitReader { bytes, pos: 0 };
+    let mut samples = Vec::new();
+    if count == 0 {
+        return Some(samples);
+    }
+
+    let mut timestamp = reader.read(64)?;
+    let mut value = reader.read(64)?;
+    let mut delta = 0i64;
+    let (mut leading, mut trailing) = (0, 0);
+    samples.push((timestamp, f64::from_bits(value)));
+
+    while samples.len() < count {
+        let mut prefix_bits = 0;
+        while prefix_bits < 4 && reader.read_bit()? {
+            prefix_bits += 1;
+        }
+        let dod = match prefix_bits {
+            0 => 0,
+            4 => reader.read(64)? as i64,
+            n => {
+                let bits = DOD_BUCKETS[n - 1].2;
+                let raw = reader.read(bits)?;
+                // Sign extend the two's complement payload.
+                ((raw << (64 - bits)) as i64) >> (64 - bits)
+            }
+        };
+        delta = delta.wrapping_add(dod);
+        timestamp = timestamp.wrapping_add(delta as u64);
+
+        if reader.read_bit()? {
+            if reader.read_bit()? {
+                leading = reader.read(6)? as u32;
+                let meaningful = reader.read(6)? as u32 + 1;
+                trailing = 64u32.checked_sub(leading + meaningful)?;
+            }
+            let meaningful = 64 - leading - trailing;
+            value ^= reader.read(meaningful)? << trailing;
+        }
+        samples.push((timestamp, f64::from_bits(value)));
+    }
+    Some(samples)
+}
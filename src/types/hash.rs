@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use crate::utils;
+
+const MAX_COMPACT_ENTRIES: usize = 128;
+const MAX_COMPACT_VALUE_LEN: usize = 64;
+
+// Small hashes are kept as an insertion-ordered pair list, like Redis'
+// listpack encoding, and become a hashtable once they outgrow it.
+#[derive(Debug, Clone)]
+enum HashEntries {
+    Compact(Vec<(String, String)>),
+    Table(HashMap<String, String>),
+}
+
+// Fields with a TTL are tracked in `expirations` (absolute milliseconds).
+// Expired fields are hidden from reads until they are reclaimed.
+#[derive(Debug, Clone)]
+pub struct RedisHash {
+    entries: HashEntries,
+    expirations: HashMap<String, u128>,
+}
+
+impl RedisHash {
+    pub fn new() -> Self {
+        Self {
+            entries: HashEntries::Compact(Vec::new()),
+            expirations: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        let now = utils::get_current_time_ms();
+        let expired = self.expirations.values().filter(|at| **at <= now).count();
+        self.raw_len() - expired
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_compact(&self) -> bool {
+        matches!(self.entries, HashEntries::Compact(_))
+    }
+
+    pub fn get(&self, field: &str) -> Option<&String> {
+        if self.is_expired(field, utils::get_current_time_ms()) {
+            return None;
+        }
+
+        match &self.entries {
+            HashEntries::Compact(pairs) => pairs.iter().find(|(f, _)| f == field).map(|(_, v)| v),
+            HashEntries::Table(table) => table.get(field),
+        }
+    }
+
+    pub fn contains(&self, field: &str) -> bool {
+        self.get(field).is_some()
+    }
+
+    // Returns true when the field did not exist before. Overwriting a field
+    // clears its TTL.
+    pub fn insert(&mut self, field: String, value: String) -> bool {
+        let existed = self.contains(&field);
+        self.expirations.remove(&field);
+
+        if let HashEntries::Compact(pairs) = &mut self.entries {
+            let fits = field.len() <= MAX_COMPACT_VALUE_LEN && value.len() <= MAX_COMPACT_VALUE_LEN;
+            match pairs.iter().position(|(f, _)| *f == field) {
+                Some(idx) if fits => {
+                    pairs[idx].1 = value;
+                    return !existed;
+                }
+                None if fits && pairs.len() < MAX_COMPACT_ENTRIES => {
+                    pairs.push((field, value));
+                    return !existed;
+                }
+                // A value too long for the compact encoding converts the hash,
+                // whether it overwrites a field or adds one.
+                _ => self.entries = HashEntries::Table(pairs.drain(..).collect()),
+            }
+        }
+
+        if let HashEntries::Table(table) = &mut self.entries {
+            table.insert(field, value);
+        }
+        !existed
+    }
+
+    pub fn remove(&mut self, field: &str) -> bool {
+        let existed = self.contains(field);
+        self.expirations.remove(field);
+
+        match &mut self.entries {
+            HashEntries::Compact(pairs) => pairs.retain(|(f, _)| f != field),
+            HashEntries::Table(table) => {
+                table.remove(field);
+            }
+        }
+        existed
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        let now = utils::get_current_time_ms();
+        let entries: Box<dyn Iterator<Item = (&String, &String)>> = match &self.entries {
+            HashEntries::Compact(pairs) => Box::new(pairs.iter().map(|(f, v)| (f, v))),
+            HashEntries::Table(table) => Box::new(table.iter()),
+        };
+
+        if self.expirations.is_empty() {
+            return entries;
+        }
+        Box::new(entries.filter(move |(field, _)| !self.is_expired(field, now)))
+    }
+
+    pub fn expires_at(&self, field: &str) -> Option<u128> {
+        self.expirations.get(field).copied()
+    }
+
+    pub fn set_expiration(&mut self, field: &str, at: u128) {
+        self.expirations.insert(field.to_owned(), at);
+    }
+
+    // Returns false when the field had no TTL.
+    pub fn persist(&mut self, field: &str) -> bool {
+        self.expirations.remove(field).is_some()
+    }
+
+    pub fn has_expirations(&self) -> bool {
+        !self.expirations.is_empty()
+    }
+
+    pub fn remove_expired(&mut self) {
+        for field in self.expired_fields() {
+            self.remove(&field);
+        }
+    }
+
+    pub fn expired_fields(&self) -> Vec<String> {
+        let now = utils::get_current_time_ms();
+        self.expirations
+            .iter()
+            .filter(|(_, at)| **at <= now)
+            .map(|(field, _)| field.to_owned())
+            .collect()
+    }
+
+    fn is_expired(&self, field: &str, now: u128) -> bool {
+        self.expirations.get(field).is_some_and(|at| *at <= now)
+    }
+
+    fn raw_len(&self) -> usize {
+        match &self.entries {
+            HashEntries::Compact(pairs) => pairs.len(),
+            HashEntries::Table(table) => table.len(),
+        }
+    }
+}
+
+impl Default for RedisHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<(String, String)> for RedisHash {
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        let mut hash = RedisHash::new();
+        for (field, value) in iter {
+            hash.insert(field, value);
+        }
+        hash
+    }
+}
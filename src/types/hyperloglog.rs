@@ -0,0 +1,488 @@
+use crate::utils;
+
+use super::redis_error::RedisError;
+
+const HLL_P: usize = 14;
+const HLL_Q: usize = 64 - HLL_P;
+const HLL_REGISTERS: usize = 1 << HLL_P;
+const HLL_BITS: usize = 6;
+const HLL_REGISTER_MAX: u8 = (1 << HLL_BITS) - 1;
+const HLL_HDR_SIZE: usize = 16;
+const HLL_DENSE_SIZE: usize = HLL_HDR_SIZE + (HLL_REGISTERS * HLL_BITS).div_ceil(8);
+const HLL_DENSE: u8 = 0;
+const HLL_SPARSE: u8 = 1;
+const HLL_SPARSE_MAX_BYTES: usize = 3000;
+const HLL_SPARSE_VAL_MAX_VALUE: u8 = 32;
+const HLL_SPARSE_VAL_MAX_LEN: usize = 4;
+const HLL_SPARSE_ZERO_MAX_LEN: usize = 64;
+const HLL_ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+const HLL_HASH_SEED: u64 = 0xadc8_3b19;
+
+// HyperLogLog stored with the same byte layout Redis uses, so the string
+// value is interchangeable with a real Redis instance.
+pub struct HyperLogLog {
+    bytes: Vec<u8>,
+}
+
+enum SparseOpcode {
+    Zero(usize),
+    XZero(usize),
+    Val(u8, usize),
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        let mut bytes = vec![0; HLL_HDR_SIZE];
+        bytes[..4].copy_from_slice(b"HYLL");
+        bytes[4] = HLL_SPARSE;
+        write_xzero(&mut bytes, HLL_REGISTERS);
+        Self { bytes }
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, RedisError> {
+        let valid = bytes.len() >= HLL_HDR_SIZE
+            && &bytes[..4] == b"HYLL"
+            && bytes[4] <= HLL_SPARSE
+            && (bytes[4] == HLL_SPARSE || bytes.len() == HLL_DENSE_SIZE);
+
+        if !valid {
+            return Err(RedisError::InvalidHyperLogLog);
+        }
+        Ok(Self { bytes })
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        self.bytes[4] == HLL_SPARSE
+    }
+
+    pub fn add(&mut self, element: &[u8]) -> Result<bool, RedisError> {
+        let (index, count) = pattern_len(element);
+        self.set_register(index, count)
+    }
+
+    pub fn count(&mut self) -> Result<u64, RedisError> {
+        if self.bytes[15] & 0x80 == 0 {
+            let mut card = [0u8; 8];
+            card.copy_from_slice(&self.bytes[8..16]);
+            return Ok(u64::from_le_bytes(card));
+        }
+
+        let card = count_registers(&self.registers()?);
+        self.bytes[8..16].copy_from_slice(&card.to_le_bytes());
+        Ok(card)
+    }
+
+    pub fn has_valid_cache(&self) -> bool {
+        self.bytes[15] & 0x80 == 0
+    }
+
+    pub fn invalidate_cache(&mut self) {
+        self.bytes[15] |= 0x80;
+    }
+
+    pub fn registers(&self) -> Result<Vec<u8>, RedisError> {
+        if !self.is_sparse() {
+            return Ok((0..HLL_REGISTERS)
+                .map(|idx| dense_get(&self.bytes[HLL_HDR_SIZE..], idx))
+                .collect());
+        }
+
+        let mut registers = Vec::with_capacity(HLL_REGISTERS);
+        for opcode in self.sparse_opcodes() {
+            let (value, len) = match opcode {
+                SparseOpcode::Zero(len) | SparseOpcode::XZero(len) => (0, len),
+                SparseOpcode::Val(value, len) => (value, len),
+            };
+            registers.extend(std::iter::repeat_n(value, len));
+        }
+
+        if registers.len() != HLL_REGISTERS {
+            return Err(RedisError::CorruptedHyperLogLog);
+        }
+        Ok(registers)
+    }
+
+    pub fn merge_into(&self, max: &mut [u8]) -> Result<(), RedisError> {
+        for (idx, value) in self.registers()?.into_iter().enumerate() {
+            max[idx] = max[idx].max(value);
+        }
+        Ok(())
+    }
+
+    pub fn to_dense(&mut self) -> Result<bool, RedisError> {
+        if !self.is_sparse() {
+            return Ok(false);
+        }
+
+        let registers = self.registers()?;
+        let mut dense = vec![0; HLL_DENSE_SIZE];
+        dense[..HLL_HDR_SIZE].copy_from_slice(&self.bytes[..HLL_HDR_SIZE]);
+        dense[4] = HLL_DENSE;
+        for (idx, value) in registers.into_iter().enumerate() {
+            if value > 0 {
+                dense_set(&mut dense[HLL_HDR_SIZE..], idx, value);
+            }
+        }
+
+        self.bytes = dense;
+        Ok(true)
+    }
+
+    pub fn set_register(&mut self, index: usize, count: u8) -> Result<bool, RedisError> {
+        if !self.is_sparse() {
+            let registers = &mut self.bytes[HLL_HDR_SIZE..];
+            if dense_get(registers, index) >= count {
+                return Ok(false);
+            }
+            dense_set(registers, index, count);
+            return Ok(true);
+        }
+
+        if count > HLL_SPARSE_VAL_MAX_VALUE {
+            return self.promote_and_set(index, count);
+        }
+
+        self.sparse_set(index, count)
+    }
+
+    pub fn decode_sparse(&self) -> Option<String> {
+        if !self.is_sparse() {
+            return None;
+        }
+
+        let decoded: Vec<String> = self
+            .sparse_opcodes()
+            .map(|opcode| match opcode {
+                SparseOpcode::Zero(len) => format!("z:{}", len),
+                SparseOpcode::XZero(len) => format!("Z:{}", len),
+                SparseOpcode::Val(value, len) => format!("v:{},{}", value, len),
+            })
+            .collect();
+        Some(decoded.join(" "))
+    }
+
+    fn promote_and_set(&mut self, index: usize, count: u8) -> Result<bool, RedisError> {
+        self.to_dense()?;
+        self.set_register(index, count)
+    }
+
+    fn sparse_opcodes(&self) -> impl Iterator<Item = SparseOpcode> + '_ {
+        let mut idx = HLL_HDR_SIZE;
+        std::iter::from_fn(move || {
+            let opcode = read_opcode(&self.bytes, idx)?;
+            idx += opcode_size(&opcode);
+            Some(opcode)
+        })
+    }
+
+    // Port of Redis' hllSparseSet: updates the run covering `index` in place
+    // and merges adjacent VAL opcodes the same way Redis does, keeping the
+    // encoded bytes identical.
+    fn sparse_set(&mut self, index: usize, count: u8) -> Result<bool, RedisError> {
+        let mut p = HLL_HDR_SIZE;
+        let mut prev = None;
+        let mut first = 0;
+        let mut current = None;
+
+        while let Some(opcode) = read_opcode(&self.bytes, p) {
+            let span = opcode_span(&opcode);
+            if index < first + span {
+                current = Some(opcode);
+                break;
+            }
+            prev = Some(p);
+            p += opcode_size(&opcode);
+            first += span;
+        }
+
+        let Some(opcode) = current else {
+            return Err(RedisError::CorruptedHyperLogLog);
+        };
+
+        let span = opcode_span(&opcode);
+        let last = first + span - 1;
+
+        match opcode {
+            SparseOpcode::Val(value, _) if value >= count => return Ok(false),
+            SparseOpcode::Val(_, 1) | SparseOpcode::Zero(1) => {
+                self.bytes[p] = val_byte(count, 1);
+                self.merge_values(prev.unwrap_or(HLL_HDR_SIZE));
+                self.invalidate_cache();
+                return Ok(true);
+            }
+            _ => {}
+        }
+
+        let mut seq = Vec::with_capacity(5);
+        match opcode {
+            SparseOpcode::Val(value, _) => {
+                if index != first {
+                    seq.push(val_byte(value, index - first));
+                }
+                seq.push(val_byte(count, 1));
+                if index != last {
+                    seq.push(val_byte(value, last - index));
+                }
+            }
+            _ => {
+                if index != first {
+                    write_zero_run(&mut seq, index - first);
+                }
+                seq.push(val_byte(count, 1));
+                if index != last {
+                    write_zero_run(&mut seq, last - index);
+                }
+            }
+        }
+
+        let old_len = opcode_size(&opcode);
+        if seq.len() > old_len && self.bytes.len() + seq.len() - old_len > HLL_SPARSE_MAX_BYTES {
+            return self.promote_and_set(index, count);
+        }
+
+        self.bytes.splice(p..p + old_len, seq);
+        self.merge_values(prev.unwrap_or(HLL_HDR_SIZE));
+        self.invalidate_cache();
+        Ok(true)
+    }
+
+    fn merge_values(&mut self, start: usize) {
+        let mut p = start;
+        let mut scan_len = 5;
+
+        while p < self.bytes.len() && scan_len > 0 {
+            scan_len -= 1;
+            let Some(opcode) = read_opcode(&self.bytes, p) else {
+                return;
+            };
+
+            if let (SparseOpcode::Val(v1, l1), Some(SparseOpcode::Val(v2, l2))) =
+                (&opcode, read_opcode(&self.bytes, p + 1))
+            {
+                if *v1 == v2 && l1 + l2 <= HLL_SPARSE_VAL_MAX_LEN {
+                    self.bytes[p + 1] = val_byte(v2, l1 + l2);
+                    self.bytes.remove(p);
+                    continue;
+                }
+            }
+
+            p += opcode_size(&opcode);
+        }
+    }
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn new_registers() -> Vec<u8> {
+    vec![0; HLL_REGISTERS]
+}
+
+pub fn count_registers(registers: &[u8]) -> u64 {
+    let m = HLL_REGISTERS as f64;
+    let mut histogram = [0usize; 64];
+    for register in registers {
+        histogram[*register as usize] += 1;
+    }
+
+    let mut z = m * tau((m - histogram[HLL_Q + 1] as f64) / m);
+    for j in (1..=HLL_Q).rev() {
+        z += histogram[j] as f64;
+        z *= 0.5;
+    }
+    z += m * sigma(histogram[0] as f64 / m);
+
+    (HLL_ALPHA_INF * m * m / z).round() as u64
+}
+
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let z_prime = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z_prime == z {
+            return z / 3.0;
+        }
+    }
+}
+
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let z_prime = z;
+        z += x * y;
+        y += y;
+        if z_prime == z {
+            return z;
+        }
+    }
+}
+
+fn pattern_len(element: &[u8]) -> (usize, u8) {
+    let hash = utils::murmur_hash64a(element, HLL_HASH_SEED);
+    let index = (hash as usize) & (HLL_REGISTERS - 1);
+    let hash = (hash >> HLL_P) | (1u64 << HLL_Q);
+    (index, hash.trailing_zeros() as u8 + 1)
+}
+
+fn dense_get(registers: &[u8], index: usize) -> u8 {
+    let byte = index * HLL_BITS / 8;
+    let fb = (index * HLL_BITS) & 7;
+    let b0 = registers[byte] as u16;
+    let b1 = registers.get(byte + 1).copied().unwrap_or(0) as u16;
+    (((b0 >> fb) | (b1 << (8 - fb))) as u8) & HLL_REGISTER_MAX
+}
+
+fn dense_set(registers: &mut [u8], index: usize, value: u8) {
+    let byte = index * HLL_BITS / 8;
+    let fb = (index * HLL_BITS) & 7;
+    let value = value as u16;
+
+    registers[byte] &= !((HLL_REGISTER_MAX as u16) << fb) as u8;
+    registers[byte] |= (value << fb) as u8;
+    if let Some(next) = registers.get_mut(byte + 1) {
+        *next &= !((HLL_REGISTER_MAX as u16) >> (8 - fb)) as u8;
+        *next |= (value >> (8 - fb)) as u8;
+    }
+}
+
+fn read_opcode(bytes: &[u8], idx: usize) -> Option<SparseOpcode> {
+    let byte = *bytes.get(idx)?;
+    if byte & 0x80 != 0 {
+        Some(SparseOpcode::Val(
+            ((byte >> 2) & 0x1f) + 1,
+            (byte & 0x3) as usize + 1,
+        ))
+    } else if byte & 0xc0 == 0x40 {
+        let low = *bytes.get(idx + 1)? as usize;
+        Some(SparseOpcode::XZero(
+            (((byte & 0x3f) as usize) << 8 | low) + 1,
+        ))
+    } else {
+        Some(SparseOpcode::Zero((byte & 0x3f) as usize + 1))
+    }
+}
+
+fn opcode_size(opcode: &SparseOpcode) -> usize {
+    match opcode {
+        SparseOpcode::XZero(_) => 2,
+        _ => 1,
+    }
+}
+
+fn opcode_span(opcode: &SparseOpcode) -> usize {
+    match opcode {
+        SparseOpcode::Zero(len) | SparseOpcode::XZero(len) | SparseOpcode::Val(_, len) => *len,
+    }
+}
+
+fn val_byte(value: u8, len: usize) -> u8 {
+    0x80 | ((value - 1) << 2) | (len as u8 - 1)
+}
+
+fn write_zero_run(buffer: &mut Vec<u8>, len: usize) {
+    if len > HLL_SPARSE_ZERO_MAX_LEN {
+        write_xzero(buffer, len);
+    } else {
+        buffer.push(len as u8 - 1);
+    }
+}
+
+fn write_xzero(buffer: &mut Vec<u8>, len: usize) {
+    let len = len - 1;
+    buffer.push((len >> 8) as u8 | 0x40);
+    buffer.push((len & 0xff) as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Three times the 0.81% standard error of 16384 registers.
+    const MAX_ERROR: f64 = 3.0 * 0.0081;
+
+    fn filled(range: std::ops::Range<u64>) -> HyperLogLog {
+        let mut hll = HyperLogLog::new();
+        for i in range {
+            hll.add(format!("element:{}", i).as_bytes()).unwrap();
+        }
+        hll
+    }
+
+    fn assert_within_bounds(hll: &mut HyperLogLog, n: u64) {
+        let count = hll.count().unwrap();
+        let error = (count as f64 - n as f64).abs() / n as f64;
+        assert!(
+            error <= MAX_ERROR,
+            "counted {} for {} elements ({:.2}% off)",
+            count,
+            n,
+            error * 100.0
+        );
+    }
+
+    #[test]
+    fn sparse_count_is_within_error_bounds() {
+        for n in [10, 100, 500] {
+            let mut hll = filled(0..n);
+            assert!(hll.is_sparse());
+            assert_within_bounds(&mut hll, n);
+        }
+    }
+
+    #[test]
+    fn dense_count_is_within_error_bounds() {
+        for n in [5_000, 50_000, 500_000] {
+            let mut hll = filled(0..n);
+            assert!(!hll.is_sparse());
+            assert_within_bounds(&mut hll, n);
+        }
+    }
+
+    #[test]
+    fn promoted_count_is_within_error_bounds() {
+        for n in [10, 100, 500] {
+            let mut hll = filled(0..n);
+            hll.to_dense().unwrap();
+            assert_within_bounds(&mut hll, n);
+        }
+    }
+
+    #[test]
+    fn merged_count_is_within_error_bounds() {
+        // Overlapping halves, merged the way PFMERGE does.
+        for n in [200, 20_000, 200_000] {
+            let mut registers = new_registers();
+            filled(0..n * 3 / 5).merge_into(&mut registers).unwrap();
+            filled(n * 2 / 5..n).merge_into(&mut registers).unwrap();
+
+            let mut merged = HyperLogLog::new();
+            for (index, count) in registers.into_iter().enumerate() {
+                if count > 0 {
+                    merged.set_register(index, count).unwrap();
+                }
+            }
+            assert_within_bounds(&mut merged, n);
+        }
+    }
+}
@@ -0,0 +1,494 @@
+const MAX_DEPTH: usize = 128;
+
+pub const MISSING_KEY_ERROR: &str =
+    "ERR could not perform this operation on a key that doesn't exist";
+pub const ROOT_ERROR: &str = "ERR new objects must be created at the root";
+
+// JSON document stored under a key. Strings hold one char per byte like the
+// rest of the keyspace, so `\u` escapes are kept as their UTF-8 bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    // Members keep their insertion order.
+    Object(Vec<(String, JsonValue)>),
+}
+
+// Concrete position of a value inside a document.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JsonStep {
+    Key(String),
+    Index(usize),
+}
+
+pub struct JsonFormat<'a> {
+    pub indent: &'a str,
+    pub newline: &'a str,
+    pub space: &'a str,
+}
+
+impl JsonFormat<'_> {
+    pub const COMPACT: JsonFormat<'static> = JsonFormat {
+        indent: "",
+        newline: "",
+        space: "",
+    };
+}
+
+impl JsonValue {
+    pub fn parse(input: &str) -> Result<JsonValue, String> {
+        let mut parser = Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+        };
+
+        parser.skip_whitespace();
+        let value = parser.parse_value(0)?;
+        parser.skip_whitespace();
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "boolean",
+            JsonValue::Integer(_) => "integer",
+            JsonValue::Float(_) => "number",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object",
+        }
+    }
+
+    pub fn wrong_type_error(&self, expected: &str) -> String {
+        format!(
+            "ERR wrong type of path value - expected {} but found {}",
+            expected,
+            self.type_name()
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_formatted_json(&JsonFormat::COMPACT)
+    }
+
+    pub fn to_formatted_json(&self, format: &JsonFormat) -> String {
+        let mut output = String::new();
+        self.write(&mut output, format, 0);
+        output
+    }
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn pointer(&self, location: &[JsonStep]) -> Option<&JsonValue> {
+        location
+            .iter()
+            .try_fold(self, |value, step| match (value, step) {
+                (JsonValue::Object(_), JsonStep::Key(key)) => value.get(key),
+                (JsonValue::Array(items), JsonStep::Index(idx)) => items.get(*idx),
+                _ => None,
+            })
+    }
+
+    pub fn pointer_mut(&mut self, location: &[JsonStep]) -> Option<&mut JsonValue> {
+        location
+            .iter()
+            .try_fold(self, |value, step| match (value, step) {
+                (JsonValue::Object(members), JsonStep::Key(key)) => {
+                    members.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+                }
+                (JsonValue::Array(items), JsonStep::Index(idx)) => items.get_mut(*idx),
+                _ => None,
+            })
+    }
+
+    // Sets `key` on an object, keeping its position when already present.
+    pub fn insert(&mut self, key: String, value: JsonValue) -> bool {
+        let JsonValue::Object(members) = self else {
+            return false;
+        };
+
+        match members.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, current)) => *current = value,
+            None => members.push((key, value)),
+        }
+        true
+    }
+
+    // Removes the value at `location`, which must not be the root.
+    pub fn remove(&mut self, location: &[JsonStep]) -> Option<JsonValue> {
+        let (last, parent) = location.split_last()?;
+        match (self.pointer_mut(parent)?, last) {
+            (JsonValue::Object(members), JsonStep::Key(key)) => {
+                let idx = members.iter().position(|(k, _)| k == key)?;
+                Some(members.remove(idx).1)
+            }
+            (JsonValue::Array(items), JsonStep::Index(idx)) if *idx < items.len() => {
+                Some(items.remove(*idx))
+            }
+            _ => None,
+        }
+    }
+
+    // Applies an RFC 7396 merge patch: objects merge member by member, null
+    // members are removed and anything else replaces the target.
+    pub fn merge(&mut self, patch: JsonValue) {
+        let JsonValue::Object(patch_members) = patch else {
+            *self = patch;
+            return;
+        };
+
+        if !matches!(self, JsonValue::Object(_)) {
+            *self = JsonValue::Object(vec![]);
+        }
+        let JsonValue::Object(members) = self else {
+            return;
+        };
+
+        for (key, value) in patch_members {
+            let current = members.iter().position(|(k, _)| *k == key);
+            match (current, value) {
+                (Some(idx), JsonValue::Null) => {
+                    members.remove(idx);
+                }
+                (None, JsonValue::Null) => {}
+                (Some(idx), value) => members[idx].1.merge(value),
+                (None, value) => {
+                    let mut target = JsonValue::Null;
+                    target.merge(value);
+                    members.push((key, target));
+                }
+            }
+        }
+    }
+
+    fn write(&self, output: &mut String, format: &JsonFormat, depth: usize) {
+        match self {
+            JsonValue::Null => output.push_str("null"),
+            JsonValue::Bool(bool) => output.push_str(if *bool { "true" } else { "false" }),
+            JsonValue::Integer(int) => output.push_str(&int.to_string()),
+            JsonValue::Float(float) => output.push_str(&format_json_float(*float)),
+            JsonValue::String(str) => write_string(output, str),
+            JsonValue::Array(items) => {
+                write_container(output, format, depth, ('[', ']'), items, |output, item| {
+                    item.write(output, format, depth + 1)
+                })
+            }
+            JsonValue::Object(members) => write_container(
+                output,
+                format,
+                depth,
+                ('{', '}'),
+                members,
+                |output, (key, value)| {
+                    write_string(output, key);
+                    output.push(':');
+                    output.push_str(format.space);
+                    value.write(output, format, depth + 1);
+                },
+            ),
+        }
+    }
+}
+
+fn write_container<T>(
+    output: &mut String,
+    format: &JsonFormat,
+    depth: usize,
+    (open, close): (char, char),
+    items: &[T],
+    mut write_item: impl FnMut(&mut String, &T),
+) {
+    output.push(open);
+    if items.is_empty() {
+        output.push(close);
+        return;
+    }
+
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            output.push(',');
+        }
+        output.push_str(format.newline);
+        output.push_str(&format.indent.repeat(depth + 1));
+        write_item(output, item);
+    }
+    output.push_str(format.newline);
+    output.push_str(&format.indent.repeat(depth));
+    output.push(close);
+}
+
+fn write_string(output: &mut String, value: &str) {
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{c}' => output.push_str("\\f"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+// Floats always keep a fraction or exponent so they read back as floats.
+fn format_json_float(value: f64) -> String {
+    format!("{:?}", value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> String {
+        let consumed = &self.chars[..self.pos.min(self.chars.len())];
+        let line = consumed.iter().filter(|c| **c == '\n').count() + 1;
+        let column = consumed.iter().rev().take_while(|c| **c != '\n').count() + 1;
+        format!("{} at line {} column {}", message, line, column)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\n' | '\r' | '\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, String> {
+        for expected in literal.chars() {
+            match self.next() {
+                Some(c) if c == expected => {}
+                Some(_) => return Err(self.error("expected value")),
+                None => return Err(self.error("EOF while parsing a value")),
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("recursion limit exceeded"));
+        }
+
+        match self.peek() {
+            None => Err(self.error("EOF while parsing a value")),
+            Some('n') => self.expect_literal("null", JsonValue::Null),
+            Some('t') => self.expect_literal("true", JsonValue::Bool(true)),
+            Some('f') => self.expect_literal("false", JsonValue::Bool(false)),
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some('[') => self.parse_array(depth),
+            Some('{') => self.parse_object(depth),
+            Some('-' | '0'..='9') => self.parse_number(),
+            Some(_) => Err(self.error("expected value")),
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonValue, String> {
+        self.pos += 1;
+        let mut items = vec![];
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(JsonValue::Array(items)),
+                Some(_) => return Err(self.error("expected `,` or `]`")),
+                None => return Err(self.error("EOF while parsing a list")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonValue, String> {
+        self.pos += 1;
+        let mut object = JsonValue::Object(vec![]);
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(object);
+        }
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('"') => {}
+                Some(_) => return Err(self.error("key must be a string")),
+                None => return Err(self.error("EOF while parsing an object")),
+            }
+            let key = self.parse_string()?;
+
+            self.skip_whitespace();
+            match self.next() {
+                Some(':') => {}
+                Some(_) => return Err(self.error("expected `:`")),
+                None => return Err(self.error("EOF while parsing an object")),
+            }
+
+            self.skip_whitespace();
+            let value = self.parse_value(depth + 1)?;
+            // Duplicate keys keep the last value, like most parsers do.
+            object.insert(key, value);
+
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(object),
+                Some(_) => return Err(self.error("expected `,` or `}`")),
+                None => return Err(self.error("EOF while parsing an object")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut value = String::new();
+
+        loop {
+            match self.next() {
+                None => return Err(self.error("EOF while parsing a string")),
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('/') => value.push('/'),
+                    Some('b') => value.push('\u{8}'),
+                    Some('f') => value.push('\u{c}'),
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('u') => {
+                        let c = self.parse_unicode_escape()?;
+                        let mut buf = [0; 4];
+                        value.extend(c.encode_utf8(&mut buf).bytes().map(|b| b as char));
+                    }
+                    Some(_) => return Err(self.error("invalid escape")),
+                    None => return Err(self.error("EOF while parsing a string")),
+                },
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(self
+                        .error("control character (\\u0000-\\u001F) found while parsing a string"))
+                }
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("invalid escape"))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode code point"));
+        }
+
+        if self.next() != Some('\\') || self.next() != Some('u') {
+            return Err(self.error("unexpected end of hex escape"));
+        }
+        let low = self.parse_hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("lone leading surrogate in hex escape"));
+        }
+
+        let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode code point"))
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let start = self.pos;
+        let mut is_float = false;
+
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        match self.next() {
+            Some('0') => {}
+            Some('1'..='9') => self.skip_digits(),
+            _ => return Err(self.error("invalid number")),
+        }
+
+        if self.peek() == Some('.') {
+            is_float = true;
+            self.pos += 1;
+            if !matches!(self.next(), Some('0'..='9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+
+        if matches!(self.peek(), Some('e' | 'E')) {
+            is_float = true;
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            if !matches!(self.next(), Some('0'..='9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if !is_float {
+            if let Ok(int) = text.parse::<i64>() {
+                return Ok(JsonValue::Integer(int));
+            }
+        }
+
+        match text.parse::<f64>() {
+            Ok(float) if float.is_finite() => Ok(JsonValue::Float(float)),
+            _ => Err(self.error("number out of range")),
+        }
+    }
+
+    fn skip_digits(&mut self) {
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.pos += 1;
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use crate::resp::resp_serializer;
+
+use super::json::{JsonStep, JsonValue};
+
+#[derive(Debug, Clone)]
+enum Selector {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    Slice(Option<i64>, Option<i64>, i64),
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Child(Vec<Selector>),
+    Descendant(Vec<Selector>),
+}
+
+// Subset of JSONPath: `$`, `.name`, `['name']`, `[index]`, `[*]`, `.*`,
+// `[start:end:step]`, unions such as `[0,2]` and `..` recursive descent.
+// Paths not starting with `$` use the legacy syntax, e.g. `.a.b` or `a[0]`,
+// where commands reply with a single value instead of one per match.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    raw: String,
+    segments: Vec<Segment>,
+    legacy: bool,
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<JsonPath, String> {
+        let (expression, legacy) = match path {
+            "." => ("$".to_owned(), true),
+            path if path.starts_with('$') => (path.to_owned(), false),
+            path if path.starts_with('.') || path.starts_with('[') => (format!("${}", path), true),
+            path => (format!("$.{}", path), true),
+        };
+
+        let segments = PathParser {
+            chars: expression.chars().collect(),
+            pos: 1,
+        }
+        .parse()
+        .ok_or_else(|| format!("ERR invalid JSONPath '{}'", path))?;
+
+        Ok(JsonPath {
+            raw: path.to_owned(),
+            segments,
+            legacy,
+        })
+    }
+
+    pub fn root() -> JsonPath {
+        JsonPath {
+            raw: ".".to_owned(),
+            segments: vec![],
+            legacy: true,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn missing_error(&self) -> String {
+        format!("ERR Path '{}' does not exist", self.raw)
+    }
+
+    // Runs `apply` on every value matched by the path. JSONPath replies hold
+    // one item per match, null where `apply` failed, while legacy paths reply
+    // with the first result and surface its error.
+    pub fn reply_per_match(
+        &self,
+        json: &mut JsonValue,
+        mut apply: impl FnMut(&mut JsonValue) -> Result<String, String>,
+    ) -> Result<String, String> {
+        let results: Vec<Result<String, String>> = self
+            .locate(json)
+            .iter()
+            .filter_map(|location| json.pointer_mut(location).map(&mut apply))
+            .collect();
+
+        if !self.legacy {
+            let items = results
+                .into_iter()
+                .map(|result| result.unwrap_or_else(|_| resp_serializer::null_resp_string()))
+                .collect();
+            return Ok(resp_serializer::to_resp_nested_array(items));
+        }
+
+        match results.into_iter().next() {
+            Some(result) => result,
+            None => Err(self.missing_error()),
+        }
+    }
+
+    // Splits a path ending in a single member name into the path of its
+    // parent and the name, which is where a missing member can be created.
+    pub fn split_last_key(&self) -> Option<(JsonPath, String)> {
+        let (last, parent) = self.segments.split_last()?;
+        let Segment::Child(selectors) = last else {
+            return None;
+        };
+        let [Selector::Key(key)] = selectors.as_slice() else {
+            return None;
+        };
+
+        let parent = JsonPath {
+            raw: self.raw.clone(),
+            segments: parent.to_vec(),
+            legacy: self.legacy,
+        };
+        Some((parent, key.to_owned()))
+    }
+
+    // Returns the positions of every value the path matches, in document order.
+    pub fn locate(&self, root: &JsonValue) -> Vec<Vec<JsonStep>> {
+        let mut locations = vec![vec![]];
+
+        for segment in &self.segments {
+            let mut next = vec![];
+            for location in locations {
+                let Some(value) = root.pointer(&location) else {
+                    continue;
+                };
+                match segment {
+                    Segment::Child(selectors) => {
+                        select(value, &location, selectors, &mut next);
+                    }
+                    Segment::Descendant(selectors) => {
+                        for (location, value) in descendants(value, location) {
+                            select(value, &location, selectors, &mut next);
+                        }
+                    }
+                }
+            }
+            locations = next;
+        }
+        locations
+    }
+}
+
+fn select(
+    value: &JsonValue,
+    location: &[JsonStep],
+    selectors: &[Selector],
+    output: &mut Vec<Vec<JsonStep>>,
+) {
+    let mut push = |step: JsonStep| {
+        let mut location = location.to_vec();
+        location.push(step);
+        output.push(location);
+    };
+
+    for selector in selectors {
+        match (selector, value) {
+            (Selector::Key(key), JsonValue::Object(_)) if value.get(key).is_some() => {
+                push(JsonStep::Key(key.to_owned()))
+            }
+            (Selector::Index(idx), JsonValue::Array(items)) => {
+                let idx = match *idx < 0 {
+                    true => items.len() as i64 + idx,
+                    false => *idx,
+                };
+                if (0..items.len() as i64).contains(&idx) {
+                    push(JsonStep::Index(idx as usize));
+                }
+            }
+            (Selector::Wildcard, JsonValue::Array(items)) => {
+                (0..items.len()).for_each(|idx| push(JsonStep::Index(idx)))
+            }
+            (Selector::Wildcard, JsonValue::Object(members)) => members
+                .iter()
+                .for_each(|(key, _)| push(JsonStep::Key(key.to_owned()))),
+            (Selector::Slice(start, end, step), JsonValue::Array(items)) if *step > 0 => {
+                let len = items.len() as i64;
+                let clamp = |bound: i64| match bound < 0 {
+                    true => (len + bound).max(0),
+                    false => bound.min(len),
+                };
+                let start = start.map_or(0, clamp);
+                let end = end.map_or(len, clamp);
+                (start..end)
+                    .step_by(*step as usize)
+                    .for_each(|idx| push(JsonStep::Index(idx as usize)));
+            }
+            _ => {}
+        }
+    }
+}
+
+// The value itself followed by everything nested below it, depth first.
+fn descendants(value: &JsonValue, location: Vec<JsonStep>) -> Vec<(Vec<JsonStep>, &JsonValue)> {
+    let mut output = vec![];
+    let mut stack = vec![(location, value)];
+
+    while let Some((location, value)) = stack.pop() {
+        let children: Vec<(JsonStep, &JsonValue)> = match value {
+            JsonValue::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| (JsonStep::Index(idx), item))
+                .collect(),
+            JsonValue::Object(members) => members
+                .iter()
+                .map(|(key, value)| (JsonStep::Key(key.to_owned()), value))
+                .collect(),
+            _ => vec![],
+        };
+
+        for (step, child) in children.into_iter().rev() {
+            let mut child_location = location.clone();
+            child_location.push(step);
+            stack.push((child_location, child));
+        }
+        output.push((location, value));
+    }
+    output
+}
+
+struct PathParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PathParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn parse(mut self) -> Option<Vec<Segment>> {
+        let mut segments = vec![];
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            let segment = match c {
+                '.' if self.peek() == Some('.') => {
+                    self.pos += 1;
+                    let selectors = match self.peek()? {
+                        '[' => {
+                            self.pos += 1;
+                            self.parse_bracket()?
+                        }
+                        _ => self.parse_dot_selector()?,
+                    };
+                    Segment::Descendant(selectors)
+                }
+                '.' => Segment::Child(self.parse_dot_selector()?),
+                '[' => Segment::Child(self.parse_bracket()?),
+                _ => return None,
+            };
+            segments.push(segment);
+        }
+        Some(segments)
+    }
+
+    fn parse_dot_selector(&mut self) -> Option<Vec<Selector>> {
+        if self.peek() == Some('*') {
+            self.pos += 1;
+            return Some(vec![Selector::Wildcard]);
+        }
+
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != '.' && c != '[') {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return None;
+        }
+        Some(vec![Selector::Key(
+            self.chars[start..self.pos].iter().collect(),
+        )])
+    }
+
+    // Parses the inside of `[...]`, the opening bracket already consumed.
+    fn parse_bracket(&mut self) -> Option<Vec<Selector>> {
+        let mut selectors = vec![];
+
+        loop {
+            self.skip_spaces();
+            let selector = match self.peek()? {
+                '*' => {
+                    self.pos += 1;
+                    Selector::Wildcard
+                }
+                quote @ ('\'' | '"') => {
+                    self.pos += 1;
+                    Selector::Key(self.parse_quoted(quote)?)
+                }
+                _ => self.parse_index_or_slice()?,
+            };
+            selectors.push(selector);
+
+            self.skip_spaces();
+            match self.peek()? {
+                ',' => self.pos += 1,
+                ']' => {
+                    self.pos += 1;
+                    return Some(selectors);
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn parse_quoted(&mut self, quote: char) -> Option<String> {
+        let mut value = String::new();
+        loop {
+            let c = self.peek()?;
+            self.pos += 1;
+            match c {
+                '\\' => {
+                    value.push(self.peek()?);
+                    self.pos += 1;
+                }
+                c if c == quote => return Some(value),
+                c => value.push(c),
+            }
+        }
+    }
+
+    fn parse_index_or_slice(&mut self) -> Option<Selector> {
+        let start = self.parse_integer();
+        self.skip_spaces();
+        if self.peek() != Some(':') {
+            return start.map(Selector::Index);
+        }
+
+        self.pos += 1;
+        self.skip_spaces();
+        let end = self.parse_integer();
+        self.skip_spaces();
+        let step = match self.peek() {
+            Some(':') => {
+                self.pos += 1;
+                self.skip_spaces();
+                self.parse_integer().unwrap_or(1)
+            }
+            _ => 1,
+        };
+        Some(Selector::Slice(start, end, step))
+    }
+
+    fn parse_integer(&mut self) -> Option<i64> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        let text: String = self.chars[start..self.pos].iter().collect();
+        match text.parse().ok() {
+            Some(int) => Some(int),
+            None => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek() == Some(' ') {
+            self.pos += 1;
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
+    ops::{Bound, Deref},
+};
+
+use super::{entry_value::EntryValue, value_container::ValueContainer};
+
+// The keys and their values. Reads go straight to the map, while writes go
+// through this type so it knows which keys changed since `take_written` was
+// last called.
+#[derive(Debug, Default)]
+pub struct Keyspace {
+    entries: HashMap<String, EntryValue>,
+    written: HashSet<String>,
+    // Keys with a TTL on themselves or on some hash field, as of the last
+    // `take_written`.
+    volatile: BTreeSet<String>,
+    // Where the next sample of volatile keys starts.
+    volatile_cursor: Option<String>,
+}
+
+impl Keyspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: String, entry: EntryValue) -> Option<EntryValue> {
+        self.written.insert(key.to_owned());
+        self.entries.insert(key, entry)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<EntryValue> {
+        let entry = self.entries.remove(key)?;
+        self.written.insert(key.to_owned());
+        Some(entry)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut EntryValue> {
+        let entry = self.entries.get_mut(key)?;
+        self.written.insert(key.to_owned());
+        Some(entry)
+    }
+
+    pub fn entry(&mut self, key: String) -> Entry<'_, String, EntryValue> {
+        self.written.insert(key.to_owned());
+        self.entries.entry(key)
+    }
+
+    // Drains the keys written since the last call, bringing the set of
+    // volatile keys up to date with them.
+    pub fn take_written(&mut self) -> Vec<String> {
+        let written: Vec<String> = self.written.drain().collect();
+        for key in &written {
+            match self.entries.get(key) {
+                Some(entry) if has_ttl(entry) => {
+                    self.volatile.insert(key.to_owned());
+                }
+                _ => {
+                    self.volatile.remove(key);
+                }
+            }
+        }
+        written
+    }
+
+    // Up to `count` volatile keys, resuming where the previous sample ended
+    // so every key is visited in turn.
+    pub fn sample_volatile(&mut self, count: usize) -> Vec<String> {
+        let start = match &self.volatile_cursor {
+            Some(cursor) => Bound::Excluded(cursor.to_owned()),
+            None => Bound::Unbounded,
+        };
+
+        let mut sample: Vec<String> = self
+            .volatile
+            .range((start, Bound::Unbounded))
+            .take(count)
+            .cloned()
+            .collect();
+        if sample.len() < count {
+            let wrapped: Vec<String> = self
+                .volatile
+                .iter()
+                .take(count - sample.len())
+                .filter(|key| !sample.contains(key))
+                .cloned()
+                .collect();
+            sample.extend(wrapped);
+        }
+
+        self.volatile_cursor = sample.last().cloned();
+        sample
+    }
+}
+
+impl Deref for Keyspace {
+    type Target = HashMap<String, EntryValue>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entries
+    }
+}
+
+impl From<HashMap<String, EntryValue>> for Keyspace {
+    fn from(entries: HashMap<String, EntryValue>) -> Self {
+        let volatile = entries
+            .iter()
+            .filter(|(_, entry)| has_ttl(entry))
+            .map(|(key, _)| key.to_owned())
+            .collect();
+
+        Self {
+            entries,
+            volatile,
+            ..Self::default()
+        }
+    }
+}
+
+fn has_ttl(entry: &EntryValue) -> bool {
+    match &entry.value {
+        ValueContainer::Hash(hash) if hash.has_expirations() => true,
+        _ => entry.expires_at.is_some(),
+    }
+}
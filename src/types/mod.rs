@@ -1,12 +1,30 @@
+pub mod bitmap;
+pub mod bloom_filter;
 pub mod connection_context;
+pub mod cuckoo_filter;
 
 pub mod entry_value;
 pub mod execution_response;
+pub mod geohash;
+pub mod gorilla;
+pub mod hash;
+pub mod hyperloglog;
 pub mod instance_type;
+pub mod json;
+pub mod json_path;
+pub mod keyspace;
 pub mod redis_error;
 pub mod redis_replica;
 pub mod redis_settings;
+pub mod search_index;
+pub mod search_query;
+pub mod set;
+pub mod sorted_set;
+pub mod stream;
 pub mod stream_entry;
 pub mod stream_key;
+pub mod time_series;
 pub mod transactions;
 pub mod value_container;
+pub mod vector_set;
+pub mod waiter_registry;
@@ -15,6 +15,17 @@ pub enum RedisError {
     IOError(std::io::Error),
     ParsingError,
     InvalidOpCode,
+    RDBUnsupportedValueType(u8),
+    RDBUnsupportedValue,
+    RDBInvalidLZFString,
+    RDBInvalidListpack,
+    RDBInvalidIntSet,
+    RDBInvalidFilter,
+    RDBInvalidTimeSeries,
+    RDBInvalidVectorSet,
+    InvalidDumpPayload,
+    InvalidHyperLogLog,
+    CorruptedHyperLogLog,
 }
 
 impl Error for RedisError {}
@@ -49,6 +60,25 @@ impl Display for RedisError {
             RedisError::RDBInvalidHeader => write!(f, "RDB header is invalid"),
             RedisError::ParsingError => write!(f, "Parsing error"),
             RedisError::InvalidOpCode => write!(f, "Invalid Op Code"),
+            RedisError::RDBUnsupportedValueType(t) => {
+                write!(f, "RDB value type is not supported: {}", t)
+            }
+            RedisError::RDBUnsupportedValue => write!(f, "Value can't be encoded as RDB"),
+            RedisError::RDBInvalidLZFString => write!(f, "RDB LZF string is corrupted"),
+            RedisError::RDBInvalidListpack => write!(f, "RDB listpack is corrupted"),
+            RedisError::RDBInvalidIntSet => write!(f, "RDB intset is corrupted"),
+            RedisError::RDBInvalidFilter => write!(f, "RDB bloom or cuckoo filter is corrupted"),
+            RedisError::RDBInvalidTimeSeries => write!(f, "RDB time series is corrupted"),
+            RedisError::RDBInvalidVectorSet => write!(f, "RDB vector set is corrupted"),
+            RedisError::InvalidDumpPayload => {
+                write!(f, "DUMP payload version or checksum are wrong")
+            }
+            RedisError::InvalidHyperLogLog => {
+                write!(f, "WRONGTYPE Key is not a valid HyperLogLog string value.")
+            }
+            RedisError::CorruptedHyperLogLog => {
+                write!(f, "INVALIDOBJ Corrupted HLL object detected")
+            }
         }
     }
 }
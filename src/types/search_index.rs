@@ -0,0 +1,389 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Bound,
+};
+
+use super::{
+    hash::RedisHash,
+    search_query::{NumericBound, QueryNode},
+};
+
+#[derive(Debug, Clone)]
+pub enum FieldKind {
+    Text {
+        weight: f64,
+    },
+    Tag {
+        separator: char,
+        case_sensitive: bool,
+    },
+    Numeric,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldSpec {
+    // Hash field the values are read from.
+    pub name: String,
+    // Name used for the field in queries and replies.
+    pub alias: String,
+    pub kind: FieldKind,
+}
+
+impl FieldSpec {
+    fn tags(&self, value: &str) -> Vec<String> {
+        let FieldKind::Tag {
+            separator,
+            case_sensitive,
+        } = self.kind
+        else {
+            return vec![];
+        };
+        value
+            .split(separator)
+            .map(|tag| normalize_tag(tag, case_sensitive))
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+}
+
+// Numbers ordered with `total_cmp` so they can key a BTreeMap.
+#[derive(Debug, Clone, Copy)]
+struct Number(f64);
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Number {}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[derive(Debug)]
+enum FieldIndex {
+    // Term to the frequency of the term in each document.
+    Text(HashMap<String, HashMap<String, u32>>),
+    Tag(HashMap<String, HashSet<String>>),
+    Numeric(BTreeMap<Number, HashSet<String>>),
+}
+
+// What a document contributed to each field index, so it can be taken out
+// again when the hash changes.
+#[derive(Debug)]
+enum IndexedValue {
+    Text(HashMap<String, u32>),
+    Tag(Vec<String>),
+    Numeric(f64),
+}
+
+// Inverted index over the hashes under `prefixes`.
+#[derive(Debug)]
+pub struct SearchIndex {
+    pub name: String,
+    pub prefixes: Vec<String>,
+    pub schema: Vec<FieldSpec>,
+    fields: Vec<FieldIndex>,
+    docs: HashMap<String, Vec<(usize, IndexedValue)>>,
+}
+
+impl SearchIndex {
+    pub fn new(name: String, prefixes: Vec<String>, schema: Vec<FieldSpec>) -> Self {
+        let fields = schema
+            .iter()
+            .map(|field| match field.kind {
+                FieldKind::Text { .. } => FieldIndex::Text(HashMap::new()),
+                FieldKind::Tag { .. } => FieldIndex::Tag(HashMap::new()),
+                FieldKind::Numeric => FieldIndex::Numeric(BTreeMap::new()),
+            })
+            .collect();
+
+        Self {
+            name,
+            prefixes,
+            schema,
+            fields,
+            docs: HashMap::new(),
+        }
+    }
+
+    pub fn watches(&self, key: &str) -> bool {
+        self.prefixes.is_empty() || self.prefixes.iter().any(|prefix| key.starts_with(prefix))
+    }
+
+    pub fn field(&self, alias: &str) -> Option<&FieldSpec> {
+        self.schema.iter().find(|field| field.alias == alias)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.docs.keys()
+    }
+
+    // Replaces whatever `key` had indexed with the fields of `hash`, or only
+    // drops it when the key is no longer a hash.
+    pub fn update(&mut self, key: &str, hash: Option<&RedisHash>) {
+        self.remove(key);
+        let Some(hash) = hash.filter(|_| self.watches(key)) else {
+            return;
+        };
+
+        let mut indexed = vec![];
+        for (pos, spec) in self.schema.iter().enumerate() {
+            let Some(value) = hash.get(&spec.name) else {
+                continue;
+            };
+
+            let value = match (&spec.kind, &mut self.fields[pos]) {
+                (FieldKind::Text { .. }, FieldIndex::Text(terms)) => {
+                    let mut frequencies = HashMap::new();
+                    for term in tokenize(value) {
+                        *frequencies.entry(term).or_insert(0) += 1;
+                    }
+                    for (term, frequency) in &frequencies {
+                        terms
+                            .entry(term.to_owned())
+                            .or_default()
+                            .insert(key.to_owned(), *frequency);
+                    }
+                    IndexedValue::Text(frequencies)
+                }
+                (FieldKind::Tag { .. }, FieldIndex::Tag(tags)) => {
+                    let values = spec.tags(value);
+                    for tag in &values {
+                        tags.entry(tag.to_owned())
+                            .or_default()
+                            .insert(key.to_owned());
+                    }
+                    IndexedValue::Tag(values)
+                }
+                (FieldKind::Numeric, FieldIndex::Numeric(numbers)) => {
+                    // Values that are not numbers are left out of the index.
+                    let Some(number) = value.parse::<f64>().ok().filter(|n| !n.is_nan()) else {
+                        continue;
+                    };
+                    numbers
+                        .entry(Number(number))
+                        .or_default()
+                        .insert(key.to_owned());
+                    IndexedValue::Numeric(number)
+                }
+                _ => continue,
+            };
+            indexed.push((pos, value));
+        }
+        self.docs.insert(key.to_owned(), indexed);
+    }
+
+    fn remove(&mut self, key: &str) {
+        let Some(indexed) = self.docs.remove(key) else {
+            return;
+        };
+
+        for (pos, value) in indexed {
+            match (&mut self.fields[pos], value) {
+                (FieldIndex::Text(terms), IndexedValue::Text(frequencies)) => {
+                    for term in frequencies.keys() {
+                        if let Some(docs) = terms.get_mut(term) {
+                            docs.remove(key);
+                            if docs.is_empty() {
+                                terms.remove(term);
+                            }
+                        }
+                    }
+                }
+                (FieldIndex::Tag(tags), IndexedValue::Tag(values)) => {
+                    for tag in &values {
+                        if let Some(docs) = tags.get_mut(tag) {
+                            docs.remove(key);
+                            if docs.is_empty() {
+                                tags.remove(tag);
+                            }
+                        }
+                    }
+                }
+                (FieldIndex::Numeric(numbers), IndexedValue::Numeric(number)) => {
+                    let number = Number(number);
+                    if let Some(docs) = numbers.get_mut(&number) {
+                        docs.remove(key);
+                        if docs.is_empty() {
+                            numbers.remove(&number);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Returns the documents matching the query with their relevance score,
+    // the sum over matched terms of frequency, field weight and term rarity.
+    pub fn search(&self, query: &QueryNode) -> Result<HashMap<String, f64>, String> {
+        match query {
+            QueryNode::All => Ok(self.docs.keys().map(|key| (key.to_owned(), 0.0)).collect()),
+            QueryNode::Term {
+                field,
+                term,
+                prefix,
+            } => {
+                let fields = match field {
+                    Some(alias) => vec![self.field_position(alias)?],
+                    None => (0..self.schema.len()).collect(),
+                };
+
+                let mut output: HashMap<String, f64> = HashMap::new();
+                for pos in fields {
+                    let (FieldIndex::Text(terms), FieldKind::Text { weight }) =
+                        (&self.fields[pos], &self.schema[pos].kind)
+                    else {
+                        continue;
+                    };
+                    let matches = terms.iter().filter(|(candidate, _)| match prefix {
+                        true => candidate.starts_with(term.as_str()),
+                        false => *candidate == term,
+                    });
+                    for (_, docs) in matches {
+                        let rarity = (1.0 + self.docs.len() as f64 / docs.len() as f64).ln();
+                        for (key, frequency) in docs {
+                            *output.entry(key.to_owned()).or_default() +=
+                                *frequency as f64 * weight * rarity;
+                        }
+                    }
+                }
+                Ok(output)
+            }
+            QueryNode::Tag { field, tags } => {
+                let pos = self.field_position(field)?;
+                let (FieldIndex::Tag(index), FieldKind::Tag { case_sensitive, .. }) =
+                    (&self.fields[pos], &self.schema[pos].kind)
+                else {
+                    return Err(format!("Field `{}` is not a TAG field", field));
+                };
+                Ok(tags
+                    .iter()
+                    .filter_map(|tag| index.get(&normalize_tag(tag, *case_sensitive)))
+                    .flatten()
+                    .map(|key| (key.to_owned(), 0.0))
+                    .collect())
+            }
+            QueryNode::Numeric { field, min, max } => {
+                let pos = self.field_position(field)?;
+                let FieldIndex::Numeric(index) = &self.fields[pos] else {
+                    return Err(format!("Field `{}` is not a NUMERIC field", field));
+                };
+                if min.value() > max.value() {
+                    return Ok(HashMap::new());
+                }
+                let bound = |bound: &NumericBound| match bound {
+                    NumericBound::Inclusive(value) => Bound::Included(Number(*value)),
+                    NumericBound::Exclusive(value) => Bound::Excluded(Number(*value)),
+                };
+                Ok(index
+                    .range((bound(min), bound(max)))
+                    .flat_map(|(_, keys)| keys)
+                    .map(|key| (key.to_owned(), 0.0))
+                    .collect())
+            }
+            QueryNode::And(nodes) => {
+                let mut output: Option<HashMap<String, f64>> = None;
+                for node in nodes {
+                    let matches = self.search(node)?;
+                    output = Some(match output {
+                        None => matches,
+                        Some(current) => current
+                            .into_iter()
+                            .filter_map(|(key, score)| {
+                                matches.get(&key).map(|other| (key, score + other))
+                            })
+                            .collect(),
+                    });
+                }
+                Ok(output.unwrap_or_default())
+            }
+            QueryNode::Or(nodes) => {
+                let mut output: HashMap<String, f64> = HashMap::new();
+                for node in nodes {
+                    for (key, score) in self.search(node)? {
+                        *output.entry(key).or_default() += score;
+                    }
+                }
+                Ok(output)
+            }
+            QueryNode::Not(node) => {
+                let excluded = self.search(node)?;
+                Ok(self
+                    .docs
+                    .keys()
+                    .filter(|key| !excluded.contains_key(*key))
+                    .map(|key| (key.to_owned(), 0.0))
+                    .collect())
+            }
+        }
+    }
+
+    fn field_position(&self, alias: &str) -> Result<usize, String> {
+        self.schema
+            .iter()
+            .position(|field| field.alias == alias)
+            .ok_or_else(|| format!("Unknown field `{}`", alias))
+    }
+}
+
+// Lowercased words, split on anything that is not a letter, digit or `_`.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+}
+
+fn normalize_tag(tag: &str, case_sensitive: bool) -> String {
+    match case_sensitive {
+        true => tag.trim().to_owned(),
+        false => tag.trim().to_lowercase(),
+    }
+}
+
+// Every index by name. Hash writes go through `update_key` so indexes never
+// have to rescan the keyspace.
+#[derive(Debug, Default)]
+pub struct SearchRegistry {
+    indexes: BTreeMap<String, SearchIndex>,
+}
+
+impl SearchRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SearchIndex> {
+        self.indexes.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.indexes.contains_key(name)
+    }
+
+    pub fn insert(&mut self, index: SearchIndex) {
+        self.indexes.insert(index.name.to_owned(), index);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<SearchIndex> {
+        self.indexes.remove(name)
+    }
+
+    pub fn update_key(&mut self, key: &str, hash: Option<&RedisHash>) {
+        for index in self.indexes.values_mut() {
+            index.update(key, hash);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use super::search_index;
+
+#[derive(Debug, Clone, Copy)]
+pub enum NumericBound {
+    Inclusive(f64),
+    Exclusive(f64),
+}
+
+impl NumericBound {
+    pub fn value(&self) -> f64 {
+        match self {
+            NumericBound::Inclusive(value) | NumericBound::Exclusive(value) => *value,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum QueryNode {
+    All,
+    Term {
+        field: Option<String>,
+        term: String,
+        prefix: bool,
+    },
+    Tag {
+        field: String,
+        tags: Vec<String>,
+    },
+    Numeric {
+        field: String,
+        min: NumericBound,
+        max: NumericBound,
+    },
+    And(Vec<QueryNode>),
+    Or(Vec<QueryNode>),
+    Not(Box<QueryNode>),
+}
+
+// Parses the query dialect of FT.SEARCH: words and `prefix*` match text,
+// `@field:...` scopes to a field, `[min max]` and `{a | b}` match numeric
+// ranges and tag sets. Juxtaposition is AND, `|` is OR, `-` negates and
+// parentheses group, with NOT binding tightest and OR loosest.
+pub fn parse(query: &str) -> Result<QueryNode, String> {
+    let mut parser = QueryParser {
+        chars: query.chars().collect(),
+        pos: 0,
+    };
+
+    parser.skip_spaces();
+    if parser.peek() == Some('*') {
+        parser.pos += 1;
+        parser.skip_spaces();
+        return match parser.peek() {
+            None => Ok(QueryNode::All),
+            Some(_) => Err(parser.error()),
+        };
+    }
+
+    let node = parser.parse_or(None)?;
+    match parser.peek() {
+        None => Ok(node),
+        Some(_) => Err(parser.error()),
+    }
+}
+
+struct QueryParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl QueryParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self) -> String {
+        format!("Syntax error at offset {}", self.pos)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_spaces();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(self.error()),
+        }
+    }
+
+    fn parse_or(&mut self, field: Option<&str>) -> Result<QueryNode, String> {
+        let mut nodes = vec![self.parse_and(field)?];
+        loop {
+            self.skip_spaces();
+            if self.peek() != Some('|') {
+                break;
+            }
+            self.pos += 1;
+            nodes.push(self.parse_and(field)?);
+        }
+        Ok(flatten(nodes, QueryNode::Or))
+    }
+
+    fn parse_and(&mut self, field: Option<&str>) -> Result<QueryNode, String> {
+        let mut nodes = vec![];
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                None | Some('|') | Some(')') => break,
+                _ => nodes.push(self.parse_unary(field)?),
+            }
+        }
+        match nodes.is_empty() {
+            true => Err(self.error()),
+            false => Ok(flatten(nodes, QueryNode::And)),
+        }
+    }
+
+    fn parse_unary(&mut self, field: Option<&str>) -> Result<QueryNode, String> {
+        self.skip_spaces();
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                Ok(QueryNode::Not(Box::new(self.parse_unary(field)?)))
+            }
+            Some('(') => {
+                self.pos += 1;
+                let node = self.parse_or(field)?;
+                self.expect(')')?;
+                Ok(node)
+            }
+            Some('@') if field.is_none() => {
+                self.pos += 1;
+                let name = self.parse_word();
+                if name.is_empty() {
+                    return Err(self.error());
+                }
+                self.expect(':')?;
+                self.parse_field_expression(&name)
+            }
+            Some('"') => {
+                self.pos += 1;
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c != '"') {
+                    self.pos += 1;
+                }
+                let phrase: String = self.chars[start..self.pos].iter().collect();
+                self.expect('"')?;
+                let terms = search_index::tokenize(&phrase)
+                    .map(|term| text_term(field, term, false))
+                    .collect();
+                Ok(flatten(terms, QueryNode::And))
+            }
+            Some(_) => {
+                let word = self.parse_word();
+                if word.is_empty() {
+                    return Err(self.error());
+                }
+                let prefix = self.peek() == Some('*');
+                if prefix {
+                    self.pos += 1;
+                }
+                let terms: Vec<QueryNode> = search_index::tokenize(&word)
+                    .map(|term| text_term(field, term, prefix))
+                    .collect();
+                match terms.is_empty() {
+                    true => Err(self.error()),
+                    false => Ok(flatten(terms, QueryNode::And)),
+                }
+            }
+            None => Err(self.error()),
+        }
+    }
+
+    fn parse_field_expression(&mut self, field: &str) -> Result<QueryNode, String> {
+        self.skip_spaces();
+        match self.peek() {
+            Some('[') => {
+                self.pos += 1;
+                let min = self.parse_bound()?;
+                let max = self.parse_bound()?;
+                self.expect(']')?;
+                Ok(QueryNode::Numeric {
+                    field: field.to_owned(),
+                    min,
+                    max,
+                })
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut tags = vec![];
+                let mut tag = String::new();
+                loop {
+                    match self.peek() {
+                        Some('\\') => {
+                            self.pos += 1;
+                            tag.extend(self.peek());
+                        }
+                        Some('|') => tags.push(std::mem::take(&mut tag)),
+                        Some('}') => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(self.error()),
+                    }
+                    self.pos += 1;
+                }
+                self.pos += 1;
+                tags.push(tag);
+                Ok(QueryNode::Tag {
+                    field: field.to_owned(),
+                    tags,
+                })
+            }
+            _ => self.parse_unary(Some(field)),
+        }
+    }
+
+    fn parse_bound(&mut self) -> Result<NumericBound, String> {
+        self.skip_spaces();
+        let exclusive = self.peek() == Some('(');
+        if exclusive {
+            self.pos += 1;
+        }
+
+        let start = self.pos;
+        while self.peek().is_some_and(|c| !c.is_whitespace() && c != ']') {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        let value = match text.to_lowercase().as_str() {
+            "inf" | "+inf" => f64::INFINITY,
+            "-inf" => f64::NEG_INFINITY,
+            text => text
+                .parse::<f64>()
+                .ok()
+                .filter(|value| !value.is_nan())
+                .ok_or_else(|| self.error())?,
+        };
+
+        Ok(match exclusive {
+            true => NumericBound::Exclusive(value),
+            false => NumericBound::Inclusive(value),
+        })
+    }
+
+    // A run of characters up to the next space or operator; `\` escapes.
+    fn parse_word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '\\' => {
+                    self.pos += 1;
+                    word.extend(self.peek());
+                }
+                c if c.is_whitespace() || "()|@:[]{}\"*".contains(c) => break,
+                c => word.push(c),
+            }
+            self.pos += 1;
+        }
+        word
+    }
+}
+
+fn text_term(field: Option<&str>, term: String, prefix: bool) -> QueryNode {
+    QueryNode::Term {
+        field: field.map(|field| field.to_owned()),
+        term,
+        prefix,
+    }
+}
+
+fn flatten(mut nodes: Vec<QueryNode>, combine: fn(Vec<QueryNode>) -> QueryNode) -> QueryNode {
+    match nodes.len() {
+        1 => nodes.remove(0),
+        _ => combine(nodes),
+    }
+}
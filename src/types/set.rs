@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+
+const MAX_INTSET_ENTRIES: usize = 512;
+
+// Sets holding only integers are kept as a sorted integer array, like Redis'
+// intset encoding, and become a hashtable once a string member is added or
+// they outgrow it.
+#[derive(Debug, Clone)]
+pub enum RedisSet {
+    IntSet(Vec<i64>),
+    Table(HashSet<String>),
+}
+
+impl RedisSet {
+    pub fn new() -> Self {
+        RedisSet::IntSet(Vec::new())
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            RedisSet::IntSet(ints) => ints.len(),
+            RedisSet::Table(table) => table.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_intset(&self) -> bool {
+        matches!(self, RedisSet::IntSet(_))
+    }
+
+    pub fn contains(&self, member: &str) -> bool {
+        match self {
+            RedisSet::IntSet(ints) => {
+                as_set_integer(member).is_some_and(|int| ints.binary_search(&int).is_ok())
+            }
+            RedisSet::Table(table) => table.contains(member),
+        }
+    }
+
+    // Returns true when the member was not in the set before.
+    pub fn insert(&mut self, member: String) -> bool {
+        let ints = match self {
+            RedisSet::Table(table) => return table.insert(member),
+            RedisSet::IntSet(ints) => ints,
+        };
+
+        if let Some(int) = as_set_integer(&member) {
+            match ints.binary_search(&int) {
+                Ok(_) => return false,
+                Err(idx) if ints.len() < MAX_INTSET_ENTRIES => {
+                    ints.insert(idx, int);
+                    return true;
+                }
+                Err(_) => {}
+            }
+        }
+
+        let mut table: HashSet<String> = ints.iter().map(|int| int.to_string()).collect();
+        table.insert(member);
+        *self = RedisSet::Table(table);
+        true
+    }
+
+    pub fn remove(&mut self, member: &str) -> bool {
+        match self {
+            RedisSet::IntSet(ints) => {
+                match as_set_integer(member).and_then(|int| ints.binary_search(&int).ok()) {
+                    Some(idx) => {
+                        ints.remove(idx);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            RedisSet::Table(table) => table.remove(member),
+        }
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        match self {
+            RedisSet::IntSet(ints) => Box::new(ints.iter().map(|int| int.to_string())),
+            RedisSet::Table(table) => Box::new(table.iter().cloned()),
+        }
+    }
+
+    pub fn members(&self) -> Vec<String> {
+        self.iter().collect()
+    }
+}
+
+impl Default for RedisSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<String> for RedisSet {
+    fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+        let mut set = RedisSet::new();
+        for member in iter {
+            set.insert(member);
+        }
+        set
+    }
+}
+
+// Only members that round-trip exactly, e.g. not "+1" or "01", are integers.
+fn as_set_integer(member: &str) -> Option<i64> {
+    let int = member.parse::<i64>().ok()?;
+    (int.to_string() == member).then_some(int)
+}
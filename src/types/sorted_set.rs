@@ -0,0 +1,560 @@
+use std::collections::HashMap;
+
+use crate::utils;
+
+const MAX_LEVEL: usize = 32;
+const HEAD: usize = 0;
+
+#[derive(Debug, Clone)]
+struct Level {
+    forward: Option<usize>,
+    span: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    member: String,
+    score: f64,
+    backward: Option<usize>,
+    levels: Vec<Level>,
+}
+
+impl Node {
+    fn new(member: String, score: f64, level: usize) -> Self {
+        Self {
+            member,
+            score,
+            backward: None,
+            levels: (0..level)
+                .map(|_| Level {
+                    forward: None,
+                    span: 0,
+                })
+                .collect(),
+        }
+    }
+
+    fn precedes(&self, score: f64, member: &str) -> bool {
+        self.score < score || (self.score == score && self.member.as_str() < member)
+    }
+}
+
+// A port of Redis' zskiplist: nodes ordered by (score, member), with a span on
+// every forward link so ranks can be computed while walking. Nodes live in an
+// arena and link to each other by index; slot 0 is the header.
+#[derive(Debug, Clone)]
+struct SkipList {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    len: usize,
+    level: usize,
+    seed: u64,
+}
+
+impl SkipList {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node::new(String::new(), 0.0, MAX_LEVEL)],
+            free: Vec::new(),
+            len: 0,
+            level: 1,
+            seed: utils::random_u64() | 1,
+        }
+    }
+
+    // Each extra level is kept with a 1/4 probability, as in Redis.
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < MAX_LEVEL {
+            self.seed ^= self.seed << 13;
+            self.seed ^= self.seed >> 7;
+            self.seed ^= self.seed << 17;
+            if self.seed & 3 != 0 {
+                break;
+            }
+            level += 1;
+        }
+        level
+    }
+
+    fn insert(&mut self, score: f64, member: String) {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut rank = [0usize; MAX_LEVEL];
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            rank[i] = if i == self.level - 1 { 0 } else { rank[i + 1] };
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !self.nodes[next].precedes(score, &member) {
+                    break;
+                }
+                rank[i] += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        let level = self.random_level();
+        if level > self.level {
+            for i in self.level..level {
+                rank[i] = 0;
+                update[i] = HEAD;
+                self.nodes[HEAD].levels[i].span = self.len;
+            }
+            self.level = level;
+        }
+
+        let node = self.alloc(Node::new(member, score, level));
+        for i in 0..level {
+            let prev = update[i];
+            self.nodes[node].levels[i].forward = self.nodes[prev].levels[i].forward;
+            self.nodes[prev].levels[i].forward = Some(node);
+            self.nodes[node].levels[i].span = self.nodes[prev].levels[i].span - (rank[0] - rank[i]);
+            self.nodes[prev].levels[i].span = rank[0] - rank[i] + 1;
+        }
+        for (i, prev) in update.iter().enumerate().take(self.level).skip(level) {
+            self.nodes[*prev].levels[i].span += 1;
+        }
+
+        self.nodes[node].backward = (update[0] != HEAD).then_some(update[0]);
+        if let Some(next) = self.nodes[node].levels[0].forward {
+            self.nodes[next].backward = Some(node);
+        }
+        self.len += 1;
+    }
+
+    fn delete(&mut self, score: f64, member: &str) -> bool {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !self.nodes[next].precedes(score, member) {
+                    break;
+                }
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        match self.nodes[x].levels[0].forward {
+            Some(node) if self.nodes[node].score == score && self.nodes[node].member == member => {
+                self.unlink(node, &update);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn unlink(&mut self, node: usize, update: &[usize; MAX_LEVEL]) {
+        for (i, prev) in update.iter().enumerate().take(self.level) {
+            if self.nodes[*prev].levels[i].forward == Some(node) {
+                self.nodes[*prev].levels[i].span += self.nodes[node].levels[i].span;
+                self.nodes[*prev].levels[i].span -= 1;
+                self.nodes[*prev].levels[i].forward = self.nodes[node].levels[i].forward;
+            } else {
+                self.nodes[*prev].levels[i].span -= 1;
+            }
+        }
+
+        if let Some(next) = self.nodes[node].levels[0].forward {
+            self.nodes[next].backward = self.nodes[node].backward;
+        }
+
+        while self.level > 1 && self.nodes[HEAD].levels[self.level - 1].forward.is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+
+        self.nodes[node] = Node::new(String::new(), 0.0, 0);
+        self.free.push(node);
+    }
+
+    fn alloc(&mut self, node: Node) -> usize {
+        match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = node;
+                idx
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    // 0-based rank of an element known to be in the list.
+    fn rank(&self, score: f64, member: &str) -> Option<usize> {
+        let mut rank = 0;
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                let node = &self.nodes[next];
+                if !(node.score < score || (node.score == score && node.member.as_str() <= member))
+                {
+                    break;
+                }
+                rank += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            if x != HEAD && self.nodes[x].member == member {
+                return Some(rank - 1);
+            }
+        }
+        None
+    }
+
+    fn by_rank(&self, rank: usize) -> Option<usize> {
+        let target = rank + 1;
+        let mut traversed = 0;
+        let mut x = HEAD;
+
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if traversed + self.nodes[x].levels[i].span > target {
+                    break;
+                }
+                traversed += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            if traversed == target {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    // First node for which `before` no longer holds.
+    fn first_not(&self, before: impl Fn(&Node) -> bool) -> Option<usize> {
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !before(&self.nodes[next]) {
+                    break;
+                }
+                x = next;
+            }
+        }
+        self.nodes[x].levels[0].forward
+    }
+
+    // Last node for which `within` still holds.
+    fn last_where(&self, within: impl Fn(&Node) -> bool) -> Option<usize> {
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !within(&self.nodes[next]) {
+                    break;
+                }
+                x = next;
+            }
+        }
+        (x != HEAD).then_some(x)
+    }
+
+    fn next(&self, node: usize, reverse: bool) -> Option<usize> {
+        match reverse {
+            true => self.nodes[node].backward,
+            false => self.nodes[node].levels[0].forward,
+        }
+    }
+
+    fn entry(&self, node: usize) -> (&String, f64) {
+        (&self.nodes[node].member, self.nodes[node].score)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreRange {
+    pub min: f64,
+    pub max: f64,
+    pub min_exclusive: bool,
+    pub max_exclusive: bool,
+}
+
+impl ScoreRange {
+    // Parses `min`/`max` bounds such as `1.5`, `(1.5`, `-inf` or `+inf`.
+    pub fn parse(min: &str, max: &str) -> Option<ScoreRange> {
+        let (min, min_exclusive) = parse_score_bound(min)?;
+        let (max, max_exclusive) = parse_score_bound(max)?;
+        Some(ScoreRange {
+            min,
+            max,
+            min_exclusive,
+            max_exclusive,
+        })
+    }
+
+    fn above_min(&self, score: f64) -> bool {
+        match self.min_exclusive {
+            true => score > self.min,
+            false => score >= self.min,
+        }
+    }
+
+    fn below_max(&self, score: f64) -> bool {
+        match self.max_exclusive {
+            true => score < self.max,
+            false => score <= self.max,
+        }
+    }
+}
+
+fn parse_score_bound(value: &str) -> Option<(f64, bool)> {
+    let (value, exclusive) = match value.strip_prefix('(') {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+
+    if value.is_empty() || value.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let score = value.parse::<f64>().ok().filter(|score| !score.is_nan())?;
+    Some((score, exclusive))
+}
+
+#[derive(Debug, Clone)]
+pub enum LexBound {
+    NegativeInfinity,
+    PositiveInfinity,
+    Inclusive(String),
+    Exclusive(String),
+}
+
+impl LexBound {
+    // Parses `-`, `+`, `[member` or `(member`.
+    pub fn parse(value: &str) -> Option<LexBound> {
+        match value {
+            "-" => Some(LexBound::NegativeInfinity),
+            "+" => Some(LexBound::PositiveInfinity),
+            _ => match value.split_at_checked(1)? {
+                ("[", member) => Some(LexBound::Inclusive(member.to_owned())),
+                ("(", member) => Some(LexBound::Exclusive(member.to_owned())),
+                _ => None,
+            },
+        }
+    }
+
+    fn admits_from_below(&self, member: &str) -> bool {
+        match self {
+            LexBound::NegativeInfinity => true,
+            LexBound::PositiveInfinity => false,
+            LexBound::Inclusive(min) => member >= min.as_str(),
+            LexBound::Exclusive(min) => member > min.as_str(),
+        }
+    }
+
+    fn admits_from_above(&self, member: &str) -> bool {
+        match self {
+            LexBound::NegativeInfinity => false,
+            LexBound::PositiveInfinity => true,
+            LexBound::Inclusive(max) => member <= max.as_str(),
+            LexBound::Exclusive(max) => member < max.as_str(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LexRange {
+    pub min: LexBound,
+    pub max: LexBound,
+}
+
+impl LexRange {
+    pub fn parse(min: &str, max: &str) -> Option<LexRange> {
+        Some(LexRange {
+            min: LexBound::parse(min)?,
+            max: LexBound::parse(max)?,
+        })
+    }
+}
+
+// Like Redis' skiplist encoding: the skiplist keeps members ordered by score
+// while the dictionary answers score lookups in constant time.
+#[derive(Debug, Clone)]
+pub struct SortedSet {
+    scores: HashMap<String, f64>,
+    list: SkipList,
+}
+
+impl SortedSet {
+    pub fn new() -> Self {
+        Self {
+            scores: HashMap::new(),
+            list: SkipList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    // Adds the member or updates its score. Returns true when it is new.
+    pub fn insert(&mut self, member: String, score: f64) -> bool {
+        match self.scores.get(&member).copied() {
+            Some(current) if current == score => false,
+            Some(current) => {
+                self.list.delete(current, &member);
+                self.list.insert(score, member.clone());
+                self.scores.insert(member, score);
+                false
+            }
+            None => {
+                self.list.insert(score, member.clone());
+                self.scores.insert(member, score);
+                true
+            }
+        }
+    }
+
+    pub fn remove(&mut self, member: &str) -> bool {
+        match self.scores.remove(member) {
+            Some(score) => self.list.delete(score, member),
+            None => false,
+        }
+    }
+
+    pub fn rank(&self, member: &str, reverse: bool) -> Option<usize> {
+        let rank = self.list.rank(self.score(member)?, member)?;
+        Some(match reverse {
+            true => self.len() - 1 - rank,
+            false => rank,
+        })
+    }
+
+    // Elements between two 0-based ranks, both inclusive and within bounds.
+    pub fn range_by_rank(&self, start: usize, end: usize, reverse: bool) -> Vec<(String, f64)> {
+        let first = match reverse {
+            true => self.list.by_rank(self.len() - 1 - start),
+            false => self.list.by_rank(start),
+        };
+        self.walk(first, reverse, |_| true, 0, Some(end + 1 - start))
+    }
+
+    pub fn range_by_score(
+        &self,
+        range: &ScoreRange,
+        reverse: bool,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Vec<(String, f64)> {
+        let first = match reverse {
+            true => self.list.last_where(|node| range.below_max(node.score)),
+            false => self.list.first_not(|node| !range.above_min(node.score)),
+        };
+        let in_range = |(_, score): (&String, f64)| match reverse {
+            true => range.above_min(score),
+            false => range.below_max(score),
+        };
+        self.walk(first, reverse, in_range, offset, limit)
+    }
+
+    pub fn range_by_lex(
+        &self,
+        range: &LexRange,
+        reverse: bool,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Vec<(String, f64)> {
+        let first = match reverse {
+            true => self
+                .list
+                .last_where(|node| range.max.admits_from_above(&node.member)),
+            false => self
+                .list
+                .first_not(|node| !range.min.admits_from_below(&node.member)),
+        };
+        let in_range = |(member, _): (&String, f64)| match reverse {
+            true => range.min.admits_from_below(member),
+            false => range.max.admits_from_above(member),
+        };
+        self.walk(first, reverse, in_range, offset, limit)
+    }
+
+    pub fn count_by_score(&self, range: &ScoreRange) -> usize {
+        let first = self.list.first_not(|node| !range.above_min(node.score));
+        let last = self.list.last_where(|node| range.below_max(node.score));
+        self.count_between(first, last)
+    }
+
+    pub fn count_by_lex(&self, range: &LexRange) -> usize {
+        let first = self
+            .list
+            .first_not(|node| !range.min.admits_from_below(&node.member));
+        let last = self
+            .list
+            .last_where(|node| range.max.admits_from_above(&node.member));
+        self.count_between(first, last)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, f64)> + '_ {
+        let mut node = self.list.first_not(|_| false);
+        std::iter::from_fn(move || {
+            let current = node?;
+            node = self.list.next(current, false);
+            Some(self.list.entry(current))
+        })
+    }
+
+    fn count_between(&self, first: Option<usize>, last: Option<usize>) -> usize {
+        let (Some(first), Some(last)) = (first, last) else {
+            return 0;
+        };
+
+        let (first_member, first_score) = self.list.entry(first);
+        let (last_member, last_score) = self.list.entry(last);
+        match (
+            self.list.rank(first_score, first_member),
+            self.list.rank(last_score, last_member),
+        ) {
+            (Some(first), Some(last)) if last >= first => last - first + 1,
+            _ => 0,
+        }
+    }
+
+    fn walk(
+        &self,
+        mut node: Option<usize>,
+        reverse: bool,
+        in_range: impl Fn((&String, f64)) -> bool,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Vec<(String, f64)> {
+        let mut skipped = 0;
+        let mut entries = Vec::new();
+
+        while let Some(current) = node {
+            if limit.is_some_and(|limit| entries.len() >= limit) {
+                break;
+            }
+
+            let entry = self.list.entry(current);
+            if !in_range(entry) {
+                break;
+            }
+
+            if skipped < offset {
+                skipped += 1;
+            } else {
+                entries.push((entry.0.to_owned(), entry.1));
+            }
+            node = self.list.next(current, reverse);
+        }
+        entries
+    }
+}
+
+impl Default for SortedSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use super::{stream_entry::StreamEntry, stream_key::StreamKey};
+
+// An entry delivered to a consumer of a group and not acknowledged yet.
+#[derive(Debug, Clone)]
+pub struct PendingEntry {
+    pub consumer: String,
+    pub delivered_at: u128,
+    pub delivery_count: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Consumer {
+    pub seen_at: u128,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsumerGroup {
+    pub last_delivered: StreamKey,
+    pub pending: BTreeMap<StreamKey, PendingEntry>,
+    pub consumers: BTreeMap<String, Consumer>,
+}
+
+impl ConsumerGroup {
+    pub fn new(last_delivered: StreamKey) -> Self {
+        Self {
+            last_delivered,
+            pending: BTreeMap::new(),
+            consumers: BTreeMap::new(),
+        }
+    }
+
+    // Returns whether the consumer was created.
+    pub fn touch_consumer(&mut self, name: &str, now: u128) -> bool {
+        match self.consumers.get_mut(name) {
+            Some(consumer) => {
+                consumer.seen_at = now;
+                false
+            }
+            None => {
+                self.consumers
+                    .insert(name.to_owned(), Consumer { seen_at: now });
+                true
+            }
+        }
+    }
+
+    // Drops the consumer along with its pending entries, returning how many
+    // were pending.
+    pub fn remove_consumer(&mut self, name: &str) -> Option<usize> {
+        self.consumers.remove(name)?;
+        let before = self.pending.len();
+        self.pending.retain(|_, entry| entry.consumer != name);
+        Some(before - self.pending.len())
+    }
+
+    pub fn pending_count(&self, consumer: &str) -> usize {
+        self.pending
+            .values()
+            .filter(|entry| entry.consumer == consumer)
+            .count()
+    }
+
+    // Hands `id` to the consumer, as a first delivery or a redelivery.
+    pub fn deliver(&mut self, consumer: &str, id: StreamKey, now: u128) {
+        let entry = self.pending.entry(id).or_insert(PendingEntry {
+            consumer: consumer.to_owned(),
+            delivered_at: now,
+            delivery_count: 0,
+        });
+        entry.consumer = consumer.to_owned();
+        entry.delivered_at = now;
+        entry.delivery_count += 1;
+    }
+}
+
+pub enum TrimStrategy {
+    MaxLen(usize),
+    // Drops the entries with an id below this one.
+    MinId(StreamKey),
+}
+
+// Entries kept in id order. `last_id` is the greatest id ever added, which
+// new ids must exceed even after that entry is gone.
+#[derive(Debug, Clone, Default)]
+pub struct RedisStream {
+    entries: Vec<StreamEntry>,
+    last_id: Option<StreamKey>,
+    pub groups: BTreeMap<String, ConsumerGroup>,
+}
+
+impl RedisStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_parts(
+        entries: Vec<StreamEntry>,
+        last_id: Option<StreamKey>,
+        groups: BTreeMap<String, ConsumerGroup>,
+    ) -> Self {
+        Self {
+            entries,
+            last_id,
+            groups,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[StreamEntry] {
+        &self.entries
+    }
+
+    pub fn last_id(&self) -> Option<StreamKey> {
+        self.last_id
+    }
+
+    // The id must be greater than `last_id`.
+    pub fn push(&mut self, entry: StreamEntry) {
+        self.last_id = Some(entry.id);
+        self.entries.push(entry);
+    }
+
+    pub fn get(&self, id: &StreamKey) -> Option<&StreamEntry> {
+        self.entries
+            .binary_search_by(|entry| entry.id.cmp(id))
+            .ok()
+            .map(|idx| &self.entries[idx])
+    }
+
+    // Entries with an id strictly greater than `id`.
+    pub fn after(&self, id: &StreamKey) -> &[StreamEntry] {
+        let start = self.entries.partition_point(|entry| entry.id <= *id);
+        &self.entries[start..]
+    }
+
+    // Removes the entries with the given ids, returning how many existed.
+    pub fn delete(&mut self, ids: &[StreamKey]) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| !ids.contains(&entry.id));
+        before - self.entries.len()
+    }
+
+    // Evicts the oldest entries, at most `limit` of them, returning how many
+    // were evicted.
+    pub fn trim(&mut self, strategy: &TrimStrategy, limit: Option<usize>) -> usize {
+        let excess = match strategy {
+            TrimStrategy::MaxLen(max_len) => self.entries.len().saturating_sub(*max_len),
+            TrimStrategy::MinId(min_id) => self.entries.partition_point(|entry| entry.id < *min_id),
+        };
+        let evicted = excess.min(limit.unwrap_or(usize::MAX));
+        self.entries.drain(..evicted);
+        evicted
+    }
+}
+
+pub fn no_group_error(key: &str, group: &str) -> String {
+    format!(
+        "NOGROUP No such key '{}' or consumer group '{}'",
+        key, group
+    )
+}
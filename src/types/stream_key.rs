@@ -4,6 +4,8 @@ use crate::utils;
 
 use super::redis_error::RedisError;
 
+pub const INVALID_ID_ERROR: &str = "ERR Invalid stream ID specified as stream command argument";
+
 #[derive(Debug, Clone, Copy)]
 pub struct StreamKey {
     pub miliseconds_time: u128,
@@ -38,7 +40,7 @@ impl StreamKey {
         sequence: Option<u64>,
     ) -> Result<Self, RedisError> {
         if key == "$" {
-            return Ok(last_key.unwrap());
+            return last_key.ok_or(RedisError::InvalidStreamEntryId(key.to_owned()));
         }
         if key == "-" {
             return Ok(Self::new(0, 1));
@@ -50,7 +52,7 @@ impl StreamKey {
             return Ok(Self::from_now(0));
         }
 
-        let splited: Vec<&str> = key.split('-').collect();
+        let splited: Vec<&str> = key.splitn(2, '-').collect();
 
         let time = splited
             .get(0)
@@ -90,6 +92,52 @@ impl StreamKey {
         })
     }
 
+    // Parses an explicit `ms-seq` id, where a bare `ms` takes `sequence`.
+    pub fn parse(id: &str, sequence: u64) -> Result<Self, &'static str> {
+        match id {
+            "$" | "*" => Err(INVALID_ID_ERROR),
+            id => Self::from_string(&id.to_owned(), &None, Some(sequence))
+                .map_err(|_| INVALID_ID_ERROR),
+        }
+    }
+
+    // Parses one end of an XRANGE style interval, where `-` and `+` are the
+    // smallest and greatest ids and a leading `(` excludes the id itself.
+    pub fn parse_range_bound(id: &str, start: bool) -> Result<Self, &'static str> {
+        let (exclusive, id) = match id.strip_prefix('(') {
+            Some(id) => (true, id),
+            None => (false, id),
+        };
+        let key = match (id, exclusive) {
+            ("-" | "+", true) => return Err(INVALID_ID_ERROR),
+            ("-", false) => Self::new(0, 0),
+            ("+", false) => Self::new(u128::MAX, u64::MAX),
+            (id, _) => Self::parse(id, if start { 0 } else { u64::MAX })?,
+        };
+
+        match (exclusive, start) {
+            (false, _) => Ok(key),
+            (true, true) => key.next().ok_or("ERR invalid start ID for the interval"),
+            (true, false) => key.previous().ok_or("ERR invalid end ID for the interval"),
+        }
+    }
+
+    // The smallest id greater than this one.
+    pub fn next(&self) -> Option<Self> {
+        match self.sequence_number.checked_add(1) {
+            Some(sequence) => Some(Self::new(self.miliseconds_time, sequence)),
+            None => Some(Self::new(self.miliseconds_time.checked_add(1)?, 0)),
+        }
+    }
+
+    // The greatest id smaller than this one.
+    pub fn previous(&self) -> Option<Self> {
+        match self.sequence_number.checked_sub(1) {
+            Some(sequence) => Some(Self::new(self.miliseconds_time, sequence)),
+            None => Some(Self::new(self.miliseconds_time.checked_sub(1)?, u64::MAX)),
+        }
+    }
+
     fn inc_sequence(&self) -> Self {
         Self {
             miliseconds_time: self.miliseconds_time,
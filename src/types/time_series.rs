@@ -0,0 +1,401 @@
+use crate::utils;
+
+use super::{gorilla::Chunk, redis_error::RedisError};
+
+const CHUNK_SAMPLES: usize = 256;
+pub const BLOCK_ERROR: &str =
+    "ERR TSDB: Error at upsert, update is not supported when DUPLICATE_POLICY is set to BLOCK mode";
+pub const RETENTION_ERROR: &str = "ERR TSDB: Timestamp is older than retention";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    #[default]
+    Block,
+    First,
+    Last,
+    Min,
+    Max,
+    Sum,
+}
+
+impl DuplicatePolicy {
+    pub fn parse(value: &str) -> Option<DuplicatePolicy> {
+        match value.to_uppercase().as_str() {
+            "BLOCK" => Some(DuplicatePolicy::Block),
+            "FIRST" => Some(DuplicatePolicy::First),
+            "LAST" => Some(DuplicatePolicy::Last),
+            "MIN" => Some(DuplicatePolicy::Min),
+            "MAX" => Some(DuplicatePolicy::Max),
+            "SUM" => Some(DuplicatePolicy::Sum),
+            _ => None,
+        }
+    }
+
+    fn resolve(self, old: f64, new: f64) -> Result<f64, &'static str> {
+        match self {
+            DuplicatePolicy::Block => Err(BLOCK_ERROR),
+            DuplicatePolicy::First => Ok(old),
+            DuplicatePolicy::Last => Ok(new),
+            DuplicatePolicy::Min => Ok(old.min(new)),
+            DuplicatePolicy::Max => Ok(old.max(new)),
+            DuplicatePolicy::Sum => Ok(old + new),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Avg,
+    Sum,
+    Min,
+    Max,
+    Count,
+    First,
+    Last,
+}
+
+impl Aggregation {
+    pub fn parse(value: &str) -> Option<Aggregation> {
+        match value.to_lowercase().as_str() {
+            "avg" => Some(Aggregation::Avg),
+            "sum" => Some(Aggregation::Sum),
+            "min" => Some(Aggregation::Min),
+            "max" => Some(Aggregation::Max),
+            "count" => Some(Aggregation::Count),
+            "first" => Some(Aggregation::First),
+            "last" => Some(Aggregation::Last),
+            _ => None,
+        }
+    }
+
+    fn apply(self, values: &[f64]) -> f64 {
+        match self {
+            Aggregation::Avg => values.iter().sum::<f64>() / values.len() as f64,
+            Aggregation::Sum => values.iter().sum(),
+            Aggregation::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregation::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Aggregation::Count => values.len() as f64,
+            Aggregation::First => values.first().copied().unwrap_or_default(),
+            Aggregation::Last => values.last().copied().unwrap_or_default(),
+        }
+    }
+
+    fn id(self) -> u8 {
+        self as u8
+    }
+
+    fn from_id(id: u8) -> Option<Aggregation> {
+        [
+            Aggregation::Avg,
+            Aggregation::Sum,
+            Aggregation::Min,
+            Aggregation::Max,
+            Aggregation::Count,
+            Aggregation::First,
+            Aggregation::Last,
+        ]
+        .get(id as usize)
+        .copied()
+    }
+}
+
+// Buckets of `duration` milliseconds starting at multiples of it from `align`.
+#[derive(Debug, Clone, Copy)]
+pub struct Bucketing {
+    pub aggregation: Aggregation,
+    pub duration: u64,
+    pub align: u64,
+}
+
+impl Bucketing {
+    pub fn bucket_start(&self, timestamp: u64) -> u64 {
+        let offset = (timestamp as i128 - self.align as i128).rem_euclid(self.duration as i128);
+        timestamp - offset as u64
+    }
+
+    // Folds the samples, sorted by timestamp, into one sample per bucket
+    // stamped with the bucket start.
+    pub fn aggregate(&self, samples: &[(u64, f64)]) -> Vec<(u64, f64)> {
+        let mut output = vec![];
+        let mut values = vec![];
+        let mut current = None;
+
+        for &(timestamp, value) in samples {
+            let bucket = self.bucket_start(timestamp);
+            if current.is_some_and(|current| current != bucket) {
+                output.extend(current.map(|start| (start, self.aggregation.apply(&values))));
+                values.clear();
+            }
+            current = Some(bucket);
+            values.push(value);
+        }
+        output.extend(current.map(|start| (start, self.aggregation.apply(&values))));
+        output
+    }
+}
+
+// Downsamples every sample added to the series into the `dest` series.
+#[derive(Debug, Clone)]
+pub struct CompactionRule {
+    pub dest: String,
+    pub bucketing: Bucketing,
+    // Start of the bucket still receiving samples.
+    current_bucket: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TimeSeries {
+    pub retention: u64,
+    pub duplicate_policy: DuplicatePolicy,
+    pub labels: Vec<(String, String)>,
+    pub source: Option<String>,
+    pub rules: Vec<CompactionRule>,
+    chunks: Vec<Chunk>,
+}
+
+impl TimeSeries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    pub fn last_timestamp(&self) -> Option<u64> {
+        self.chunks.last().map(|chunk| chunk.last_timestamp())
+    }
+
+    pub fn label(&self, name: &str) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|(label, _)| label == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn add_rule(&mut self, dest: String, bucketing: Bucketing) {
+        self.rules.push(CompactionRule {
+            dest,
+            bucketing,
+            current_bucket: None,
+        });
+    }
+
+    // Inserts a sample, resolving an existing one at the same timestamp with
+    // `policy`, and drops whatever falls out of the retention window.
+    pub fn add(
+        &mut self,
+        timestamp: u64,
+        value: f64,
+        policy: Option<DuplicatePolicy>,
+    ) -> Result<(), &'static str> {
+        if let Some(last) = self.last_timestamp() {
+            if self.retention > 0 && timestamp < last.saturating_sub(self.retention) {
+                return Err(RETENTION_ERROR);
+            }
+        }
+
+        match self.chunks.last_mut() {
+            Some(chunk) if timestamp > chunk.last_timestamp() && chunk.len() < CHUNK_SAMPLES => {
+                chunk.push(timestamp, value)
+            }
+            Some(chunk) if timestamp > chunk.last_timestamp() => {
+                self.chunks.push(Chunk::from_samples(&[(timestamp, value)]))
+            }
+            Some(_) => self.upsert(timestamp, value, policy)?,
+            None => self.chunks.push(Chunk::from_samples(&[(timestamp, value)])),
+        }
+
+        self.trim();
+        Ok(())
+    }
+
+    // Rewrites the chunk covering an out of order timestamp.
+    fn upsert(
+        &mut self,
+        timestamp: u64,
+        value: f64,
+        policy: Option<DuplicatePolicy>,
+    ) -> Result<(), &'static str> {
+        let idx = self
+            .chunks
+            .partition_point(|chunk| chunk.first_timestamp() <= timestamp)
+            .saturating_sub(1);
+        let mut samples = self.chunks[idx].samples();
+
+        match samples.binary_search_by_key(&timestamp, |(timestamp, _)| *timestamp) {
+            Ok(pos) => {
+                let policy = policy.unwrap_or(self.duplicate_policy);
+                samples[pos].1 = policy.resolve(samples[pos].1, value)?;
+            }
+            Err(pos) => samples.insert(pos, (timestamp, value)),
+        }
+
+        let chunks = samples
+            .chunks(CHUNK_SAMPLES)
+            .map(Chunk::from_samples)
+            .collect::<Vec<Chunk>>();
+        self.chunks.splice(idx..=idx, chunks);
+        Ok(())
+    }
+
+    fn trim(&mut self) {
+        let Some(last) = self.last_timestamp() else {
+            return;
+        };
+        if self.retention == 0 {
+            return;
+        }
+
+        let cutoff = last.saturating_sub(self.retention);
+        self.chunks.retain(|chunk| chunk.last_timestamp() >= cutoff);
+        if let Some(first) = self.chunks.first_mut() {
+            if first.first_timestamp() < cutoff {
+                let samples = first.samples();
+                let kept = samples.partition_point(|(timestamp, _)| *timestamp < cutoff);
+                *first = Chunk::from_samples(&samples[kept..]);
+            }
+        }
+    }
+
+    // Samples with `from <= timestamp <= to`, oldest first.
+    pub fn range(&self, from: u64, to: u64) -> Vec<(u64, f64)> {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.last_timestamp() >= from && chunk.first_timestamp() <= to)
+            .flat_map(|chunk| chunk.samples())
+            .filter(|(timestamp, _)| (from..=to).contains(timestamp))
+            .collect()
+    }
+
+    // Advances every rule past a sample added at `timestamp` and returns the
+    // samples to upsert in each destination: closed buckets once a sample
+    // lands past them, and older buckets again when late samples change them.
+    pub fn compactions(&mut self, timestamp: u64) -> Vec<(String, u64, f64)> {
+        let mut output = vec![];
+        let mut rules = std::mem::take(&mut self.rules);
+
+        for rule in rules.iter_mut() {
+            let bucket = rule.bucketing.bucket_start(timestamp);
+            let closed = match rule.current_bucket {
+                Some(current) if bucket > current => {
+                    rule.current_bucket = Some(bucket);
+                    Some(current)
+                }
+                Some(current) if bucket < current => Some(bucket),
+                Some(_) => None,
+                None => {
+                    rule.current_bucket = Some(bucket);
+                    None
+                }
+            };
+
+            let Some(start) = closed else {
+                continue;
+            };
+            let end = start.saturating_add(rule.bucketing.duration - 1);
+            let samples = self.range(start, end);
+            if let Some(&(_, value)) = rule.bucketing.aggregate(&samples).first() {
+                output.push((rule.dest.to_owned(), start, value));
+            }
+        }
+
+        self.rules = rules;
+        output
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&self.retention.to_le_bytes());
+        bytes.push(self.duplicate_policy as u8);
+        bytes.extend_from_slice(&(self.labels.len() as u32).to_le_bytes());
+        for (label, value) in &self.labels {
+            write_str(&mut bytes, label);
+            write_str(&mut bytes, value);
+        }
+        write_str(&mut bytes, self.source.as_deref().unwrap_or_default());
+        bytes.extend_from_slice(&(self.rules.len() as u32).to_le_bytes());
+        for rule in &self.rules {
+            write_str(&mut bytes, &rule.dest);
+            bytes.push(rule.bucketing.aggregation.id());
+            bytes.extend_from_slice(&rule.bucketing.duration.to_le_bytes());
+            bytes.extend_from_slice(&rule.bucketing.align.to_le_bytes());
+            bytes.extend_from_slice(&rule.current_bucket.unwrap_or(u64::MAX).to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.chunks.len() as u32).to_le_bytes());
+        for chunk in &self.chunks {
+            bytes.extend_from_slice(&chunk.to_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RedisError> {
+        Self::decode(bytes).ok_or(RedisError::RDBInvalidTimeSeries)
+    }
+
+    fn decode(mut bytes: &[u8]) -> Option<Self> {
+        let bytes = &mut bytes;
+        let mut series = TimeSeries::new();
+        series.retention = u64::from_le_bytes(utils::take_bytes(bytes)?);
+        series.duplicate_policy = match utils::take_bytes::<1>(bytes)?[0] {
+            0 => DuplicatePolicy::Block,
+            1 => DuplicatePolicy::First,
+            2 => DuplicatePolicy::Last,
+            3 => DuplicatePolicy::Min,
+            4 => DuplicatePolicy::Max,
+            5 => DuplicatePolicy::Sum,
+            _ => return None,
+        };
+
+        for _ in 0..u32::from_le_bytes(utils::take_bytes(bytes)?) {
+            series.labels.push((read_str(bytes)?, read_str(bytes)?));
+        }
+        series.source = Some(read_str(bytes)?).filter(|source| !source.is_empty());
+
+        for _ in 0..u32::from_le_bytes(utils::take_bytes(bytes)?) {
+            let dest = read_str(bytes)?;
+            let aggregation = Aggregation::from_id(utils::take_bytes::<1>(bytes)?[0])?;
+            let duration = u64::from_le_bytes(utils::take_bytes(bytes)?);
+            let align = u64::from_le_bytes(utils::take_bytes(bytes)?);
+            let current_bucket = u64::from_le_bytes(utils::take_bytes(bytes)?);
+            if duration == 0 {
+                return None;
+            }
+            series.rules.push(CompactionRule {
+                dest,
+                bucketing: Bucketing {
+                    aggregation,
+                    duration,
+                    align,
+                },
+                current_bucket: (current_bucket != u64::MAX).then_some(current_bucket),
+            });
+        }
+
+        for _ in 0..u32::from_le_bytes(utils::take_bytes(bytes)?) {
+            series.chunks.push(Chunk::from_bytes(bytes)?);
+        }
+        bytes.is_empty().then_some(series)
+    }
+}
+
+fn write_str(bytes: &mut Vec<u8>, value: &str) {
+    let value = utils::string_to_bytes(value);
+    bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&value);
+}
+
+fn read_str(bytes: &mut &[u8]) -> Option<String> {
+    let len = u32::from_le_bytes(utils::take_bytes(bytes)?) as usize;
+    if bytes.len() < len {
+        return None;
+    }
+
+    let (value, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Some(utils::bytes_to_string(value))
+}
@@ -1,15 +1,73 @@
-use super::stream_entry::StreamEntry;
+use std::collections::VecDeque;
+
+use super::{
+    bloom_filter::BloomFilter, cuckoo_filter::CuckooFilter, hash::RedisHash, json::JsonValue,
+    set::RedisSet, sorted_set::SortedSet, stream::RedisStream, time_series::TimeSeries,
+    vector_set::VectorSet,
+};
 
 #[derive(Debug, Clone)]
 pub enum ValueContainer {
     String(String),
-    Stream(Vec<StreamEntry>),
+    Stream(RedisStream),
     Integer(i64),
     Array(Vec<ValueContainer>),
+    List(VecDeque<String>),
+    Hash(RedisHash),
+    Set(RedisSet),
+    SortedSet(SortedSet),
+    Json(JsonValue),
+    BloomFilter(BloomFilter),
+    CuckooFilter(CuckooFilter),
+    TimeSeries(TimeSeries),
+    VectorSet(VectorSet),
     Boolean(bool),
     Null,
 }
 
+// A type held by its own ValueContainer variant, so the keyspace helpers can
+// look values up by type.
+pub trait TypedValue: Sized {
+    fn from_value(value: &ValueContainer) -> Option<&Self>;
+    fn from_value_mut(value: &mut ValueContainer) -> Option<&mut Self>;
+    fn into_value(self) -> ValueContainer;
+}
+
+macro_rules! typed_value {
+    ($variant:ident, $type:ty) => {
+        impl TypedValue for $type {
+            fn from_value(value: &ValueContainer) -> Option<&Self> {
+                match value {
+                    ValueContainer::$variant(inner) => Some(inner),
+                    _ => None,
+                }
+            }
+
+            fn from_value_mut(value: &mut ValueContainer) -> Option<&mut Self> {
+                match value {
+                    ValueContainer::$variant(inner) => Some(inner),
+                    _ => None,
+                }
+            }
+
+            fn into_value(self) -> ValueContainer {
+                ValueContainer::$variant(self)
+            }
+        }
+    };
+}
+
+typed_value!(Stream, RedisStream);
+typed_value!(List, VecDeque<String>);
+typed_value!(Hash, RedisHash);
+typed_value!(Set, RedisSet);
+typed_value!(SortedSet, SortedSet);
+typed_value!(Json, JsonValue);
+typed_value!(BloomFilter, BloomFilter);
+typed_value!(CuckooFilter, CuckooFilter);
+typed_value!(TimeSeries, TimeSeries);
+typed_value!(VectorSet, VectorSet);
+
 impl Into<String> for ValueContainer {
     fn into(self) -> String {
         to_string(&self)
@@ -29,6 +87,7 @@ fn to_string(container: &ValueContainer) -> String {
         ValueContainer::Boolean(b) => b.to_string(),
         ValueContainer::Null => "null".to_owned(),
         ValueContainer::Stream(a) => a
+            .entries()
             .iter()
             .map(|x| x.into())
             .collect::<Vec<String>>()
@@ -38,5 +97,8 @@ fn to_string(container: &ValueContainer) -> String {
             .map(|x| to_string(x))
             .collect::<Vec<String>>()
             .join(", "),
+        // Only strings are read as strings: GET and the other string
+        // commands reply WRONGTYPE for every other type.
+        _ => String::new(),
     }
 }
@@ -0,0 +1,622 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use crate::utils;
+
+use super::redis_error::RedisError;
+
+pub const DEFAULT_M: usize = 16;
+pub const DEFAULT_EF_CONSTRUCTION: usize = 200;
+pub const DEFAULT_EF_SEARCH: usize = 100;
+// Sets this small are searched exhaustively, which is exact and cheap enough.
+const BRUTE_FORCE_LIMIT: usize = 256;
+const MAX_LEVEL: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    Cosine,
+    L2,
+}
+
+impl Metric {
+    pub fn parse(value: &str) -> Option<Metric> {
+        match value.to_uppercase().as_str() {
+            "COSINE" => Some(Metric::Cosine),
+            "L2" => Some(Metric::L2),
+            _ => None,
+        }
+    }
+
+    // Cosine vectors are normalized on insert, so their distance is 1 - dot.
+    // L2 distances stay squared until they are reported.
+    fn distance(&self, a: &[f32], b: impl Iterator<Item = f32>) -> f32 {
+        match self {
+            Metric::Cosine => 1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>(),
+            Metric::L2 => a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum(),
+        }
+    }
+
+    // What VSIM reports: similarity in [0, 1] for cosine, distance for L2.
+    pub fn score(&self, distance: f32) -> f64 {
+        match self {
+            Metric::Cosine => (1.0 - distance as f64 / 2.0).clamp(0.0, 1.0),
+            Metric::L2 => (distance as f64).sqrt(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantization {
+    NoQuant,
+    Int8,
+}
+
+#[derive(Debug, Clone)]
+enum StoredVector {
+    F32(Vec<f32>),
+    // Each component is `value * scale`.
+    Int8 { values: Vec<i8>, scale: f32 },
+}
+
+impl StoredVector {
+    fn new(vector: Vec<f32>, quantization: Quantization) -> Self {
+        match quantization {
+            Quantization::NoQuant => StoredVector::F32(vector),
+            Quantization::Int8 => {
+                let max = vector.iter().fold(0f32, |max, x| max.max(x.abs()));
+                let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+                let values = vector
+                    .iter()
+                    .map(|x| (x / scale).round().clamp(-127.0, 127.0) as i8)
+                    .collect();
+                StoredVector::Int8 { values, scale }
+            }
+        }
+    }
+
+    fn to_vec(&self) -> Vec<f32> {
+        match self {
+            StoredVector::F32(values) => values.clone(),
+            StoredVector::Int8 { values, scale } => {
+                values.iter().map(|x| *x as f32 * scale).collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    element: String,
+    vector: StoredVector,
+    attributes: Option<String>,
+    // Neighbour ids for every layer the node lives on, bottom layer first.
+    neighbors: Vec<Vec<usize>>,
+}
+
+// Distance paired with a node id, ordered by distance for the search heaps.
+#[derive(Debug, Clone, Copy)]
+struct Candidate(f32, usize);
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+// Vectors of a fixed dimension indexed with an HNSW graph: every node lives
+// on a random number of layers, each sparser than the one below, and a
+// search walks greedily down from the top layer to the full bottom one.
+#[derive(Debug, Clone)]
+pub struct VectorSet {
+    metric: Metric,
+    quantization: Quantization,
+    dim: usize,
+    m: usize,
+    ef_construction: usize,
+    // Removed nodes leave a hole that the next insert reuses.
+    nodes: Vec<Option<Node>>,
+    free: Vec<usize>,
+    ids: HashMap<String, usize>,
+    entry_point: Option<usize>,
+}
+
+impl VectorSet {
+    pub fn new(
+        metric: Metric,
+        quantization: Quantization,
+        dim: usize,
+        m: usize,
+        ef_construction: usize,
+    ) -> Self {
+        Self {
+            metric,
+            quantization,
+            dim,
+            m,
+            ef_construction,
+            nodes: vec![],
+            free: vec![],
+            ids: HashMap::new(),
+            entry_point: None,
+        }
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn quantization(&self) -> Quantization {
+        self.quantization
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, element: &str) -> bool {
+        self.ids.contains_key(element)
+    }
+
+    pub fn vector(&self, element: &str) -> Option<Vec<f32>> {
+        self.node(*self.ids.get(element)?)
+            .map(|node| node.vector.to_vec())
+    }
+
+    pub fn attributes(&self, element: &str) -> Option<&String> {
+        self.node(*self.ids.get(element)?)?.attributes.as_ref()
+    }
+
+    // Returns false when the element does not exist.
+    pub fn set_attributes(&mut self, element: &str, attributes: Option<String>) -> bool {
+        let Some(&id) = self.ids.get(element) else {
+            return false;
+        };
+        if let Some(node) = self.nodes[id].as_mut() {
+            node.attributes = attributes;
+        }
+        true
+    }
+
+    // Adds the element, or moves it when it already exists. Returns whether
+    // it was new.
+    pub fn add(&mut self, element: &str, vector: Vec<f32>, attributes: Option<String>) -> bool {
+        let vector = StoredVector::new(self.normalize(vector), self.quantization);
+        match self.ids.get(element) {
+            Some(&id) => {
+                let previous = self.nodes[id]
+                    .as_ref()
+                    .and_then(|node| node.attributes.clone());
+                self.remove(element);
+                self.insert(element.to_owned(), vector, attributes.or(previous));
+                false
+            }
+            None => {
+                self.insert(element.to_owned(), vector, attributes);
+                true
+            }
+        }
+    }
+
+    // Cosine compares directions only, so those vectors are kept unit length.
+    fn normalize(&self, mut vector: Vec<f32>) -> Vec<f32> {
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if self.metric == Metric::Cosine && norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+        vector
+    }
+
+    fn node(&self, id: usize) -> Option<&Node> {
+        self.nodes.get(id).and_then(|node| node.as_ref())
+    }
+
+    fn max_neighbors(&self, layer: usize) -> usize {
+        match layer {
+            0 => self.m * 2,
+            _ => self.m,
+        }
+    }
+
+    fn distance(&self, query: &[f32], id: usize) -> f32 {
+        match self.node(id).map(|node| &node.vector) {
+            Some(StoredVector::F32(values)) => self.metric.distance(query, values.iter().copied()),
+            Some(StoredVector::Int8 { values, scale }) => self
+                .metric
+                .distance(query, values.iter().map(|x| *x as f32 * scale)),
+            None => f32::INFINITY,
+        }
+    }
+
+    fn random_level(&self) -> usize {
+        let uniform = (utils::random_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        let level = -(1.0 - uniform).ln() / (self.m.max(2) as f64).ln();
+        (level as usize).min(MAX_LEVEL)
+    }
+
+    fn top_layer(&self) -> usize {
+        self.entry_point
+            .and_then(|id| self.node(id))
+            .map_or(0, |node| node.neighbors.len() - 1)
+    }
+
+    fn insert(&mut self, element: String, vector: StoredVector, attributes: Option<String>) {
+        let level = self.random_level();
+        let query = vector.to_vec();
+        let node = Node {
+            element: element.to_owned(),
+            vector,
+            attributes,
+            neighbors: vec![vec![]; level + 1],
+        };
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = Some(node);
+                id
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.ids.insert(element, id);
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(id);
+            return;
+        };
+
+        let top_layer = self.top_layer();
+        let mut entry_points = vec![entry_point];
+        for layer in (level + 1..=top_layer).rev() {
+            entry_points = self.closest(&query, &entry_points, 1, layer);
+        }
+
+        for layer in (0..=level.min(top_layer)).rev() {
+            let candidates = self.search_layer(&query, &entry_points, self.ef_construction, layer);
+            let neighbors: Vec<usize> = candidates
+                .iter()
+                .filter(|candidate| candidate.1 != id)
+                .take(self.max_neighbors(layer))
+                .map(|candidate| candidate.1)
+                .collect();
+
+            for &neighbor in &neighbors {
+                self.connect(neighbor, id, layer);
+            }
+            if let Some(node) = self.nodes[id].as_mut() {
+                node.neighbors[layer] = neighbors;
+            }
+            entry_points = candidates
+                .into_iter()
+                .map(|candidate| candidate.1)
+                .collect();
+        }
+
+        if level > top_layer {
+            self.entry_point = Some(id);
+        }
+    }
+
+    // Links `from` to `to`, keeping only the closest neighbours of `from`
+    // when its list overflows.
+    fn connect(&mut self, from: usize, to: usize, layer: usize) {
+        let max_neighbors = self.max_neighbors(layer);
+        let Some(neighbors) = self.nodes[from]
+            .as_ref()
+            .and_then(|node| node.neighbors.get(layer))
+        else {
+            return;
+        };
+        if neighbors.contains(&to) || from == to {
+            return;
+        }
+
+        let mut neighbors = neighbors.clone();
+        neighbors.push(to);
+        if neighbors.len() > max_neighbors {
+            let origin = self
+                .node(from)
+                .map(|node| node.vector.to_vec())
+                .unwrap_or_default();
+            let mut ranked: Vec<Candidate> = neighbors
+                .iter()
+                .map(|&id| Candidate(self.distance(&origin, id), id))
+                .collect();
+            ranked.sort();
+            neighbors = ranked
+                .into_iter()
+                .take(max_neighbors)
+                .map(|candidate| candidate.1)
+                .collect();
+        }
+
+        if let Some(node) = self.nodes[from].as_mut() {
+            node.neighbors[layer] = neighbors;
+        }
+    }
+
+    pub fn remove(&mut self, element: &str) -> bool {
+        let Some(id) = self.ids.remove(element) else {
+            return false;
+        };
+        let Some(removed) = self.nodes[id].take() else {
+            return false;
+        };
+        self.free.push(id);
+
+        // Every node that linked to the removed one gets its neighbours as
+        // candidates instead, so the graph stays connected around the hole.
+        for other in 0..self.nodes.len() {
+            let Some(node) = self.nodes[other].as_mut() else {
+                continue;
+            };
+            let mut lost = vec![];
+            for (layer, neighbors) in node.neighbors.iter_mut().enumerate() {
+                if let Some(pos) = neighbors.iter().position(|&n| n == id) {
+                    neighbors.remove(pos);
+                    lost.push(layer);
+                }
+            }
+            for layer in lost {
+                for &candidate in removed.neighbors.get(layer).into_iter().flatten() {
+                    if candidate != id {
+                        self.connect(other, candidate, layer);
+                    }
+                }
+            }
+        }
+
+        if self.entry_point == Some(id) {
+            self.entry_point = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter_map(|(id, node)| node.as_ref().map(|node| (node.neighbors.len(), id)))
+                .max()
+                .map(|(_, id)| id);
+        }
+        if self.ids.is_empty() {
+            self.nodes.clear();
+            self.free.clear();
+        }
+        true
+    }
+
+    // The `count` elements closest to `query` with their distance, nearest
+    // first.
+    pub fn search(&self, query: &[f32], count: usize, ef: usize) -> Vec<(&str, f32)> {
+        // Queries are compared at full precision even against int8 vectors.
+        let query = self.normalize(query.to_vec());
+
+        let candidates = match (self.entry_point, self.len() <= BRUTE_FORCE_LIMIT) {
+            (None, _) => vec![],
+            (Some(_), true) => {
+                let mut candidates: Vec<Candidate> = self
+                    .ids
+                    .values()
+                    .map(|&id| Candidate(self.distance(&query, id), id))
+                    .collect();
+                candidates.sort();
+                candidates
+            }
+            (Some(entry_point), false) => {
+                let mut entry_points = vec![entry_point];
+                for layer in (1..=self.top_layer()).rev() {
+                    entry_points = self.closest(&query, &entry_points, 1, layer);
+                }
+                self.search_layer(&query, &entry_points, ef.max(count), 0)
+            }
+        };
+
+        candidates
+            .into_iter()
+            .take(count)
+            .filter_map(|Candidate(distance, id)| {
+                self.node(id).map(|node| (node.element.as_str(), distance))
+            })
+            .collect()
+    }
+
+    fn closest(
+        &self,
+        query: &[f32],
+        entry_points: &[usize],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<usize> {
+        self.search_layer(query, entry_points, ef, layer)
+            .into_iter()
+            .map(|candidate| candidate.1)
+            .collect()
+    }
+
+    // Best-first search of one layer, keeping the `ef` closest nodes seen.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[usize],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        // Min-heap of nodes to expand and max-heap of the best results.
+        let mut pending: BinaryHeap<std::cmp::Reverse<Candidate>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+        for &id in entry_points {
+            let candidate = Candidate(self.distance(query, id), id);
+            pending.push(std::cmp::Reverse(candidate));
+            results.push(candidate);
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(std::cmp::Reverse(current)) = pending.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|worst| current.0 > worst.0) {
+                break;
+            }
+            let Some(neighbors) = self
+                .node(current.1)
+                .and_then(|node| node.neighbors.get(layer))
+            else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate(self.distance(query, neighbor), neighbor);
+                if results.len() < ef || results.peek().is_some_and(|worst| candidate < *worst) {
+                    pending.push(std::cmp::Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        results.into_sorted_vec()
+    }
+
+    // The graph is not stored: loading inserts every vector again.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.push(match self.metric {
+            Metric::Cosine => 0,
+            Metric::L2 => 1,
+        });
+        bytes.push(match self.quantization {
+            Quantization::NoQuant => 0,
+            Quantization::Int8 => 1,
+        });
+        bytes.extend_from_slice(&(self.dim as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.m as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.ef_construction as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.len() as u32).to_le_bytes());
+
+        for node in self.nodes.iter().flatten() {
+            write_string(&mut bytes, Some(&node.element));
+            write_string(&mut bytes, node.attributes.as_ref());
+            match &node.vector {
+                StoredVector::F32(values) => {
+                    values
+                        .iter()
+                        .for_each(|x| bytes.extend_from_slice(&x.to_le_bytes()));
+                }
+                StoredVector::Int8 { values, scale } => {
+                    bytes.extend_from_slice(&scale.to_le_bytes());
+                    bytes.extend(values.iter().map(|x| *x as u8));
+                }
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RedisError> {
+        Self::decode(bytes).ok_or(RedisError::RDBInvalidVectorSet)
+    }
+
+    fn decode(mut bytes: &[u8]) -> Option<Self> {
+        let bytes = &mut bytes;
+        let metric = match utils::take_bytes::<1>(bytes)? {
+            [0] => Metric::Cosine,
+            [1] => Metric::L2,
+            _ => return None,
+        };
+        let quantization = match utils::take_bytes::<1>(bytes)? {
+            [0] => Quantization::NoQuant,
+            [1] => Quantization::Int8,
+            _ => return None,
+        };
+        let dim = u32::from_le_bytes(utils::take_bytes(bytes)?) as usize;
+        let m = u32::from_le_bytes(utils::take_bytes(bytes)?) as usize;
+        let ef_construction = u32::from_le_bytes(utils::take_bytes(bytes)?) as usize;
+        let len = u32::from_le_bytes(utils::take_bytes(bytes)?);
+        if dim == 0 || m == 0 {
+            return None;
+        }
+
+        let mut set = VectorSet::new(metric, quantization, dim, m, ef_construction);
+        for _ in 0..len {
+            let element = read_string(bytes)??;
+            let attributes = read_string(bytes)?;
+            let vector = match quantization {
+                Quantization::NoQuant => StoredVector::F32(
+                    (0..dim)
+                        .map(|_| Some(f32::from_le_bytes(utils::take_bytes(bytes)?)))
+                        .collect::<Option<_>>()?,
+                ),
+                Quantization::Int8 => {
+                    let scale = f32::from_le_bytes(utils::take_bytes(bytes)?);
+                    if bytes.len() < dim {
+                        return None;
+                    }
+                    let (values, rest) = bytes.split_at(dim);
+                    *bytes = rest;
+                    StoredVector::Int8 {
+                        values: values.iter().map(|x| *x as i8).collect(),
+                        scale,
+                    }
+                }
+            };
+            if set.contains(&element) {
+                return None;
+            }
+            set.insert(element, vector, attributes);
+        }
+
+        match bytes.is_empty() {
+            true => Some(set),
+            false => None,
+        }
+    }
+}
+
+// Strings are length prefixed, with 0 for none and the length + 1 otherwise.
+fn write_string(bytes: &mut Vec<u8>, value: Option<&String>) {
+    match value {
+        Some(value) => {
+            let value = utils::string_to_bytes(value);
+            bytes.extend_from_slice(&(value.len() as u32 + 1).to_le_bytes());
+            bytes.extend_from_slice(&value);
+        }
+        None => bytes.extend_from_slice(&0u32.to_le_bytes()),
+    }
+}
+
+fn read_string(bytes: &mut &[u8]) -> Option<Option<String>> {
+    let len = u32::from_le_bytes(utils::take_bytes(bytes)?) as usize;
+    if len == 0 {
+        return Some(None);
+    }
+    if bytes.len() < len - 1 {
+        return None;
+    }
+    let (value, rest) = bytes.split_at(len - 1);
+    *bytes = rest;
+    Some(Some(utils::bytes_to_string(value)))
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    sync::Mutex,
+};
+
+use tokio::sync::oneshot;
+
+use crate::resp_desserializer::RespTk;
+
+use super::keyspace::Keyspace;
+
+pub type WaiterId = u64;
+
+// Tries to serve a blocked client from `key`, returning None while the key
+// has nothing for it.
+pub type WaiterHandler = Box<dyn FnMut(&mut Keyspace, &str) -> Option<WaiterOutcome> + Send>;
+
+pub struct WaiterOutcome {
+    pub response: String,
+    pub commands: Vec<RespTk>,
+    pub ready_keys: Vec<String>,
+}
+
+struct Waiter {
+    keys: Vec<String>,
+    handler: WaiterHandler,
+    sender: oneshot::Sender<String>,
+}
+
+pub struct WaiterRegistry {
+    next_id: WaiterId,
+    queues: HashMap<String, VecDeque<WaiterId>>,
+    waiters: HashMap<WaiterId, Waiter>,
+}
+
+impl WaiterRegistry {
+    pub fn new() -> Self {
+        Self {
+            next_id: 0,
+            queues: HashMap::new(),
+            waiters: HashMap::new(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        keys: Vec<String>,
+        handler: WaiterHandler,
+    ) -> (WaiterId, oneshot::Receiver<String>) {
+        let (sender, receiver) = oneshot::channel();
+        let id = self.next_id;
+        self.next_id += 1;
+
+        for key in &keys {
+            self.queues.entry(key.to_owned()).or_default().push_back(id);
+        }
+        self.waiters.insert(
+            id,
+            Waiter {
+                keys,
+                handler,
+                sender,
+            },
+        );
+
+        (id, receiver)
+    }
+
+    pub fn remove(&mut self, id: WaiterId) {
+        self.take(id);
+    }
+
+    // Serves the clients blocked on `key` in arrival order, following any keys
+    // they make ready in turn. Returns the commands to replicate.
+    pub fn serve(&mut self, mem: &mut Keyspace, key: &str) -> Vec<RespTk> {
+        let mut commands = Vec::new();
+        let mut ready = VecDeque::from([key.to_owned()]);
+
+        while let Some(key) = ready.pop_front() {
+            let ids: Vec<WaiterId> = match self.queues.get(&key) {
+                Some(queue) => queue.iter().copied().collect(),
+                None => continue,
+            };
+
+            for id in ids {
+                let Some(waiter) = self.waiters.get_mut(&id) else {
+                    continue;
+                };
+
+                // Receivers are dropped under the registry lock, so an open
+                // sender here is still open once the handler has popped data
+                // for it and the data cannot be lost.
+                if waiter.sender.is_closed() {
+                    self.take(id);
+                    continue;
+                }
+
+                // Waiters for another type may still be served after this one.
+                let Some(outcome) = (waiter.handler)(mem, &key) else {
+                    continue;
+                };
+
+                if let Some(waiter) = self.take(id) {
+                    let _ = waiter.sender.send(outcome.response);
+                }
+                commands.extend(outcome.commands);
+                ready.extend(outcome.ready_keys);
+            }
+        }
+
+        commands
+    }
+
+    fn take(&mut self, id: WaiterId) -> Option<Waiter> {
+        let waiter = self.waiters.remove(&id)?;
+        for key in &waiter.keys {
+            if let Some(queue) = self.queues.get_mut(key) {
+                queue.retain(|waiting| *waiting != id);
+                if queue.is_empty() {
+                    self.queues.remove(key);
+                }
+            }
+        }
+        Some(waiter)
+    }
+}
+
+impl Default for WaiterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for WaiterRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaiterRegistry")
+            .field("waiters", &self.waiters.len())
+            .finish()
+    }
+}
+
+// Unregisters a waiter when the blocked command finishes or is dropped.
+// The receiver is only dropped under the registry lock, so a waiter the
+// registry still sees as open is never handed a response it would lose.
+pub struct WaiterGuard<'a> {
+    registry: &'a Mutex<WaiterRegistry>,
+    id: WaiterId,
+    receiver: Option<oneshot::Receiver<String>>,
+}
+
+impl<'a> WaiterGuard<'a> {
+    pub fn new(
+        registry: &'a Mutex<WaiterRegistry>,
+        id: WaiterId,
+        receiver: oneshot::Receiver<String>,
+    ) -> Self {
+        Self {
+            registry,
+            id,
+            receiver: Some(receiver),
+        }
+    }
+
+    pub async fn recv(&mut self) -> Option<String> {
+        self.receiver.as_mut()?.await.ok()
+    }
+
+    // Unregisters the waiter, returning the response it may have been sent
+    // after the caller stopped waiting.
+    pub fn finish(mut self) -> Option<String> {
+        self.release()
+    }
+
+    fn release(&mut self) -> Option<String> {
+        let mut registry = self.registry.lock().unwrap_or_else(|err| err.into_inner());
+        registry.remove(self.id);
+        let mut receiver = self.receiver.take()?;
+        receiver.try_recv().ok()
+    }
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
@@ -1,4 +1,8 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    collections::hash_map::{DefaultHasher, RandomState},
+    hash::{BuildHasher, Hasher},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub fn get_current_time_ms() -> u128 {
     let start = SystemTime::now();
@@ -7,3 +11,197 @@ pub fn get_current_time_ms() -> u128 {
         .expect("Time went backwards");
     since_the_epoch.as_millis()
 }
+
+pub fn bytes_to_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| *b as char).collect()
+}
+
+pub fn string_to_bytes(value: &str) -> Vec<u8> {
+    value.chars().map(|c| c as u8).collect()
+}
+
+pub fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+pub fn random_index(len: usize) -> usize {
+    (random_u64() % len as u64) as usize
+}
+
+// Picks `count` distinct indexes below `len`, or all of them when fewer exist.
+pub fn sample_distinct(len: usize, count: usize) -> Vec<usize> {
+    let mut indexes: Vec<usize> = (0..len).collect();
+    if count >= len {
+        return indexes;
+    }
+
+    for idx in 0..count {
+        let swap = idx + random_index(len - idx);
+        indexes.swap(idx, swap);
+    }
+    indexes.truncate(count);
+    indexes
+}
+
+// Glob-style matching with the same rules as Redis' stringmatchlen.
+pub fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    glob_match_chars(&pattern, &value)
+}
+
+fn glob_match_chars(mut pattern: &[char], mut value: &[char]) -> bool {
+    while let Some(&current) = pattern.first() {
+        match current {
+            '*' => {
+                while pattern.get(1) == Some(&'*') {
+                    pattern = &pattern[1..];
+                }
+                if pattern.len() == 1 {
+                    return true;
+                }
+                return (0..=value.len()).any(|idx| glob_match_chars(&pattern[1..], &value[idx..]));
+            }
+            '?' => {
+                if value.is_empty() {
+                    return false;
+                }
+                value = &value[1..];
+            }
+            '[' => {
+                let Some(&target) = value.first() else {
+                    return false;
+                };
+
+                let negate = pattern.get(1) == Some(&'^');
+                let mut idx = if negate { 2 } else { 1 };
+                let mut matched = false;
+                loop {
+                    match pattern.get(idx) {
+                        // An unterminated class matches up to the end of the pattern.
+                        None => {
+                            idx -= 1;
+                            break;
+                        }
+                        Some(']') => break,
+                        Some('\\') if idx + 1 < pattern.len() => {
+                            idx += 1;
+                            matched |= pattern[idx] == target;
+                        }
+                        Some(&start)
+                            if pattern.get(idx + 1) == Some(&'-') && idx + 2 < pattern.len() =>
+                        {
+                            let end = pattern[idx + 2];
+                            let (low, high) = if start <= end {
+                                (start, end)
+                            } else {
+                                (end, start)
+                            };
+                            matched |= (low..=high).contains(&target);
+                            idx += 2;
+                        }
+                        Some(&other) => matched |= other == target,
+                    }
+                    idx += 1;
+                }
+
+                if matched == negate {
+                    return false;
+                }
+                pattern = &pattern[idx..];
+                value = &value[1..];
+            }
+            '\\' if pattern.len() >= 2 => {
+                if value.first() != Some(&pattern[1]) {
+                    return false;
+                }
+                pattern = &pattern[1..];
+                value = &value[1..];
+            }
+            _ => {
+                if value.first() != Some(&current) {
+                    return false;
+                }
+                value = &value[1..];
+            }
+        }
+        pattern = &pattern[1..];
+    }
+
+    value.is_empty()
+}
+
+// Pages through an unordered collection by a stable hash of each member, so a
+// cursor stays valid while members are added or removed between calls.
+pub fn scan_page<'a, T>(
+    items: impl Iterator<Item = (&'a String, T)>,
+    cursor: u64,
+    count: usize,
+) -> (u64, Vec<(&'a String, T)>) {
+    let mut candidates: Vec<(u64, &String, T)> = items
+        .map(|(member, value)| (stable_hash(member), member, value))
+        .filter(|(hash, ..)| *hash >= cursor)
+        .collect();
+
+    if candidates.len() <= count {
+        return (0, candidates.into_iter().map(|(_, m, v)| (m, v)).collect());
+    }
+
+    candidates.select_nth_unstable_by_key(count - 1, |(hash, ..)| *hash);
+    let last_hash = candidates[count - 1].0;
+    let (page, rest): (Vec<_>, Vec<_>) = candidates
+        .into_iter()
+        .partition(|(hash, ..)| *hash <= last_hash);
+
+    let next_cursor = match last_hash.checked_add(1) {
+        Some(next) if !rest.is_empty() => next,
+        _ => 0,
+    };
+    (
+        next_cursor,
+        page.into_iter().map(|(_, m, v)| (m, v)).collect(),
+    )
+}
+
+fn stable_hash(value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(value.as_bytes());
+    hasher.finish()
+}
+
+pub fn murmur_hash64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4_a793_5bd1_e995;
+    const R: u32 = 47;
+
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+    let mut chunks = key.chunks_exact(8);
+
+    for chunk in chunks.by_ref() {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, byte) in tail.iter().enumerate() {
+            h ^= (*byte as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+// Splits the first `N` bytes off `bytes`, for decoding fixed width fields.
+pub fn take_bytes<const N: usize>(bytes: &mut &[u8]) -> Option<[u8; N]> {
+    let (head, rest) = bytes.split_first_chunk::<N>()?;
+    *bytes = rest;
+    Some(*head)
+}